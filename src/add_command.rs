@@ -1,14 +1,17 @@
 use crate::common_structs::{ CommandResult, ExecutableCommand, Priority};
 use crate::data_service::add_todo;
+use crate::duration_format::{parse_date_time, parse_duration};
+use crate::output::{reset_color, success_color};
 use crate::todo::Todo;
-use chrono::Utc;
-use termion::color;
+use chrono::{DateTime, Duration, Local, Utc};
 use std::error::Error;
 
 #[derive(Debug)]
 pub struct AddCommand {
     title: String,
-    priority: Priority
+    priority: Priority,
+    estimate: Option<Duration>,
+    due: Option<DateTime<Utc>>
 }
 
 impl AddCommand {
@@ -16,7 +19,7 @@ impl AddCommand {
         if title.trim().is_empty() {
             return Err("Value cannot be empty".into());
         }
-        Ok(AddCommand { title: title.to_string(), priority: priority })
+        Ok(AddCommand { title: title.to_string(), priority, estimate: None, due: None })
     }
 
     pub fn new_from_command_result(command_result: &CommandResult) -> Result<AddCommand, Box<dyn Error>> {
@@ -31,16 +34,24 @@ impl AddCommand {
             None => Priority::Low
         };
     
-        let add_command = AddCommand::new(command_result.get_value(), priority)?;
+        let mut add_command = AddCommand::new(command_result.get_value(), priority)?;
+        if let Some(estimate) = command_result.get_options().get("estimate") {
+            add_command.estimate = Some(parse_duration(estimate)?);
+        }
+        if let Some(due) = command_result.get_options().get("due") {
+            add_command.due = Some(parse_date_time(due, Local::now())?);
+        }
         Ok(add_command)
     }
 }
 
 impl ExecutableCommand for AddCommand {
     fn execute(&self) -> Result<(), Box<dyn Error>> {
-        let todo = Todo::new(0, self.title.as_str(), self.priority, Utc::now())?;
+        let mut todo = Todo::new(0, self.title.as_str(), self.priority, Utc::now())?;
+        todo.set_estimate(self.estimate)?;
+        todo.set_due(self.due);
         let id_assigned = add_todo(todo)?;
-        println!("{}The todo {} has been added with id {}{}!", color::Fg(color::Green), self.title, id_assigned, color::Fg(color::Reset));
+        println!("{}The todo {} has been added with id {}{}!", success_color(), self.title, id_assigned, reset_color());
         Ok(())
     }
 }
@@ -49,6 +60,7 @@ impl ExecutableCommand for AddCommand {
 mod tests {
     use crate::add_command::{ AddCommand, Priority };
     use crate::common_structs::{ Command, CommandResult };
+    use chrono::Duration;
     use std::collections::HashMap;
 
     #[test]
@@ -106,6 +118,24 @@ mod tests {
         assert_eq!(Priority::Low, add_command.priority);
     }
 
+    #[test]
+    fn add_command_new_from_command_with_estimate_2h_return_valid_addcommand() {
+        let command_result = CommandResult::new(Command::Add, 
+                                                "test", 
+                                                HashMap::from([(String::from("estimate"), String::from("2h"))]));
+        let add_command = AddCommand::new_from_command_result(&command_result).unwrap();
+        assert_eq!(Duration::hours(2), add_command.estimate.unwrap());
+    }
+
+    #[test]
+    fn add_command_new_from_command_with_invalid_estimate_return_error() {
+        let command_result = CommandResult::new(Command::Add, 
+                                                "test", 
+                                                HashMap::from([(String::from("estimate"), String::from("2"))]));
+        let command = AddCommand::new_from_command_result(&command_result);
+        assert_eq!("Invalid duration 2. Must be like 45m, 2h or 1h30m", command.unwrap_err().to_string());
+    }
+
     fn parse_command_with_x_priority_return_valid_addcommand(priority: Priority, letter: &str) {
         let command_result = CommandResult::new(Command::Add, 
             "test", 
@@ -0,0 +1,177 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::read_all_todos;
+use crate::list_command::{ListCommand, SortOrder};
+use crate::output::{bold, reset_style};
+use crate::todo::Todo;
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct AgendaCommand {
+    days: u32
+}
+
+// The todos of a part of the agenda (Overdue, a day or No date)
+#[derive(Debug)]
+pub struct AgendaSection {
+    title: String,
+    todos: Vec<Todo>
+}
+
+impl AgendaCommand {
+    const DEFAULT_DAYS: u32 = 7;
+    const MAX_DAYS: u32 = 366;
+
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<AgendaCommand, Box<dyn Error>> {
+        let days = match command_result.get_options().get("days") {
+            Some(value) => AgendaCommand::parse_days(value)?,
+            None => AgendaCommand::DEFAULT_DAYS
+        };
+        Ok(AgendaCommand { days })
+    }
+
+    pub fn parse_days(value: &str) -> Result<u32, Box<dyn Error>> {
+        match value.trim().parse::<u32>() {
+            Ok(days) if days > 0 && days <= AgendaCommand::MAX_DAYS => Ok(days),
+            _ => Err(format!("Invalid number of days {}. Must be between 1 and {}", value.trim(), AgendaCommand::MAX_DAYS).into())
+        }
+    }
+}
+
+// The date of the todo in the agenda, its due date or else the date it comes back when it's snoozed
+fn get_agenda_date(todo: &Todo, now: DateTime<Utc>) -> Option<NaiveDate> {
+    match todo.get_due_in_localtime() {
+        Some(due) => Some(due.date_naive()),
+        None if todo.is_waiting(now) => todo.get_wait_until_in_localtime().map(|x| x.date_naive()),
+        None => None
+    }
+}
+
+fn get_day_title(day: NaiveDate, today: NaiveDate) -> String {
+    let date = day.format("%a %Y-%m-%d");
+    match (day - today).num_days() {
+        0 => format!("Today - {}", date),
+        1 => format!("Tomorrow - {}", date),
+        _ => date.to_string()
+    }
+}
+
+// Split the open todos in the sections of the agenda, the todos completed today are added to the day.
+// Return the sections and the number of todos planned after the last day.
+pub fn get_agenda_sections(todos: &[Todo], today: NaiveDate, days: u32, now: DateTime<Utc>) -> (Vec<AgendaSection>, usize) {
+    let open_todos: Vec<&Todo> = todos.iter().filter(|x| !x.get_completed()).collect();
+    let select = |predicate: &dyn Fn(&Todo) -> bool| -> Vec<Todo> {
+        let mut selected: Vec<Todo> = todos.iter().filter(|x| predicate(x)).cloned().collect();
+        selected.sort_by_key(|x| (x.get_completed(), x.get_priority(), x.get_when_created_in_localtime()));
+        selected
+    };
+    let mut sections = vec![AgendaSection {
+        title: String::from("Overdue"),
+        todos: select(&|x| !x.get_completed() && x.get_due_in_localtime().map(|due| due.date_naive() < today).unwrap_or(false))
+    }];
+    for offset in 0..days {
+        let day = match today.checked_add_signed(Duration::days(i64::from(offset))) {
+            Some(day) => day,
+            None => break
+        };
+        sections.push(AgendaSection {
+            title: get_day_title(day, today),
+            todos: select(&|x| match x.get_completed() {
+                true => day == today && x.get_when_completed_in_localtime().date_naive() == today,
+                false => get_agenda_date(x, now) == Some(day)
+            })
+        });
+    }
+    sections.push(AgendaSection {
+        title: String::from("No date"),
+        todos: select(&|x| !x.get_completed() && get_agenda_date(x, now).is_none())
+    });
+    let last_day = today.checked_add_signed(Duration::days(i64::from(days) - 1)).unwrap_or(NaiveDate::MAX);
+    let later_count = open_todos.iter().filter(|x| get_agenda_date(x, now).map(|date| date > last_day).unwrap_or(false)).count();
+    (sections, later_count)
+}
+
+impl ExecutableCommand for AgendaCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let todos = read_all_todos()?;
+        let today = Local::now().date_naive();
+        let (sections, later_count) = get_agenda_sections(&todos, today, self.days, Utc::now());
+        let list = ListCommand::new(SortOrder::PriorityDESC);
+        for (index, section) in sections.iter().enumerate() {
+            // The days are always displayed, Overdue and No date only when they have todos
+            let is_day = index > 0 && index < sections.len() - 1;
+            if section.todos.is_empty() && !is_day {
+                continue;
+            }
+            println!("{}{}{}", bold(), section.title, reset_style());
+            match section.todos.is_empty() {
+                true => println!("Nothing planned"),
+                false => list.print_table(&section.todos, false)
+            }
+            println!();
+        }
+        // A single total for the todos of all the sections
+        let agenda_todos: Vec<Todo> = sections.into_iter().flat_map(|x| x.todos).collect();
+        list.print_estimate_total(&agenda_todos);
+        if later_count > 0 {
+            println!("{} todo(s) planned after the next {} days", later_count, self.days);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agenda_command::{get_agenda_sections, AgendaCommand};
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use crate::todo::Todo;
+    use chrono::{Local, NaiveDate, TimeZone, Utc};
+    use std::collections::HashMap;
+
+    fn get_todo(id: u32, due: Option<NaiveDate>) -> Todo {
+        let created = Local.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap().with_timezone(&Utc);
+        let mut todo = Todo::new(id, &format!("Todo {}", id), Priority::Medium, created).unwrap();
+        todo.set_due(due.map(|x| x.and_hms_opt(17, 0, 0).unwrap().and_local_timezone(Local).unwrap().with_timezone(&Utc)));
+        todo
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn get_ids(todos: &[Todo]) -> Vec<u32> {
+        todos.iter().map(|x| x.get_id()).collect()
+    }
+
+    #[test]
+    fn agenda_command_new_from_command_result_with_days_return_days() {
+        let command = AgendaCommand::new_from_command_result(&CommandResult::new(Command::Agenda, "", HashMap::new())).unwrap();
+        assert_eq!(7, command.days);
+        let command = AgendaCommand::new_from_command_result(&CommandResult::new(Command::Agenda, "",
+            HashMap::from([(String::from("days"), String::from("0"))])));
+        assert_eq!("Invalid number of days 0. Must be between 1 and 366", command.unwrap_err().to_string());
+        let command = AgendaCommand::new_from_command_result(&CommandResult::new(Command::Agenda, "",
+            HashMap::from([(String::from("days"), String::from("4000000000"))])));
+        assert_eq!("Invalid number of days 4000000000. Must be between 1 and 366", command.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn get_agenda_sections_return_todos_by_day() {
+        let now = Local.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap().with_timezone(&Utc);
+        let mut completed_today = get_todo(5, Some(date(19)));
+        completed_today.set_completed(true, Some(now));
+        let mut snoozed = get_todo(6, None);
+        snoozed.set_wait_until(Some(Local.with_ymd_and_hms(2026, 10, 20, 0, 0, 0).unwrap().with_timezone(&Utc)));
+        let todos = vec![get_todo(1, Some(date(17))), get_todo(2, Some(date(19))), get_todo(3, None),
+                         get_todo(4, Some(date(30))), completed_today, snoozed];
+        let (sections, later_count) = get_agenda_sections(&todos, date(19), 3, now);
+        let titles: Vec<&str> = sections.iter().map(|x| x.title.as_str()).collect();
+        assert_eq!(vec!["Overdue", "Today - Mon 2026-10-19", "Tomorrow - Tue 2026-10-20", "Wed 2026-10-21", "No date"], titles);
+        assert_eq!(vec![1], get_ids(&sections[0].todos));
+        assert_eq!(vec![2, 5], get_ids(&sections[1].todos));
+        assert_eq!(vec![6], get_ids(&sections[2].todos));
+        assert!(sections[3].todos.is_empty());
+        assert_eq!(vec![3], get_ids(&sections[4].todos));
+        assert_eq!(1, later_count);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::archive_todos;
+use crate::duration_format::parse_age;
+use crate::output::{reset_color, success_color};
+use chrono::{Duration, Utc};
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct ArchiveCommand {
+    older_than: Option<Duration>
+}
+
+impl ArchiveCommand {
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<ArchiveCommand, Box<dyn Error>> {
+        let older_than = match command_result.get_options().get("older-than") {
+            Some(value) => Some(parse_age(value)?),
+            None => None
+        };
+        Ok(ArchiveCommand { older_than })
+    }
+}
+
+impl ExecutableCommand for ArchiveCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let completed_before = match Utc::now().checked_sub_signed(self.older_than.unwrap_or_else(Duration::zero)) {
+            Some(completed_before) => completed_before,
+            None => return Err("The age given with --older-than is too long".into())
+        };
+        match archive_todos(completed_before)? {
+            0 => println!("There is no completed todo to archive"),
+            count => println!("{}{} completed todo(s) moved to the archive!{}", success_color(), count, reset_color())
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::archive_command::ArchiveCommand;
+    use crate::common_structs::{Command, CommandResult};
+    use chrono::Duration;
+    use std::collections::HashMap;
+
+    #[test]
+    fn archive_command_new_from_command_result_with_older_than_return_age() {
+        let command = ArchiveCommand::new_from_command_result(&CommandResult::new(Command::Archive, "", HashMap::new()));
+        assert_eq!(None, command.unwrap().older_than);
+        let command = ArchiveCommand::new_from_command_result(&CommandResult::new(Command::Archive,
+            "",
+            HashMap::from([(String::from("older-than"), String::from("30d"))])));
+        assert_eq!(Some(Duration::days(30)), command.unwrap().older_than);
+    }
+}
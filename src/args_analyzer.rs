@@ -1,164 +1,198 @@
-use crate::common_structs::Command;
+use crate::cli_definition::{get_command_definitions, CommandDefinition, OptionDefinition};
 use crate::common_structs::CommandResult;
 use std::collections::HashMap;
+use std::error::Error;
+
+// The option added to the command result when the help of a command is requested
+pub const HELP_OPTION: &str = "help";
+// The option of every command setting the colors of its output, --color alone means always
+pub const COLOR_OPTION: &str = "color";
+
+fn find_command<'a>(definitions: &'a [CommandDefinition], name: &str) -> Result<&'a CommandDefinition, Box<dyn Error>> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err("No command provided".into());
+    }
+    match definitions.iter().find(|x| x.is_named(&name)) {
+        Some(definition) => Ok(definition),
+        None => {
+            let names: Vec<&str> = definitions.iter().map(|x| x.get_name()).collect();
+            Err(format!("Unknown command {}{}", name, get_suggestion(&name, &names, "")).into())
+        }
+    }
+}
 
-fn extract_command(value: String) -> Option<Command> {
-    let trimmed_lowercase_value = value.trim().to_lowercase();
-    match trimmed_lowercase_value.as_str() {
-       "" => None,
-       "add" => Some(Command::Add),
-       "edit" => Some(Command::Edit),
-       "delete" => Some(Command::Delete),
-       "done" => Some(Command::Done),
-       "fetch" => Some(Command::Fetch),
-       "list" => Some(Command::List),
-       "next" => Some(Command::Next),
-       "purge" => Some(Command::Purge),
-       "remove" => Some(Command::Remove),
-       _ => Some(Command::Unknown)
+// Return the option matching a flag like -p or --priority
+fn find_option<'a>(definition: &'a CommandDefinition, flag: &str) -> Result<&'a OptionDefinition, Box<dyn Error>> {
+    let option = match flag.strip_prefix("--") {
+        Some(name) => definition.get_options().iter().find(|x| x.get_name() == name),
+        None => definition.get_options().iter().find(|x| x.get_short().map(|s| format!("-{}", s)).as_deref() == Some(flag))
+    };
+    match option {
+        Some(option) => Ok(option),
+        None => {
+            let names: Vec<&str> = definition.get_options().iter().map(|x| x.get_name()).collect();
+            let suggestion = get_suggestion(flag.trim_start_matches('-'), &names, "--");
+            Err(format!("Unknown option {} for {}{}", flag, definition.get_name(), suggestion).into())
+        }
     }
 }
 
-fn get_option_patterns() -> Vec<String> {
-    vec![String::from("-p="),
-         String::from("--priority="),
-         String::from("-t="),
-         String::from("--title="),
-         String::from("-s="),
-         String::from("--sort="),
-         String::from("-a"),
-         String::from("--all")]
+// Return " Did you mean x?" when a candidate is close to the value
+pub fn get_suggestion(value: &str, candidates: &[&str], prefix: &str) -> String {
+    let value = value.to_lowercase();
+    let closest = candidates.iter()
+        .map(|x| (x, if x.starts_with(&value) && !value.is_empty() { 0 } else { get_edit_distance(&value, x) }))
+        .filter(|(x, distance)| *distance <= 2 && *distance < x.len())
+        .min_by_key(|(_, distance)| *distance);
+    match closest {
+        Some((candidate, _)) => format!(". Did you mean {}{}?", prefix, candidate),
+        None => String::new()
+    }
 }
 
-fn get_option_name_from_pattern(value: &str) -> Option<String> {
-    let option_patterns = get_option_patterns();
-    let mut result: Option<String> = None;
-    for pattern in option_patterns {
-
-        if (!pattern.ends_with('=') && value == pattern) || 
-           (pattern.ends_with('=') && value.starts_with(&pattern)) {
-            result = match pattern.as_str() {
-                "-p=" => Some(String::from("priority")),
-                "--priority=" => Some(String::from("priority")),
-                "-t=" => Some(String::from("title")),
-                "--title=" => Some(String::from("title")),
-                "-s=" => Some(String::from("sort")),
-                "--sort=" => Some(String::from("sort")),
-                "-a" => Some(String::from("all")),
-                "--all" => Some(String::from("all")),
-                _ => None
-            }
+// Levenshtein distance between two words
+fn get_edit_distance(first: &str, second: &str) -> usize {
+    let second_chars: Vec<char> = second.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=second_chars.len()).collect();
+    for (i, first_char) in first.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, second_char) in second_chars.iter().enumerate() {
+            let substitution_cost = if first_char == *second_char { 0 } else { 1 };
+            let distance = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+            current_row.push(distance);
         }
+        previous_row = current_row;
     }
-    result
+    previous_row[second_chars.len()]
 }
 
-fn extract_value(values: &Vec<String>) -> String {
-    let mut retval = String::new();
-    let option_patterns = get_option_patterns();
-    for value in values {
-        let mut pattern_found = false;
-        for pattern in &option_patterns {
-            if value.starts_with(pattern.as_str()) {
-                pattern_found = true;
-                break;
-            }
+// Replace a user defined alias at the start of the arguments by the command and options it stands for.
+// An alias can start with another alias but the commands and their built-in aliases can't be redefined.
+pub fn expand_aliases(args: Vec<String>, aliases: &HashMap<String, String>) -> Result<Vec<String>, Box<dyn Error>> {
+    let definitions = get_command_definitions();
+    let mut args = args;
+    let mut expanded_aliases: Vec<String> = vec![];
+    loop {
+        let name = match args.first() {
+            Some(name) => name.trim().to_lowercase(),
+            None => return Ok(args)
+        };
+        if definitions.iter().any(|x| x.is_named(&name)) {
+            return Ok(args);
         }
-        if pattern_found {
-            break;
+        let expansion = match aliases.get(&name) {
+            Some(expansion) => expansion,
+            None => return Ok(args)
+        };
+        if expanded_aliases.contains(&name) {
+            expanded_aliases.push(name);
+            return Err(format!("The alias {} refers to itself ({})", expanded_aliases[0], expanded_aliases.join(" -> ")).into());
         }
-        else {
-            retval = format!("{} {}", retval, value);
+        let mut expanded_args: Vec<String> = expansion.split_whitespace().map(|x| x.to_string()).collect();
+        if expanded_args.is_empty() {
+            return Err(format!("The alias {} is empty", name).into());
         }
+        expanded_aliases.push(name);
+        expanded_args.extend(args.into_iter().skip(1));
+        args = expanded_args;
     }
-    retval.trim_start().to_string()
 }
 
-fn extract_options(values: &Vec<String>) -> HashMap<String, String> {
-    let mut retval = HashMap::new();
-    let mut option_found = false;
-    let mut current_option_name = String::new();
-    let mut current_option_value = String::new();
-    for value in values {
-        match get_option_name_from_pattern(&value) {
-            Some(option) => {
-                if option_found {
-                    //Add the last option found
-                    retval.insert(current_option_name, current_option_value);
-                }
-                option_found = true;
-                current_option_name = option;
-                current_option_value = extract_option_value(&value);
-                }
-            _ => {
-                if option_found {
-                    if current_option_value.len() > 0 {
-                        current_option_value.push(' ');
-                    }
-                    current_option_value.push_str(&value);
-                }
-            }
-        }           
-    }
-    if option_found {
-        //Add the last option
-        retval.insert(current_option_name, current_option_value);
-    }
-    retval
+fn is_option(token: &str) -> bool {
+    token.starts_with('-') && token.len() > 1
 }
 
-fn extract_option_value(option: &str) -> String {
-    let mut retval = String::new();
-    let option_parts: Vec<_> = option.split('=').collect();
-    if option_parts.len() > 1 {
-        for elem in option_parts[1..].to_vec() {
-            if retval.len() > 0 {
-                retval.push('=');
-            }
-            retval.push_str(elem);
+// Analyze the arguments following beaver using the command definitions.
+// The tokens that follow an option with a value are added to that option (-t=Another title),
+// the others form the value of the command. Everything after -- is part of the value.
+pub fn analyze_args(args: Vec<String>) -> Result<CommandResult, Box<dyn Error>> {
+    let definitions = get_command_definitions();
+    let definition = find_command(&definitions, args.first().map(|x| x.as_str()).unwrap_or(""))?;
+    let mut value_tokens: Vec<&str> = vec![];
+    let mut options: HashMap<String, String> = HashMap::new();
+    let mut current_option: Option<&str> = None;
+    let mut options_ended = false;
+    for token in args.iter().skip(1) {
+        if !options_ended && token == "--" {
+            options_ended = true;
+            current_option = None;
+            continue;
         }
-    }
-    retval.trim_end().to_string()
-}
-
-pub fn analyze_args(args: Vec<String>) -> Option<CommandResult> {
-    if !args.is_empty() {
-        let command_extracted = extract_command(args[0].to_string());
-
-        let mut value_extracted: String = String::new();
-        let mut options_extracted: HashMap<String, String> = HashMap::new();
-        if args.len() > 1 {
-            let values_and_options = args[1..].to_vec();
-            value_extracted = extract_value(&values_and_options);
-            options_extracted = extract_options(&values_and_options);
+        if !options_ended && is_option(token) {
+            let (flag, inline_value) = match token.split_once('=') {
+                Some((flag, value)) => (flag, Some(value)),
+                None => (token.as_str(), None)
+            };
+            if flag == "-h" || flag == "--help" {
+                options.insert(String::from(HELP_OPTION), String::new());
+                current_option = None;
+                continue;
+            }
+            if flag == "--color" {
+                if options.contains_key(COLOR_OPTION) {
+                    return Err("The option --color is given more than once".into());
+                }
+                options.insert(String::from(COLOR_OPTION), inline_value.unwrap_or("").trim().to_string());
+                current_option = Some(COLOR_OPTION);
+                continue;
+            }
+            let option = find_option(definition, flag)?;
+            if options.contains_key(option.get_name()) {
+                return Err(format!("The option --{} is given more than once", option.get_name()).into());
+            }
+            if !option.takes_value() && inline_value.is_some() {
+                return Err(format!("The option --{} doesn't take a value", option.get_name()).into());
+            }
+            options.insert(option.get_name().to_string(), inline_value.unwrap_or("").trim().to_string());
+            current_option = match option.takes_value() {
+                true => Some(option.get_name()),
+                false => None
+            };
+            continue;
         }
-        match command_extracted {
-            Some(command_extracted) => Some(CommandResult::new(command_extracted, 
-                                                               value_extracted.as_str(),
-                                                               options_extracted)),
-            None => None
+        match current_option {
+            Some(name) => {
+                let option_value = options.get_mut(name).unwrap();
+                if !option_value.is_empty() {
+                    option_value.push(' ');
+                }
+                option_value.push_str(token);
+            },
+            None => value_tokens.push(token)
         }
     }
-    else {
-        None
+    let value = value_tokens.join(" ");
+    if definition.get_value().is_none() && !value.trim().is_empty() && !options.contains_key(HELP_OPTION) {
+        return Err(format!("The command {} doesn't take a value ({})", definition.get_name(), value.trim()).into());
     }
+    Ok(CommandResult::new(definition.get_command(), value.trim(), options))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::args_analyzer::analyze_args;
-    use crate::args_analyzer::Command;
-    use crate::args_analyzer::extract_option_value;
-    use crate::args_analyzer::get_option_name_from_pattern;
+    use crate::args_analyzer::{analyze_args, expand_aliases, get_edit_distance, COLOR_OPTION, HELP_OPTION};
+    use crate::common_structs::Command;
+    use std::collections::HashMap;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    fn to_aliases(aliases: &[(&str, &str)]) -> HashMap<String, String> {
+        aliases.iter().map(|(name, expansion)| (name.to_string(), expansion.to_string())).collect()
+    }
 
     #[test]
-    fn command_equality_unkown_add_return_false() {
-        assert!(Command::Unknown != Command::Add);
+    fn command_equality_list_add_return_false() {
+        assert!(Command::List != Command::Add);
     }
 
     #[test]
-    fn command_equality_unkown_unknown_return_true() {
-        assert!(Command::Unknown == Command::Unknown);
+    fn command_equality_list_list_return_true() {
+        assert!(Command::List == Command::List);
     }
 
     #[test]
@@ -167,23 +201,52 @@ mod tests {
     }
 
     #[test]
-    fn analyze_args_with_empty_vec_return_none() {
-        assert!(analyze_args(vec![]).is_none());
+    fn analyze_args_with_empty_vec_return_error() {
+        assert_eq!("No command provided", analyze_args(vec![]).err().unwrap().to_string());
     }
 
     #[test]
-    fn analyze_args_with_one_empty_string_return_none() {
-        assert!(analyze_args(vec![String::from("")]).is_none());
+    fn analyze_args_with_one_whitespaces_string_return_error() {
+        assert_eq!("No command provided", analyze_args(to_args(&["   "])).err().unwrap().to_string());
     }
 
     #[test]
-    fn analyze_args_with_one_whitespaces_string_return_none() {
-        assert!(analyze_args(vec![String::from("   ")]).is_none());
+    fn analyze_args_with_blabla_string_command_return_unknown_command() {
+        assert_eq!("Unknown command blabla", analyze_args(to_args(&["blabla"])).err().unwrap().to_string());
     }
 
     #[test]
-    fn analyze_args_with_blabla_string_command_return_unknown_command() {
-        assert!(Command::Unknown == analyze_args(vec![String::from("blabla")]).unwrap().get_command());
+    fn analyze_args_with_misspelled_command_return_suggestion() {
+        assert_eq!("Unknown command lsit. Did you mean list?", analyze_args(to_args(&["lsit"])).err().unwrap().to_string());
+    }
+
+    #[test]
+    fn analyze_args_with_builtin_aliases_return_commands() {
+        assert!(Command::Add == analyze_args(to_args(&["a", "Call mom"])).unwrap().get_command());
+        assert!(Command::List == analyze_args(to_args(&["LS"])).unwrap().get_command());
+        assert!(Command::Remove == analyze_args(to_args(&["rm", "2"])).unwrap().get_command());
+        assert!(Command::Done == analyze_args(to_args(&["d", "2"])).unwrap().get_command());
+    }
+
+    #[test]
+    fn expand_aliases_with_nested_alias_return_command_and_options() {
+        let aliases = to_aliases(&[("urgent", "list --priority=H --sort=creationtime"), ("u", "urgent -a")]);
+        assert_eq!(to_args(&["list", "--priority=H", "--sort=creationtime", "-a", "--where=title~report"]),
+                   expand_aliases(to_args(&["u", "--where=title~report"]), &aliases).unwrap());
+        assert_eq!(to_args(&["ls", "-a"]), expand_aliases(to_args(&["ls", "-a"]), &aliases).unwrap());
+    }
+
+    #[test]
+    fn expand_aliases_with_alias_of_command_keep_command() {
+        let aliases = to_aliases(&[("list", "list -a")]);
+        assert_eq!(to_args(&["list"]), expand_aliases(to_args(&["list"]), &aliases).unwrap());
+    }
+
+    #[test]
+    fn expand_aliases_with_cycle_return_error() {
+        let aliases = to_aliases(&[("hot", "urgent"), ("urgent", "top -a"), ("top", "hot")]);
+        assert_eq!("The alias urgent refers to itself (urgent -> top -> hot -> urgent)",
+                   expand_aliases(to_args(&["urgent"]), &aliases).err().unwrap().to_string());
     }
 
     #[test]
@@ -262,90 +325,127 @@ mod tests {
     }
 
     #[test]
-    fn analyze_args_with_add_with_twowords_and_two_options_return_add_valid_command() {
-        let command_result = analyze_args(vec![String::from("add"), 
-                                               String::from("test"),
-                                               String::from("two"),
-                                               String::from("--priority=H"),
-                                               String::from("and"),
-                                               String::from("P"),
-                                               String::from("--title=Test"),
-                                               String::from("again")]).unwrap();
-        assert!(Command::Add == command_result.get_command());
-        assert_eq!("test two", command_result.get_value());
-        let actual_options = command_result.get_options();
-        assert_eq!(2, actual_options.len());
-        assert!(actual_options.contains_key("priority"));
-        assert_eq!("H and P", actual_options["priority"]);
-        assert!(actual_options.contains_key("title"));
-        assert_eq!("Test again", actual_options["title"]);
+    fn analyze_args_with_edit_with_option_value_with_spaces_return_edit_valid_command() {
+        let command_result = analyze_args(to_args(&["edit", "1", "-t=Another", "title", "-p=m"])).unwrap();
+        assert!(Command::Edit == command_result.get_command());
+        assert_eq!("1", command_result.get_value());
+        assert_eq!(2, command_result.get_options().len());
+        assert_eq!("Another title", command_result.get_options()["title"]);
+        assert_eq!("m", command_result.get_options()["priority"]);
     }
 
     #[test]
-    fn get_option_name_from_pattern_with_p_return_priority() {
-        let actual = get_option_name_from_pattern("-p=H");
-        match actual {
-            Some(value) => assert_eq!("priority", value),
-            None => panic!("Test failed")
-        }
+    fn analyze_args_with_unknown_option_return_suggestion() {
+        assert_eq!("Unknown option --prio for add. Did you mean --priority?",
+                   analyze_args(to_args(&["add", "test", "--prio=H"])).err().unwrap().to_string());
     }
 
     #[test]
-    fn get_option_name_from_pattern_with_priority_return_priority() {
-        let actual = get_option_name_from_pattern("--priority=H");
-        match actual {
-            Some(value) => assert_eq!("priority", value),
-            None => panic!("Test failed")
-        }
+    fn analyze_args_with_option_of_another_command_return_error() {
+        assert_eq!("Unknown option --sort for add",
+                   analyze_args(to_args(&["add", "test", "--sort=priority"])).err().unwrap().to_string());
+    }
+
+    #[test]
+    fn analyze_args_with_unknown_short_option_return_error() {
+        assert_eq!("Unknown option -z for add", analyze_args(to_args(&["add", "test", "-z"])).err().unwrap().to_string());
+    }
+
+    #[test]
+    fn analyze_args_with_flag_with_value_return_error() {
+        assert_eq!("The option --all doesn't take a value", analyze_args(to_args(&["list", "-a=yes"])).err().unwrap().to_string());
     }
 
     #[test]
-    fn get_option_name_from_pattern_with_blabla_return_none() {
-        assert!(get_option_name_from_pattern("--blabla=H").is_none());
+    fn analyze_args_with_option_given_twice_return_error() {
+        assert_eq!("The option --priority is given more than once",
+                   analyze_args(to_args(&["add", "test", "-p=H", "--priority=L"])).err().unwrap().to_string());
     }
 
     #[test]
-    fn get_option_name_from_pattern_with_long_all_return_all() {
-        assert_eq!("all", get_option_name_from_pattern("--all").unwrap());
+    fn analyze_args_with_value_for_command_without_value_return_error() {
+        assert_eq!("The command next doesn't take a value (now)", analyze_args(to_args(&["next", "now"])).err().unwrap().to_string());
     }
 
     #[test]
-    fn get_option_name_from_pattern_with_long_allocator_return_none() {
-        assert!(get_option_name_from_pattern("--allocator").is_none());
+    fn analyze_args_with_double_dash_read_following_tokens_as_value() {
+        let command_result = analyze_args(to_args(&["add", "-p=H", "--", "-p=L", "is", "a", "title"])).unwrap();
+        assert_eq!("-p=L is a title", command_result.get_value());
+        assert_eq!("H", command_result.get_options()["priority"]);
     }
 
     #[test]
-    fn get_option_name_from_pattern_with_short_all_return_all() {
-        assert_eq!("all", get_option_name_from_pattern("-a").unwrap());
+    fn analyze_args_with_option_value_starting_with_option_return_option_value() {
+        let command_result = analyze_args(to_args(&["edit", "1", "-t=-p=x", "rule"])).unwrap();
+        assert_eq!("1", command_result.get_value());
+        assert_eq!("-p=x rule", command_result.get_options()["title"]);
     }
 
     #[test]
-    fn get_option_name_from_pattern_with_short_allocator_return_none() {
-        assert!(get_option_name_from_pattern("-allocator").is_none());
+    fn analyze_args_with_flag_followed_by_value_return_command_value() {
+        let command_result = analyze_args(to_args(&["import", "--dry-run", "backlog.csv", "--from=csv"])).unwrap();
+        assert_eq!("backlog.csv", command_result.get_value());
+        assert_eq!("csv", command_result.get_options()["from"]);
+        assert!(command_result.get_options().contains_key("dry-run"));
     }
 
     #[test]
-    fn extract_option_value_with_p_equal_h_return_h() {
-        assert_eq!("H".to_string(), extract_option_value("-p=H"));
+    fn analyze_args_with_help_return_help_option() {
+        let command_result = analyze_args(to_args(&["next", "--help"])).unwrap();
+        assert!(Command::Next == command_result.get_command());
+        assert!(command_result.get_options().contains_key(HELP_OPTION));
     }
 
     #[test]
-    fn extract_option_value_with_p_equal_hequalh_return_hequalh() {
-        assert_eq!("H=H".to_string(), extract_option_value("-p=H=H"));
+    fn analyze_args_with_color_return_color_option() {
+        let command_result = analyze_args(to_args(&["list", "--color=never", "-a"])).unwrap();
+        assert_eq!("never", command_result.get_options()[COLOR_OPTION]);
+        let command_result = analyze_args(to_args(&["add", "Fix", "the", "--color", "flag"])).unwrap();
+        assert_eq!("Fix the", command_result.get_value());
+        assert_eq!("flag", command_result.get_options()[COLOR_OPTION]);
+        let command_result = analyze_args(to_args(&["add", "--", "Fix", "the", "--color", "flag"])).unwrap();
+        assert_eq!("Fix the --color flag", command_result.get_value());
+        assert!(!command_result.get_options().contains_key(COLOR_OPTION));
     }
 
     #[test]
-    fn extract_option_value_with_p_equal_hequalhequal_return_hequalhequal() {
-        assert_eq!("H=H=".to_string(), extract_option_value("-p=H=H="));
+    fn analyze_args_with_done_with_id_list_return_done_valid_command() {
+        let command_result = analyze_args(vec![String::from("done"), 
+                                               String::from("1"),
+                                               String::from("3"),
+                                               String::from("5-8")]).unwrap();
+        assert!(Command::Done == command_result.get_command());
+        assert_eq!("1 3 5-8", command_result.get_value());
     }
 
     #[test]
-    fn extract_option_value_with_p_equal_return_empty() {
-        assert_eq!("".to_string(), extract_option_value("-p="));
+    fn analyze_args_with_remove_with_where_return_remove_valid_command() {
+        let command_result = analyze_args(vec![String::from("remove"), 
+                                               String::from("--where=priority=L,title~weekly"),
+                                               String::from("report")]).unwrap();
+        assert!(Command::Remove == command_result.get_command());
+        assert_eq!("", command_result.get_value());
+        assert_eq!("priority=L,title~weekly report", command_result.get_options()["where"]);
     }
 
     #[test]
-    fn extract_option_value_with_p_return_empty() {
-        assert_eq!("".to_string(), extract_option_value("-p"));
+    fn analyze_args_with_import_with_map_and_dry_run_return_import_valid_command() {
+        let command_result = analyze_args(vec![String::from("import"),
+                                               String::from("backlog.csv"),
+                                               String::from("--from=csv"),
+                                               String::from("--map=title:Summary,priority:Prio"),
+                                               String::from("--dry-run")]).unwrap();
+        assert!(Command::Import == command_result.get_command());
+        assert_eq!("backlog.csv", command_result.get_value());
+        assert_eq!("title:Summary,priority:Prio", command_result.get_options()["map"]);
+        assert!(command_result.get_options().contains_key("dry-run"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn get_edit_distance_return_number_of_edits() {
+        assert_eq!(0, get_edit_distance("list", "list"));
+        assert_eq!(2, get_edit_distance("lsit", "list"));
+        assert_eq!(3, get_edit_distance("", "add"));
+        assert_eq!(1, get_edit_distance("stat", "stats"));
+    }
+}
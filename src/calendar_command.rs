@@ -0,0 +1,242 @@
+use crate::common_structs::{CommandResult, ExecutableCommand, Priority};
+use crate::data_service::read_todos_with_archive;
+use crate::list_command::{ListCommand, SortOrder};
+use crate::output::{bold, reset_style};
+use crate::todo::Todo;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use std::error::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CalendarPeriod {
+    // The first day of the month
+    Month(NaiveDate),
+    Day(NaiveDate)
+}
+
+impl CalendarPeriod {
+    // Parse a month like 2026-11 or a day like 2026-11-05, the current month when the value is empty
+    pub fn from_value(value: &str, today: NaiveDate) -> Result<CalendarPeriod, Box<dyn Error>> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(CalendarPeriod::Month(today.with_day(1).unwrap()));
+        }
+        if let Ok(day) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(CalendarPeriod::Day(day));
+        }
+        match NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d") {
+            Ok(first_day) => Ok(CalendarPeriod::Month(first_day)),
+            Err(_) => Err(format!("Invalid month {}. Must be like 2026-11, or a day like 2026-11-05", value).into())
+        }
+    }
+}
+
+// The todos due and completed on a day of the calendar
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayCount {
+    due: usize,
+    completed: usize,
+    has_high_priority: bool
+}
+
+impl DayCount {
+    // Format the counts like 2d 1c
+    fn format(&self) -> String {
+        let mut counts = vec![];
+        if self.due > 0 {
+            counts.push(format!("{}d", self.due));
+        }
+        if self.completed > 0 {
+            counts.push(format!("{}c", self.completed));
+        }
+        counts.join(" ")
+    }
+}
+
+#[derive(Debug)]
+pub struct CalendarCommand {
+    period: CalendarPeriod
+}
+
+impl CalendarCommand {
+    const CELL_WIDTH: usize = 8;
+    const WEEK_DAYS: [&'static str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<CalendarCommand, Box<dyn Error>> {
+        let period = CalendarPeriod::from_value(command_result.get_value(), Local::now().date_naive())?;
+        Ok(CalendarCommand { period })
+    }
+
+    fn get_separator_line() -> String {
+        format!("+{}", format!("{}+", "-".repeat(CalendarCommand::CELL_WIDTH)).repeat(7))
+    }
+
+    // Return the lines of the month grid, each day shows its number and its counts
+    pub fn get_calendar_lines(first_day: NaiveDate, counts: &[DayCount]) -> Vec<String> {
+        let width = (CalendarCommand::CELL_WIDTH + 1) * 7 + 1;
+        let mut lines = vec![format!("{:^width$}", first_day.format("%B %Y").to_string(), width=width),
+                             CalendarCommand::get_separator_line()];
+        let week_days: Vec<String> = CalendarCommand::WEEK_DAYS.iter()
+            .map(|x| format!("{:^width$}|", x, width=CalendarCommand::CELL_WIDTH))
+            .collect();
+        lines.push(format!("|{}", week_days.concat()));
+        lines.push(CalendarCommand::get_separator_line());
+        // The weeks start on Monday, the cells before the first day and after the last day are empty
+        let offset = first_day.weekday().num_days_from_monday() as usize;
+        let mut cells: Vec<Option<usize>> = vec![None; offset];
+        cells.extend((0..counts.len()).map(Some));
+        while !cells.len().is_multiple_of(7) {
+            cells.push(None);
+        }
+        for week in cells.chunks(7) {
+            let mut day_line = String::from("|");
+            let mut count_line = String::from("|");
+            for cell in week {
+                let (day, count, style, reset) = match cell {
+                    Some(index) if counts[*index].has_high_priority =>
+                        ((index + 1).to_string(), counts[*index].format(), bold().to_string(), reset_style().to_string()),
+                    Some(index) => ((index + 1).to_string(), counts[*index].format(), String::new(), String::new()),
+                    None => (String::new(), String::new(), String::new(), String::new())
+                };
+                day_line.push_str(&format!(" {}{:<width$}{}|", style, day, reset, width=CalendarCommand::CELL_WIDTH - 1));
+                count_line.push_str(&format!(" {}{:<width$}{}|", style, count, reset, width=CalendarCommand::CELL_WIDTH - 1));
+            }
+            lines.push(day_line);
+            lines.push(count_line);
+            lines.push(CalendarCommand::get_separator_line());
+        }
+        lines.push(String::from("d: todos due, c: todos completed, bold: days with High priority todos"));
+        lines
+    }
+
+    fn print_month(&self, todos: &[Todo], first_day: NaiveDate) {
+        let last_day = first_day + Months::new(1) - Duration::days(1);
+        let counts = count_per_day(todos, first_day, last_day);
+        for line in CalendarCommand::get_calendar_lines(first_day, &counts) {
+            println!("{}", line);
+        }
+    }
+
+    fn print_day(&self, todos: &[Todo], day: NaiveDate) {
+        println!("{}{}{}", bold(), day.format("%a %Y-%m-%d"), reset_style());
+        let day_todos = get_day_todos(todos, day);
+        match day_todos.is_empty() {
+            true => println!("Nothing due or completed"),
+            false => ListCommand::new(SortOrder::PriorityDESC).print_todos(&day_todos, false)
+        }
+    }
+}
+
+// Return the date of the todo in the calendar, the completion date of the completed todos
+// and the due date of the open todos
+fn get_calendar_date(todo: &Todo) -> Option<NaiveDate> {
+    match todo.get_completed() {
+        true => Some(todo.get_when_completed_in_localtime().date_naive()),
+        false => todo.get_due_in_localtime().map(|x| x.date_naive())
+    }
+}
+
+// Count the todos due and completed on each day from the first to the last day
+pub fn count_per_day(todos: &[Todo], first_day: NaiveDate, last_day: NaiveDate) -> Vec<DayCount> {
+    let mut counts = vec![DayCount::default(); ((last_day - first_day).num_days() + 1) as usize];
+    for todo in todos {
+        let date = match get_calendar_date(todo) {
+            Some(date) if date >= first_day && date <= last_day => date,
+            _ => continue
+        };
+        let count = &mut counts[(date - first_day).num_days() as usize];
+        match todo.get_completed() {
+            true => count.completed += 1,
+            false => count.due += 1
+        }
+        count.has_high_priority |= todo.get_priority() == Priority::High;
+    }
+    counts
+}
+
+// Return the open todos due on the day followed by the todos completed on the day
+pub fn get_day_todos(todos: &[Todo], day: NaiveDate) -> Vec<Todo> {
+    let mut day_todos: Vec<Todo> = todos.iter().filter(|x| get_calendar_date(x) == Some(day)).cloned().collect();
+    day_todos.sort_by_key(|x| (x.get_completed(), x.get_priority(), x.get_when_created_in_localtime()));
+    day_todos
+}
+
+impl ExecutableCommand for CalendarCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let todos = read_todos_with_archive()?;
+        match self.period {
+            CalendarPeriod::Month(first_day) => self.print_month(&todos, first_day),
+            CalendarPeriod::Day(day) => self.print_day(&todos, day)
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calendar_command::{count_per_day, get_day_todos, CalendarCommand, CalendarPeriod, DayCount};
+    use crate::common_structs::Priority;
+    use crate::todo::Todo;
+    use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn get_todo(id: u32, priority: Priority, due: Option<NaiveDate>, completed: Option<NaiveDate>) -> Todo {
+        let to_utc = |day: NaiveDate| Local.from_local_datetime(&day.and_hms_opt(12, 0, 0).unwrap()).unwrap().with_timezone(&Utc);
+        let mut todo = Todo::new(id, "Test", priority, to_utc(date(10, 1))).unwrap();
+        todo.set_due(due.map(to_utc));
+        if let Some(completed) = completed {
+            todo.set_completed(true, Some(to_utc(completed)));
+        }
+        todo
+    }
+
+    #[test]
+    fn calendar_period_from_value_return_month_or_day() {
+        assert_eq!(CalendarPeriod::Month(date(10, 1)), CalendarPeriod::from_value("", date(10, 19)).unwrap());
+        assert_eq!(CalendarPeriod::Month(date(11, 1)), CalendarPeriod::from_value("2026-11", date(10, 19)).unwrap());
+        assert_eq!(CalendarPeriod::Day(date(11, 5)), CalendarPeriod::from_value("2026-11-05", date(10, 19)).unwrap());
+        assert_eq!("Invalid month 2026-13. Must be like 2026-11, or a day like 2026-11-05",
+                   CalendarPeriod::from_value("2026-13", date(10, 19)).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn count_per_day_return_due_and_completed_counts() {
+        let todos = vec![get_todo(1, Priority::High, Some(date(11, 5)), None),
+                         get_todo(2, Priority::Low, Some(date(11, 5)), None),
+                         get_todo(3, Priority::Low, Some(date(11, 5)), Some(date(11, 2))),
+                         get_todo(4, Priority::Low, Some(date(12, 1)), None),
+                         get_todo(5, Priority::Low, None, None)];
+        let counts = count_per_day(&todos, date(11, 1), date(11, 30));
+        assert_eq!(30, counts.len());
+        assert_eq!(DayCount { due: 0, completed: 1, has_high_priority: false }, counts[1]);
+        assert_eq!(DayCount { due: 2, completed: 0, has_high_priority: true }, counts[4]);
+        assert_eq!("2d", counts[4].format());
+        assert_eq!(1, counts.iter().filter(|x| x.due > 0).count());
+    }
+
+    #[test]
+    fn get_calendar_lines_return_weeks_from_monday() {
+        let mut counts = vec![DayCount::default(); 30];
+        counts[4] = DayCount { due: 2, completed: 1, has_high_priority: false };
+        let lines = CalendarCommand::get_calendar_lines(date(11, 1), &counts);
+        assert_eq!("                         November 2026                          ", lines[0]);
+        assert_eq!("|  Mon   |  Tue   |  Wed   |  Thu   |  Fri   |  Sat   |  Sun   |", lines[2]);
+        // November 2026 starts on a Sunday
+        assert_eq!("|        |        |        |        |        |        | 1      |", lines[4]);
+        assert_eq!("| 2      | 3      | 4      | 5      | 6      | 7      | 8      |", lines[7]);
+        assert_eq!("|        |        |        | 2d 1c  |        |        |        |", lines[8]);
+        // 6 weeks of 3 lines after the title and the header
+        assert_eq!(4 + 6 * 3 + 1, lines.len());
+    }
+
+    #[test]
+    fn get_day_todos_return_open_todos_first() {
+        let todos = vec![get_todo(1, Priority::High, None, Some(date(11, 5))),
+                         get_todo(2, Priority::Low, Some(date(11, 5)), None),
+                         get_todo(3, Priority::Low, Some(date(11, 6)), None)];
+        let ids: Vec<u32> = get_day_todos(&todos, date(11, 5)).iter().map(|x| x.get_id()).collect();
+        assert_eq!(vec![2, 1], ids);
+    }
+}
@@ -0,0 +1,530 @@
+use crate::add_command::AddCommand;
+use crate::agenda_command::AgendaCommand;
+use crate::archive_command::ArchiveCommand;
+use crate::calendar_command::CalendarCommand;
+use crate::common_structs::{Command, CommandResult, ExecutableCommand};
+use crate::completions_command::CompletionsCommand;
+use crate::config_command::ConfigCommand;
+use crate::done_command::DoneCommand;
+use crate::edit_command::EditCommand;
+use crate::export_command::ExportCommand;
+use crate::fetch_command::FetchCommand;
+use crate::import_command::ImportCommand;
+use crate::list_command::ListCommand;
+use crate::log_command::LogCommand;
+use crate::next_command::NextCommand;
+use crate::output::{no_underline, underline};
+use crate::purge_command::PurgeCommand;
+use crate::remove_command::RemoveCommand;
+use crate::report_command::ReportCommand;
+use crate::shell_command::ShellCommand;
+use crate::snooze_command::SnoozeCommand;
+use crate::start_command::StartCommand;
+use crate::stats_command::StatsCommand;
+use crate::stop_command::StopCommand;
+use crate::trash_command::TrashCommand;
+use crate::tui_command::TuiCommand;
+use crate::unarchive_command::UnarchiveCommand;
+use std::error::Error;
+
+const NAME_WIDTH: usize = 31;
+const OPTION_WIDTH: usize = 27;
+const FORMATS: [&str; 6] = ["csv", "ics", "markdown", "org", "taskwarrior", "todotxt"];
+
+pub type CommandFactory = fn(&CommandResult) -> Result<Box<dyn ExecutableCommand>, Box<dyn Error>>;
+
+// How the shell completion scripts complete a value
+#[derive(Debug, PartialEq)]
+pub enum ValueCompletion {
+    Nothing,
+    Choices(Vec<&'static str>),
+    Files,
+    OpenTodoIds
+}
+
+#[derive(Debug)]
+pub struct OptionDefinition {
+    name: &'static str,
+    short: Option<char>,
+    // The name of the value displayed in the help, None for the options without value
+    value_name: Option<&'static str>,
+    description: Vec<&'static str>,
+    completion: ValueCompletion
+}
+
+impl OptionDefinition {
+    pub fn with_value(name: &'static str, short: Option<char>, value_name: &'static str, description: &[&'static str]) -> OptionDefinition {
+        OptionDefinition { name, short, value_name: Some(value_name), description: description.to_vec(), completion: ValueCompletion::Nothing }
+    }
+
+    pub fn flag(name: &'static str, short: Option<char>, description: &[&'static str]) -> OptionDefinition {
+        OptionDefinition { name, short, value_name: None, description: description.to_vec(), completion: ValueCompletion::Nothing }
+    }
+
+    pub fn choices(mut self, choices: &[&'static str]) -> OptionDefinition {
+        self.completion = ValueCompletion::Choices(choices.to_vec());
+        self
+    }
+
+    // Return the first line of the description, used by the completion scripts
+    pub fn get_description(&self) -> &'static str {
+        self.description.first().map(|x| x.trim_end_matches(':')).unwrap_or_default()
+    }
+
+    pub fn get_completion(&self) -> &ValueCompletion {
+        &self.completion
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_short(&self) -> Option<char> {
+        self.short
+    }
+
+    pub fn takes_value(&self) -> bool {
+        self.value_name.is_some()
+    }
+
+    // Return the flags as displayed in the help (-p=x, --priority=x)
+    pub fn get_usage(&self) -> String {
+        let value = self.value_name.map(|x| format!("={}", x)).unwrap_or_default();
+        match self.short {
+            Some(short) => format!("-{}{}, --{}{}", short, value, self.name, value),
+            None => format!("--{}{}", self.name, value)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ValueDefinition {
+    name: &'static str,
+    required: bool,
+    description: Vec<&'static str>,
+    completion: ValueCompletion
+}
+
+impl ValueDefinition {
+    pub fn new(name: &'static str, required: bool, description: &[&'static str]) -> ValueDefinition {
+        ValueDefinition { name, required, description: description.to_vec(), completion: ValueCompletion::Nothing }
+    }
+
+    pub fn completion(mut self, completion: ValueCompletion) -> ValueDefinition {
+        self.completion = completion;
+        self
+    }
+
+    pub fn get_completion(&self) -> &ValueCompletion {
+        &self.completion
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn get_usage(&self) -> String {
+        match self.required {
+            true => self.name.to_string(),
+            false => format!("[{}]", self.name)
+        }
+    }
+}
+
+pub struct CommandDefinition {
+    name: &'static str,
+    command: Command,
+    summary: &'static str,
+    aliases: Vec<&'static str>,
+    value: Option<ValueDefinition>,
+    options: Vec<OptionDefinition>,
+    create: CommandFactory
+}
+
+impl CommandDefinition {
+    pub fn new(name: &'static str, command: Command, summary: &'static str, create: CommandFactory) -> CommandDefinition {
+        CommandDefinition { name, command, summary, aliases: vec![], value: None, options: vec![], create }
+    }
+
+    pub fn alias(mut self, alias: &'static str) -> CommandDefinition {
+        self.aliases.push(alias);
+        self
+    }
+
+    pub fn value(mut self, value: ValueDefinition) -> CommandDefinition {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn option(mut self, option: OptionDefinition) -> CommandDefinition {
+        self.options.push(option);
+        self
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_command(&self) -> Command {
+        self.command
+    }
+
+    pub fn get_summary(&self) -> &'static str {
+        self.summary
+    }
+
+    pub fn get_aliases(&self) -> &Vec<&'static str> {
+        &self.aliases
+    }
+
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+
+    // Return the name followed by the aliases (list, ls)
+    fn get_names(&self) -> String {
+        let mut names = vec![self.name];
+        names.extend(&self.aliases);
+        names.join(", ")
+    }
+
+    pub fn get_value(&self) -> Option<&ValueDefinition> {
+        self.value.as_ref()
+    }
+
+    pub fn get_options(&self) -> &Vec<OptionDefinition> {
+        &self.options
+    }
+
+    pub fn create_command(&self, command_result: &CommandResult) -> Result<Box<dyn ExecutableCommand>, Box<dyn Error>> {
+        (self.create)(command_result)
+    }
+
+    fn uses_criteria(&self) -> bool {
+        self.options.iter().any(|x| x.name == "where")
+    }
+
+    // Return the usage of the command (add title [OPTIONS]) followed by the description
+    // of its value and its options
+    fn get_usage_lines(&self) -> Vec<String> {
+        let mut title = self.name.to_string();
+        if let Some(value) = &self.value {
+            title.push(' ');
+            title.push_str(&value.get_usage());
+        }
+        if !self.options.is_empty() {
+            title.push_str(" [OPTIONS]");
+        }
+        let mut lines = vec![title];
+        if let Some(value) = &self.value {
+            lines.extend(format_entry(8, OPTION_WIDTH, value.name, &value.description));
+        }
+        for option in &self.options {
+            lines.extend(format_entry(8, OPTION_WIDTH, &option.get_usage(), &option.description));
+        }
+        lines
+    }
+
+    pub fn get_help(&self) -> String {
+        let mut lines = vec![format!("beaver {} - {}\n", self.get_names(), self.summary), String::from("USAGE:")];
+        let mut usage_lines = self.get_usage_lines();
+        usage_lines[0] = format!("    beaver {}", usage_lines[0]);
+        lines.extend(usage_lines);
+        lines.extend(format_entry(8, OPTION_WIDTH, "-h, --help", &["Print this help"]));
+        if self.uses_criteria() {
+            lines.extend(get_criteria_lines());
+        }
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+// Return the lines of a name followed by its description aligned on a column
+fn format_entry(indent: usize, width: usize, name: &str, description: &[&str]) -> Vec<String> {
+    let mut lines = vec![];
+    if name.len() >= width || description.is_empty() {
+        lines.push(format!("{:indent$}{}", "", name, indent=indent));
+    }
+    for (index, line) in description.iter().enumerate() {
+        let entry_name = if index == 0 && name.len() < width { name } else { "" };
+        lines.push(format!("{:indent$}{:<width$}{}", "", entry_name, line, indent=indent, width=width));
+    }
+    lines
+}
+
+fn get_criteria_lines() -> Vec<String> {
+    vec![String::from("CRITERIA:"),
+         String::from("    Criteria are separated by commas and all must match (ex: --where=priority=L,title~report)"),
+         String::from("        priority=x                 The priority is H, M or L"),
+         String::from("        title~text                 The title contains the text (case insensitive)"),
+         String::from("        status=x                   The status is open or completed"),
+         String::from("        created<yyyy-mm-dd         The todo was created before the date"),
+         String::from("        created>yyyy-mm-dd         The todo was created after the date")]
+}
+
+// Return the usage of beaver with every command
+pub fn get_usage() -> String {
+    let definitions = get_command_definitions();
+    let mut lines = vec![String::from("Beaver todos\n"),
+                         String::from("USAGE:"),
+                         String::from("beaver command [OPTIONS]"),
+                         String::from("beaver command --help\n"),
+                         String::from("COMMANDS:")];
+    lines.extend(format_entry(4, NAME_WIDTH, "-v, --version", &["Print version info and exit"]));
+    lines.extend(format_entry(4, NAME_WIDTH, "-h, --help", &["Prints help information"]));
+    lines.extend(format_entry(4, NAME_WIDTH, "--color=x", &["Color the output, possible values are auto, always and never",
+                                                          "(auto colors a terminal unless NO_COLOR is set)"]));
+    for definition in &definitions {
+        lines.extend(format_entry(4, NAME_WIDTH, &definition.get_names(), &[definition.summary]));
+    }
+    lines.push(String::new());
+    lines.push(String::from("USAGE BY COMMAND:"));
+    for definition in &definitions {
+        let mut usage_lines = definition.get_usage_lines();
+        usage_lines[0] = format!("    {}{}{}{}", underline(), definition.name, no_underline(), &usage_lines[0][definition.name.len()..]);
+        lines.extend(usage_lines);
+    }
+    lines.extend(get_criteria_lines());
+    format!("{}\n", lines.join("\n"))
+}
+
+pub fn find_command_definition(command: Command) -> Option<CommandDefinition> {
+    get_command_definitions().into_iter().find(|x| x.command == command)
+}
+
+fn priority_option() -> OptionDefinition {
+    OptionDefinition::with_value("priority", Some('p'), "x", &["The priority of the todo, possible values are H, M and L",
+                                                             "for High, Medium and Low"])
+        .choices(&["H", "M", "L"])
+}
+
+fn estimate_option() -> OptionDefinition {
+    OptionDefinition::with_value("estimate", Some('e'), "x", &["The estimated duration of the todo (ex: 45m, 2h or 1h30m)"])
+}
+
+fn due_option(description: &'static str) -> OptionDefinition {
+    OptionDefinition::with_value("due", Some('d'), "x", &[description, "(ex: 2026-11-05, 2026-11-05 14:00, tomorrow or 3d)"])
+}
+
+fn yes_option(description: &'static str) -> OptionDefinition {
+    OptionDefinition::flag("yes", Some('y'), &[description])
+}
+
+fn dry_run_option() -> OptionDefinition {
+    OptionDefinition::flag("dry-run", None, &["Print the todos that would be deleted without deleting them"])
+}
+
+fn where_option(description: &'static str) -> OptionDefinition {
+    OptionDefinition::with_value("where", Some('w'), "x", &[description])
+}
+
+fn date_format_option() -> OptionDefinition {
+    OptionDefinition::with_value("date-format", None, "x", &["The csv date format (Default: %Y-%m-%d %H:%M:%S)"])
+}
+
+pub fn get_command_definitions() -> Vec<CommandDefinition> {
+    vec![
+        CommandDefinition::new("list", Command::List, "Display the todo list",
+                               |x| Ok(Box::new(ListCommand::new_from_command_result(x)?)))
+            .alias("ls")
+            .option(OptionDefinition::flag("all", Some('a'), &["Display all todos even those who are completed"]))
+            .option(OptionDefinition::with_value("sort", Some('s'), "x", &["Sort the todo list by one of the following:",
+                                                                            "prioritydesc: Sort by priority from High to Low (Default)",
+                                                                            "priority: Sort by priority from Low to High",
+                                                                            "creationtimedesc: Sort by creation time by more to less recent",
+                                                                            "creationtime: Sort by creation time by less to more recent"])
+                        .choices(&["prioritydesc", "priority", "creationtimedesc", "creationtime"]))
+            .option(OptionDefinition::flag("archived", None, &["Display the archived todos with their uuid"]))
+            .option(OptionDefinition::flag("waiting", None, &["Display the snoozed todos with the date they come back"]))
+            .option(OptionDefinition::with_value("priority", Some('p'), "x", &["Display only the todos with the priority, possible values are H, M and L"])
+                        .choices(&["H", "M", "L"])),
+        CommandDefinition::new("add", Command::Add, "Add a new todo",
+                               |x| Ok(Box::new(AddCommand::new_from_command_result(x)?)))
+            .alias("a")
+            .value(ValueDefinition::new("title", true, &["The title (text) of the todo"]))
+            .option(priority_option())
+            .option(estimate_option())
+            .option(due_option("The date the todo is due")),
+        CommandDefinition::new("edit", Command::Edit, "Edit an existing todo",
+                               |x| Ok(Box::new(EditCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("ids", false, &["The ids of the todos to edit (ex: 1 3 5-8 or 2,4)"]).completion(ValueCompletion::OpenTodoIds))
+            .option(where_option("Edit the todos matching the criteria instead of ids"))
+            .option(OptionDefinition::with_value("title", Some('t'), "x", &["The title (text) of the todo"]))
+            .option(priority_option())
+            .option(estimate_option())
+            .option(due_option("The date the todo is due, none removes it"))
+            .option(OptionDefinition::flag("interactive", Some('i'), &["Edit every field of the todo in $EDITOR"]))
+            .option(yes_option("Update several todos without asking for a confirmation")),
+        CommandDefinition::new("done", Command::Done, "Complete a todo",
+                               |x| Ok(Box::new(DoneCommand::new_from_command_result(x)?)))
+            .alias("d")
+            .value(ValueDefinition::new("ids", false, &["The ids of the todos to complete (ex: 1 3 5-8 or 2,4)"]).completion(ValueCompletion::OpenTodoIds))
+            .option(where_option("Complete the todos matching the criteria instead of ids"))
+            .option(yes_option("Complete several todos without asking for a confirmation")),
+        CommandDefinition::new("next", Command::Next, "Display the next todo to work on",
+                               |_| Ok(Box::new(NextCommand {}))),
+        CommandDefinition::new("agenda", Command::Agenda, "Display the todos of the next days by due date",
+                               |x| Ok(Box::new(AgendaCommand::new_from_command_result(x)?)))
+            .option(OptionDefinition::with_value("days", None, "x", &["The number of days displayed, at most 366 (Default: 7)"])),
+        CommandDefinition::new("calendar", Command::Calendar, "Display a month with the todos due and completed per day",
+                               |x| Ok(Box::new(CalendarCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("month", false, &["The month to display (ex: 2026-11, Default: the current month)",
+                                                          "or a day to list its todos (ex: 2026-11-05)"])),
+        CommandDefinition::new("tui", Command::Tui, "Browse and change the todos in a full-screen interface",
+                               |_| Ok(Box::new(TuiCommand {}))),
+        CommandDefinition::new("shell", Command::Shell, "Run several commands in one session, writing the changes once",
+                               |_| Ok(Box::new(ShellCommand {}))),
+        CommandDefinition::new("fetch", Command::Fetch, "Display the details of a specific todo",
+                               |x| Ok(Box::new(FetchCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("id", true, &["The id of the todo to display"]).completion(ValueCompletion::OpenTodoIds))
+            .option(OptionDefinition::flag("history", None, &["Display the changes of the todo with their date and user"])),
+        CommandDefinition::new("remove", Command::Remove, "Move a todo to the trash",
+                               |x| Ok(Box::new(RemoveCommand::new_from_command_result(x)?)))
+            .alias("rm")
+            .value(ValueDefinition::new("ids", false, &["The ids of the todos to delete (ex: 1 3 5-8 or 2,4)"]).completion(ValueCompletion::OpenTodoIds))
+            .option(where_option("Delete the todos matching the criteria instead of ids"))
+            .option(yes_option("Delete without asking for a confirmation (needed when the input isn't a terminal)"))
+            .option(dry_run_option()),
+        CommandDefinition::new("purge", Command::Purge, "Delete all completed todos",
+                               |x| Ok(Box::new(PurgeCommand::new_from_command_result(x)?)))
+            .option(yes_option("Delete without asking for a confirmation (needed when the input isn't a terminal)"))
+            .option(dry_run_option()),
+        CommandDefinition::new("archive", Command::Archive, "Move the completed todos to the archive",
+                               |x| Ok(Box::new(ArchiveCommand::new_from_command_result(x)?)))
+            .option(OptionDefinition::with_value("older-than", None, "x", &["Only the todos completed for longer than the age (ex: 30d or 2w)"])),
+        CommandDefinition::new("unarchive", Command::Unarchive, "Move an archived todo back to the todo list",
+                               |x| Ok(Box::new(UnarchiveCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("uuid", true, &["The uuid of the archived todo, or its first characters (see list --archived)"])),
+        CommandDefinition::new("trash", Command::Trash, "List, restore or delete the removed todos",
+                               |x| Ok(Box::new(TrashCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("action", false, &["list: Display the removed todos (default)",
+                                                           "restore id: Move the todo back to the todo list (ex: trash restore 3)",
+                                                           "empty: Delete the todos of the trash"])
+                       .completion(ValueCompletion::Choices(vec!["list", "restore", "empty"])))
+            .option(OptionDefinition::with_value("older-than", None, "x", &["With empty, only the todos removed for longer than the age (ex: 30d)"]))
+            .option(yes_option("With empty, delete without asking for a confirmation (needed when the input isn't a terminal)"))
+            .option(dry_run_option()),
+        CommandDefinition::new("snooze", Command::Snooze, "Hide a todo from list and next until a date",
+                               |x| Ok(Box::new(SnoozeCommand::new_from_command_result(x)?)))
+            .alias("wait")
+            .value(ValueDefinition::new("id when", true, &["The todo and the date it comes back (ex: 3 2026-11-05, 3 2026-11-05 14:00,",
+                                                           "3 tomorrow, 3 3d or 3 4h), none clears the date (ex: 3 none)"])
+                       .completion(ValueCompletion::OpenTodoIds)),
+        CommandDefinition::new("start", Command::Start, "Start tracking the time spent on a todo",
+                               |x| Ok(Box::new(StartCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("id", true, &["The id of the todo to track (stops the current one)"]).completion(ValueCompletion::OpenTodoIds)),
+        CommandDefinition::new("stop", Command::Stop, "Stop tracking the time of the current todo",
+                               |_| Ok(Box::new(StopCommand {}))),
+        CommandDefinition::new("log", Command::Log, "Log time manually on a todo",
+                               |x| Ok(Box::new(LogCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("id duration", true, &["The time spent on a todo (ex: 45m, 2h or 1h30m)"])),
+        CommandDefinition::new("report", Command::Report, "Display a report on the todos",
+                               |x| Ok(Box::new(ReportCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("name", true, &["The report to display:",
+                                                        "estimates: Compare the estimates to the actual durations"])
+                       .completion(ValueCompletion::Choices(vec!["estimates"]))),
+        CommandDefinition::new("stats", Command::Stats, "Display statistics on the todos",
+                               |x| Ok(Box::new(StatsCommand::new_from_command_result(x)?)))
+            .option(OptionDefinition::with_value("period", None, "x", &["Count the todos created and completed per day, week (Default) or month"])
+                        .choices(&["day", "week", "month"]))
+            .option(OptionDefinition::flag("chart", Some('c'), &["Display the counts as sparkline charts"])),
+        CommandDefinition::new("import", Command::Import, "Import todos from a file",
+                               |x| Ok(Box::new(ImportCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("file", true, &["The file to import the todos from"]).completion(ValueCompletion::Files))
+            .option(OptionDefinition::with_value("from", Some('f'), "x", &["The format of the file, possible values are csv, ics, markdown, org,",
+                                                                            "taskwarrior or todotxt"])
+                        .choices(&FORMATS))
+            .option(OptionDefinition::with_value("map", None, "field:column,...", &["The csv columns of the todo fields (ex: title:Summary,priority:Prio)"]))
+            .option(date_format_option())
+            .option(OptionDefinition::flag("dry-run", None, &["Preview the todos and the issues without importing anything"])),
+        CommandDefinition::new("export", Command::Export, "Export the todos",
+                               |x| Ok(Box::new(ExportCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("file", false, &["The file to export the todos to (Default: standard output)"]).completion(ValueCompletion::Files))
+            .option(OptionDefinition::with_value("to", None, "x", &["The format of the export, possible values are csv, ics, markdown, org,",
+                                                                     "taskwarrior or todotxt"])
+                        .choices(&FORMATS))
+            .option(where_option("Export only the todos matching the criteria"))
+            .option(OptionDefinition::with_value("columns", None, "x,...", &["The csv columns (Default: id,title,priority,status,created,completed)",
+                                                                              "Possible values are id, title, priority, status, created, completed,",
+                                                                              "estimate and time_spent"]))
+            .option(date_format_option()),
+        CommandDefinition::new("completions", Command::Completions, "Print the shell completion script",
+                               |x| Ok(Box::new(CompletionsCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("shell", true, &["The shell, possible values are bash, zsh and fish",
+                                                         "(ex: beaver completions bash > ~/.local/share/bash-completion/completions/beaver)"])
+                       .completion(ValueCompletion::Choices(vec!["bash", "zsh", "fish"]))),
+        CommandDefinition::new("config", Command::Config, "Display or change the settings of ~/.beaver/config.toml",
+                               |x| Ok(Box::new(ConfigCommand::new_from_command_result(x)?)))
+            .value(ValueDefinition::new("action", true, &["list: Display the settings",
+                                                          "get key: Display a setting (ex: config get list.sort)",
+                                                          "set key value: Change a setting (ex: config set add.priority H)",
+                                                          "unset key: Remove a setting",
+                                                          "(ex: config set aliases.urgent \"list --priority=H\")"])
+                       .completion(ValueCompletion::Choices(vec!["list", "get", "set", "unset"])))
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Command;
+    use crate::cli_definition::{find_command_definition, get_command_definitions, get_usage, OptionDefinition};
+
+    #[test]
+    fn option_definition_get_usage_with_short_and_value_return_both_flags() {
+        let option = OptionDefinition::with_value("priority", Some('p'), "x", &["The priority"]);
+        assert_eq!("-p=x, --priority=x", option.get_usage());
+    }
+
+    #[test]
+    fn option_definition_get_usage_with_flag_without_short_return_long_flag() {
+        let option = OptionDefinition::flag("dry-run", None, &["Preview"]);
+        assert_eq!("--dry-run", option.get_usage());
+    }
+
+    #[test]
+    fn get_command_definitions_have_unique_names_and_flags() {
+        let definitions = get_command_definitions();
+        for (index, definition) in definitions.iter().enumerate() {
+            assert!(definitions[index + 1..].iter().all(|x| !x.is_named(definition.get_name())));
+            assert!(definition.get_aliases().iter().all(|alias| definitions.iter().filter(|x| x.is_named(alias)).count() == 1));
+            for (option_index, option) in definition.get_options().iter().enumerate() {
+                assert!(definition.get_options()[option_index + 1..].iter()
+                    .all(|x| x.get_name() != option.get_name() && (option.get_short().is_none() || x.get_short() != option.get_short())),
+                    "Duplicate option {} for {}", option.get_name(), definition.get_name());
+                assert!(option.get_name() != "help" && option.get_short() != Some('h'));
+            }
+        }
+    }
+
+    #[test]
+    fn get_help_of_add_return_value_and_options() {
+        let help = find_command_definition(Command::Add).unwrap().get_help();
+        assert_eq!("beaver add, a - Add a new todo\n\n\
+                    USAGE:\n    \
+                    beaver add title [OPTIONS]\n        \
+                    title                      The title (text) of the todo\n        \
+                    -p=x, --priority=x         The priority of the todo, possible values are H, M and L\n                                   \
+                    for High, Medium and Low\n        \
+                    -e=x, --estimate=x         The estimated duration of the todo (ex: 45m, 2h or 1h30m)\n        \
+                    -d=x, --due=x              The date the todo is due\n                                   \
+                    (ex: 2026-11-05, 2026-11-05 14:00, tomorrow or 3d)\n        \
+                    -h, --help                 Print this help\n", help);
+    }
+
+    #[test]
+    fn get_help_of_remove_return_criteria() {
+        assert!(find_command_definition(Command::Remove).unwrap().get_help().contains("CRITERIA:"));
+    }
+
+    #[test]
+    fn get_usage_return_every_command() {
+        let usage = get_usage();
+        for definition in get_command_definitions() {
+            assert!(usage.contains(&format!("\n    {:<31}{}\n", definition.get_names(), definition.summary)));
+        }
+        assert!(usage.contains("\n    list, ls                       Display the todo list\n"));
+    }
+}
@@ -1,19 +1,35 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
 
 #[derive(Debug, Eq, Copy)]
 pub enum Command {
-    Unknown,
     Add,
     Edit,
     Done,
-    Delete,
     Fetch,
     List,
     Purge,
     Next,
-    Remove
+    Remove,
+    Start,
+    Stop,
+    Log,
+    Report,
+    Stats,
+    Import,
+    Export,
+    Completions,
+    Config,
+    Tui,
+    Shell,
+    Archive,
+    Unarchive,
+    Trash,
+    Snooze,
+    Agenda,
+    Calendar
 }
 
 impl PartialEq for Command {
@@ -40,9 +56,9 @@ impl CommandResult {
                value : &str,
                options : HashMap<String, String>) -> CommandResult {
         CommandResult {
-            command: command,
+            command,
             value: String::from(value),
-            options: options
+            options
         }
     }
 
@@ -57,6 +73,15 @@ impl CommandResult {
     pub fn get_options(&self) -> &HashMap<String, String> {
         &self.options
     }
+
+    pub fn remove_option(&mut self, name: &str) -> Option<String> {
+        self.options.remove(name)
+    }
+
+    // Add an option unless it is already given
+    pub fn add_default_option(&mut self, name: &str, value: &str) {
+        self.options.entry(name.to_string()).or_insert_with(|| value.to_string());
+    }
 }
 
 #[derive(Eq, Copy, Ord, PartialOrd, Debug, Deserialize, Serialize)]
@@ -78,12 +103,12 @@ impl Clone for Priority {
     }
 }
 
-impl ToString for Priority {
-    fn to_string(&self) -> String {
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Priority::High => String::from("High"),
-            Priority::Medium => String::from("Medium"),
-            Priority::Low => String::from("Low")
+            Priority::High => write!(f, "High"),
+            Priority::Medium => write!(f, "Medium"),
+            Priority::Low => write!(f, "Low")
         }
     }
 }
@@ -100,6 +125,14 @@ impl Priority {
             _ => None
         }
     }
+
+    pub fn to_letter(self) -> &'static str {
+        match self {
+            Priority::High => "H",
+            Priority::Medium => "M",
+            Priority::Low => "L"
+        }
+    }
 }
 
 pub trait ExecutableCommand {
@@ -118,15 +151,16 @@ mod tests {
 
     #[test]
     fn command_equality_with_different_return_true() {
-        assert_ne!(Command::Add, Command::Delete);
+        assert_ne!(Command::Add, Command::Remove);
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn command_clone_return_valid_copy() {
         let mut actual = Command::Add;
         let clone = actual.clone();
-        actual = Command::Delete;
-        assert_eq!(actual, Command::Delete);
+        actual = Command::Remove;
+        assert_eq!(actual, Command::Remove);
         assert_eq!(clone, Command::Add);
     }
 
@@ -179,6 +213,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn priority_clone_return_valid_copy() {
         let mut actual = Priority::High;
         let clone = actual.clone();
@@ -232,6 +267,13 @@ mod tests {
         assert_eq!(Priority::Low, Priority::from_string("L").unwrap());
     }
 
+    #[test]
+    fn priority_to_letter_return_uppercase_letter() {
+        assert_eq!("H", Priority::High.to_letter());
+        assert_eq!("M", Priority::Medium.to_letter());
+        assert_eq!("L", Priority::Low.to_letter());
+    }
+
     #[test]
     fn priority_from_char_with_empty_return_none() {
         assert!(Priority::from_string("").is_none());
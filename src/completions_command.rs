@@ -0,0 +1,326 @@
+use crate::cli_definition::{get_command_definitions, CommandDefinition, OptionDefinition, ValueCompletion};
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::read_all_todos;
+use crate::todo::Todo;
+use std::error::Error;
+
+#[derive(Debug, PartialEq)]
+pub enum CompletionsOutput {
+    Bash,
+    Zsh,
+    Fish,
+    // The ids and titles of the open todos, called by the scripts to complete ids
+    OpenTodoIds
+}
+
+#[derive(Debug)]
+pub struct CompletionsCommand {
+    output: CompletionsOutput
+}
+
+impl CompletionsCommand {
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<CompletionsCommand, Box<dyn Error>> {
+        let output = match command_result.get_value().trim().to_lowercase().as_str() {
+            "bash" => CompletionsOutput::Bash,
+            "zsh" => CompletionsOutput::Zsh,
+            "fish" => CompletionsOutput::Fish,
+            "ids" => CompletionsOutput::OpenTodoIds,
+            "" => return Err("Value cannot be empty".into()),
+            _ => return Err("Invalid shell. Must be bash, zsh or fish".into())
+        };
+        Ok(CompletionsCommand { output })
+    }
+}
+
+impl ExecutableCommand for CompletionsCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let definitions = get_command_definitions();
+        match self.output {
+            CompletionsOutput::Bash => print!("{}", get_bash_script(&definitions)),
+            CompletionsOutput::Zsh => print!("{}", get_zsh_script(&definitions)),
+            CompletionsOutput::Fish => print!("{}", get_fish_script(&definitions)),
+            CompletionsOutput::OpenTodoIds => {
+                for line in get_open_todo_id_lines(&read_all_todos()?) {
+                    println!("{}", line);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Return id<TAB>title for each open todo
+pub fn get_open_todo_id_lines(todos: &[Todo]) -> Vec<String> {
+    let mut todos: Vec<&Todo> = todos.iter().filter(|x| !x.get_completed()).collect();
+    todos.sort_unstable_by_key(|x| x.get_id());
+    todos.iter().map(|x| format!("{}\t{}", x.get_id(), x.get_title().replace(['\t', '\n'], " "))).collect()
+}
+
+// Quote a text for bash and zsh
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+// Return the name of the command followed by its aliases
+fn get_command_names(definition: &CommandDefinition) -> Vec<&'static str> {
+    let mut names = vec![definition.get_name()];
+    names.extend(definition.get_aliases());
+    names
+}
+
+fn get_option_flags(option: &OptionDefinition) -> Vec<String> {
+    let mut flags = vec![];
+    if let Some(short) = option.get_short() {
+        flags.push(format!("-{}", short));
+    }
+    flags.push(format!("--{}", option.get_name()));
+    flags
+}
+
+pub fn get_bash_script(definitions: &[CommandDefinition]) -> String {
+    let names: Vec<&str> = definitions.iter().flat_map(get_command_names).collect();
+    let mut cases = String::new();
+    for definition in definitions {
+        let mut option_cases = String::new();
+        let mut flags = vec![String::from("-h"), String::from("--help")];
+        for option in definition.get_options() {
+            let option_flags = get_option_flags(option);
+            if let ValueCompletion::Choices(choices) = option.get_completion() {
+                option_cases.push_str(&format!("                {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;\n",
+                                               option_flags.join("|"), choices.join(" ")));
+            }
+            let suffix = if option.takes_value() { "=" } else { "" };
+            flags.extend(option_flags.iter().map(|x| format!("{}{}", x, suffix)));
+        }
+        let value_completion = match definition.get_value().map(|x| x.get_completion()) {
+            Some(ValueCompletion::Choices(choices)) => format!("            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n", choices.join(" ")),
+            Some(ValueCompletion::Files) => String::from("            compopt -o filenames\n            COMPREPLY=($(compgen -f -- \"$cur\"))\n"),
+            Some(ValueCompletion::OpenTodoIds) => String::from("            COMPREPLY=($(compgen -W \"$(_beaver_open_todo_ids)\" -- \"$cur\"))\n"),
+            Some(ValueCompletion::Nothing) | None => String::new()
+        };
+        cases.push_str(&format!("        {})\n", get_command_names(definition).join("|")));
+        if !option_cases.is_empty() {
+            cases.push_str(&format!("            case \"$option\" in\n{}            esac\n", option_cases));
+        }
+        cases.push_str(&format!("            if [[ \"$cur\" == -* ]]; then\n\
+                                 \x20               COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n\
+                                 \x20               [[ \"${{COMPREPLY[0]}}\" == *= ]] && compopt -o nospace\n\
+                                 \x20               return\n\
+                                 \x20           fi\n\
+                                 {}\
+                                 \x20           ;;\n", flags.join(" "), value_completion));
+    }
+    format!("# bash completion for beaver, generated by beaver completions bash\n\
+             \n\
+             _beaver_open_todo_ids()\n\
+             {{\n\
+             \x20   beaver completions ids 2>/dev/null | cut -f1\n\
+             }}\n\
+             \n\
+             _beaver()\n\
+             {{\n\
+             \x20   local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"\n\
+             \x20   local option=\"${{COMP_WORDS[COMP_CWORD-1]}}\"\n\
+             \x20   if [ \"$COMP_CWORD\" -eq 1 ]; then\n\
+             \x20       COMPREPLY=($(compgen -W \"-h --help -v --version {}\" -- \"$cur\"))\n\
+             \x20       return\n\
+             \x20   fi\n\
+             \x20   # Readline splits --option=value around the =\n\
+             \x20   if [ \"$cur\" = \"=\" ]; then\n\
+             \x20       cur=\"\"\n\
+             \x20   elif [ \"$option\" = \"=\" ]; then\n\
+             \x20       option=\"${{COMP_WORDS[COMP_CWORD-2]}}\"\n\
+             \x20   fi\n\
+             \x20   case \"${{COMP_WORDS[1]}}\" in\n\
+             {}\
+             \x20   esac\n\
+             }}\n\
+             \n\
+             complete -F _beaver beaver\n", names.join(" "), cases)
+}
+
+// Escape the brackets closing the description of an _arguments spec
+fn escape_zsh_description(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
+
+pub fn get_zsh_script(definitions: &[CommandDefinition]) -> String {
+    let commands: Vec<String> = definitions.iter()
+        .flat_map(|x| get_command_names(x).into_iter().map(move |name| format!("        {}", quote(&format!("{}:{}", name, x.get_summary())))))
+        .collect();
+    let mut cases = String::new();
+    for definition in definitions {
+        let mut specs = vec![format!("'(-h --help)'{{-h,--help}}{}", quote("[Print this help]"))];
+        for option in definition.get_options() {
+            let flags = get_option_flags(option);
+            let names = match option.takes_value() {
+                true => flags.iter().map(|x| format!("{}=", x)).collect::<Vec<String>>(),
+                false => flags.clone()
+            };
+            let mut spec = format!("[{}]", escape_zsh_description(option.get_description()));
+            if option.takes_value() {
+                let action = match option.get_completion() {
+                    ValueCompletion::Choices(choices) => format!("({})", choices.join(" ")),
+                    ValueCompletion::Files => String::from("_files"),
+                    _ => String::from(" ")
+                };
+                spec.push_str(&format!(":{}:{}", option.get_name(), action));
+            }
+            specs.push(match names.len() {
+                1 => quote(&format!("{}{}", names[0], spec)),
+                _ => format!("'({})'{{{}}}{}", flags.join(" "), names.join(","), quote(&spec))
+            });
+        }
+        if let Some(value) = definition.get_value() {
+            let action = match value.get_completion() {
+                ValueCompletion::Choices(choices) => format!("({})", choices.join(" ")),
+                ValueCompletion::Files => String::from("_files"),
+                ValueCompletion::OpenTodoIds => String::from("_beaver_open_todo_ids"),
+                ValueCompletion::Nothing => String::from(" ")
+            };
+            let position = match value.get_completion() {
+                ValueCompletion::Files | ValueCompletion::Choices(_) if value.is_required() => ":",
+                ValueCompletion::Files | ValueCompletion::Choices(_) => "::",
+                _ => "*:"
+            };
+            specs.push(quote(&format!("{}{}:{}", position, value.get_name(), action)));
+        }
+        cases.push_str(&format!("        {})\n            _arguments -s \\\n                {}\n            ;;\n",
+                                get_command_names(definition).join("|"), specs.join(" \\\n                ")));
+    }
+    format!("#compdef beaver\n\
+             # zsh completion for beaver, generated by beaver completions zsh\n\
+             \n\
+             _beaver_open_todo_ids() {{\n\
+             \x20   local -a ids\n\
+             \x20   ids=(${{(f)\"$(beaver completions ids 2>/dev/null)\"}})\n\
+             \x20   ids=(${{ids/$'\\t'/:}})\n\
+             \x20   _describe -t ids 'todo' ids\n\
+             }}\n\
+             \n\
+             _beaver() {{\n\
+             \x20   local -a commands\n\
+             \x20   commands=(\n\
+             {}\n\
+             \x20   )\n\
+             \x20   if (( CURRENT == 2 )); then\n\
+             \x20       _describe -t commands 'command' commands\n\
+             \x20       return\n\
+             \x20   fi\n\
+             \x20   local command=$words[2]\n\
+             \x20   shift words\n\
+             \x20   (( CURRENT-- ))\n\
+             \x20   case $command in\n\
+             {}\
+             \x20   esac\n\
+             }}\n\
+             \n\
+             _beaver \"$@\"\n", commands.join("\n"), cases)
+}
+
+// Quote a text for fish, where only \ and ' are escaped between single quotes
+fn quote_fish(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+pub fn get_fish_script(definitions: &[CommandDefinition]) -> String {
+    let mut lines = vec![String::from("# fish completion for beaver, generated by beaver completions fish"),
+                         String::from("complete -c beaver -f"),
+                         String::from("complete -c beaver -n __fish_use_subcommand -s h -l help -d 'Print help information'"),
+                         String::from("complete -c beaver -n __fish_use_subcommand -s v -l version -d 'Print version info and exit'")];
+    for definition in definitions {
+        for name in get_command_names(definition) {
+            lines.push(format!("complete -c beaver -n __fish_use_subcommand -a {} -d {}", name, quote_fish(definition.get_summary())));
+        }
+    }
+    for definition in definitions {
+        let prefix = format!("complete -c beaver -n '__fish_seen_subcommand_from {}'", get_command_names(definition).join(" "));
+        lines.push(format!("{} -s h -l help -d 'Print this help'", prefix));
+        for option in definition.get_options() {
+            let mut line = prefix.clone();
+            if let Some(short) = option.get_short() {
+                line.push_str(&format!(" -s {}", short));
+            }
+            line.push_str(&format!(" -l {}", option.get_name()));
+            if option.takes_value() {
+                line.push_str(match option.get_completion() {
+                    ValueCompletion::Files => " -r -F",
+                    _ => " -x"
+                });
+            }
+            if let ValueCompletion::Choices(choices) = option.get_completion() {
+                line.push_str(&format!(" -a {}", quote_fish(&choices.join(" "))));
+            }
+            line.push_str(&format!(" -d {}", quote_fish(option.get_description())));
+            lines.push(line);
+        }
+        match definition.get_value().map(|x| x.get_completion()) {
+            Some(ValueCompletion::Choices(choices)) => lines.push(format!("{} -a {}", prefix, quote_fish(&choices.join(" ")))),
+            Some(ValueCompletion::Files) => lines.push(format!("{} -F", prefix)),
+            Some(ValueCompletion::OpenTodoIds) => lines.push(format!("{} -a '(beaver completions ids 2>/dev/null)'", prefix)),
+            Some(ValueCompletion::Nothing) | None => ()
+        }
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli_definition::get_command_definitions;
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use crate::completions_command::{get_bash_script, get_fish_script, get_open_todo_id_lines, get_zsh_script, CompletionsCommand, CompletionsOutput};
+    use crate::todo::Todo;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    #[test]
+    fn new_from_command_result_with_invalid_shell_return_error() {
+        let command_result = CommandResult::new(Command::Completions, "Zsh", HashMap::new());
+        assert_eq!(CompletionsOutput::Zsh, CompletionsCommand::new_from_command_result(&command_result).unwrap().output);
+        let command_result = CommandResult::new(Command::Completions, "powershell", HashMap::new());
+        assert_eq!("Invalid shell. Must be bash, zsh or fish",
+                   CompletionsCommand::new_from_command_result(&command_result).err().unwrap().to_string());
+    }
+
+    #[test]
+    fn get_open_todo_id_lines_return_open_todos_sorted_by_id() {
+        let mut completed = Todo::new(1, "Pay the bills", Priority::Low, Utc::now()).unwrap();
+        completed.set_completed(true, None);
+        let todos = vec![Todo::new(3, "Call mom", Priority::High, Utc::now()).unwrap(),
+                         completed,
+                         Todo::new(2, "Write the report", Priority::Low, Utc::now()).unwrap()];
+        assert_eq!(vec!["2\tWrite the report", "3\tCall mom"], get_open_todo_id_lines(&todos));
+    }
+
+    #[test]
+    fn get_bash_script_complete_commands_options_and_values() {
+        let script = get_bash_script(&get_command_definitions());
+        assert!(script.contains("compgen -W \"-h --help -v --version list ls add a edit"));
+        assert!(script.contains("-p|--priority) COMPREPLY=($(compgen -W \"H M L\" -- \"$cur\")); return ;;"));
+        assert!(script.contains("-h --help -a --all -s= --sort="));
+        assert!(script.contains("        done|d)\n"));
+        assert!(script.contains("$(_beaver_open_todo_ids)"));
+        assert!(script.ends_with("complete -F _beaver beaver\n"));
+    }
+
+    #[test]
+    fn get_zsh_script_complete_commands_options_and_values() {
+        let script = get_zsh_script(&get_command_definitions());
+        assert!(script.starts_with("#compdef beaver\n"));
+        assert!(script.contains("'add:Add a new todo'"));
+        assert!(script.contains("'(-p --priority)'{-p=,--priority=}'[The priority of the todo, possible values are H, M and L]:priority:(H M L)'"));
+        assert!(script.contains("'*:ids:_beaver_open_todo_ids'"));
+        assert!(script.contains("':file:_files'"));
+        assert!(script.contains("'::file:_files'"));
+    }
+
+    #[test]
+    fn get_fish_script_complete_commands_options_and_values() {
+        let script = get_fish_script(&get_command_definitions());
+        assert!(script.contains("complete -c beaver -n __fish_use_subcommand -a add -d 'Add a new todo'\n"));
+        assert!(script.contains("complete -c beaver -n '__fish_seen_subcommand_from list ls' -s s -l sort -x \
+                                 -a 'prioritydesc priority creationtimedesc creationtime' -d 'Sort the todo list by one of the following'\n"));
+        assert!(script.contains("complete -c beaver -n '__fish_seen_subcommand_from done d' -a '(beaver completions ids 2>/dev/null)'\n"));
+        assert!(script.contains("complete -c beaver -n '__fish_seen_subcommand_from import' -F\n"));
+    }
+}
@@ -0,0 +1,413 @@
+use crate::agenda_command::AgendaCommand;
+use crate::args_analyzer::{analyze_args, expand_aliases, get_suggestion};
+use crate::cli_definition::{get_command_definitions, ValueCompletion};
+use crate::common_structs::CommandResult;
+use crate::csv_format::CsvFormat;
+use crate::duration_format::{parse_age, parse_duration};
+use chrono::Duration;
+use home::home_dir;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use toml::{Table, Value};
+
+const COLOR_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+const COLOR_KEYS: [&str; 6] = ["success", "warning", "error", "high", "medium", "low"];
+const TRASH_EXPIRY_KEY: &str = "trash.expire-after";
+const DEFAULT_TRASH_EXPIRY_DAYS: i64 = 30;
+// The options with a default value, the others act on the todos and are only given on the command line
+const DEFAULT_OPTIONS: [&str; 14] = ["add.estimate", "add.priority", "agenda.days", "archive.older-than", "export.columns",
+                                     "export.date-format", "export.to", "import.date-format", "import.from", "import.map",
+                                     "list.all", "list.sort", "stats.chart", "stats.period"];
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+// The settings of ~/.beaver/config.toml:
+// [aliases] the user defined commands (urgent = "list --priority=H")
+// [colors] the color of the success, warning and error messages and of the todos by priority (high = "red")
+// [trash] expire-after, the age of the removed todos deleted from the trash (30d by default or never)
+// [<command>] the default value of the options of a command (sort = "creationtime" under [list])
+#[derive(Debug, Default)]
+pub struct Config {
+    aliases: HashMap<String, String>,
+    colors: HashMap<String, u8>,
+    trash_expiry: Option<String>,
+    defaults: HashMap<String, HashMap<String, String>>
+}
+
+impl Config {
+    pub fn new_from_table(table: &Table) -> Result<Config, Box<dyn Error>> {
+        let mut config = Config::default();
+        for (section, value) in table {
+            let entries = match value {
+                Value::Table(entries) => entries,
+                _ => return Err(format!("Invalid key {} in the config file, settings must be in a section (ex: [list])", section).into())
+            };
+            for (key, value) in entries {
+                let key = format!("{}.{}", section, key);
+                validate_setting(&key, value)?;
+                config.add_setting(&key, value);
+            }
+        }
+        for name in config.aliases.keys() {
+            validate_alias(name, &config.aliases)?;
+        }
+        Ok(config)
+    }
+
+    // Add a validated setting
+    fn add_setting(&mut self, key: &str, value: &Value) {
+        let (section, name) = key.split_once('.').unwrap();
+        match (section, value) {
+            ("aliases", Value::String(expansion)) => {
+                self.aliases.insert(name.to_lowercase(), expansion.clone());
+            },
+            ("colors", Value::String(color)) => {
+                self.colors.insert(name.to_string(), parse_color(color).unwrap());
+            },
+            ("trash", Value::String(expiry)) if key == TRASH_EXPIRY_KEY => {
+                self.trash_expiry = Some(expiry.clone());
+            },
+            (_, Value::Boolean(true)) => {
+                self.defaults.entry(section.to_string()).or_default().insert(name.to_string(), String::new());
+            },
+            (_, Value::String(option_value)) => {
+                self.defaults.entry(section.to_string()).or_default().insert(name.to_string(), option_value.clone());
+            },
+            _ => ()
+        }
+    }
+
+    pub fn get_aliases(&self) -> &HashMap<String, String> {
+        &self.aliases
+    }
+
+    // Return the age of the todos deleted from the trash, None when they never expire
+    pub fn get_trash_expiry(&self) -> Option<Duration> {
+        match self.trash_expiry.as_deref() {
+            Some(expiry) if expiry.trim().eq_ignore_ascii_case("never") => None,
+            Some(expiry) => parse_age(expiry).ok(),
+            None => Some(Duration::days(DEFAULT_TRASH_EXPIRY_DAYS))
+        }
+    }
+
+    pub fn get_default_option(&self, command_name: &str, option_name: &str) -> Option<&str> {
+        self.defaults.get(command_name).and_then(|x| x.get(option_name)).map(|x| x.as_str())
+    }
+
+    // Add the default options of the command that aren't given in the arguments
+    pub fn apply_defaults(&self, command_result: &mut CommandResult) {
+        let definition = get_command_definitions().into_iter().find(|x| x.get_command() == command_result.get_command());
+        let defaults = match definition.and_then(|x| self.defaults.get(x.get_name())) {
+            Some(defaults) => defaults,
+            None => return
+        };
+        for (name, value) in defaults {
+            command_result.add_default_option(name, value);
+        }
+    }
+
+    // Return the ANSI value of a color set in the config file
+    pub fn get_color(&self, key: &str) -> Option<u8> {
+        self.colors.get(key).copied()
+    }
+}
+
+pub fn get_config_file() -> String {
+    format!("{}/.beaver/config.toml", home_dir().unwrap().display())
+}
+
+// Return the content of the config file without validating the settings
+pub fn read_config_table() -> Result<Table, Box<dyn Error>> {
+    let config_file = get_config_file();
+    if !Path::new(&config_file).exists() {
+        return Ok(Table::new());
+    }
+    parse_config(&fs::read_to_string(&config_file)?)
+}
+
+fn parse_config(content: &str) -> Result<Table, Box<dyn Error>> {
+    content.parse::<Table>().map_err(|e| format!("Unable to read the config file {}: {}", get_config_file(), e.message()).into())
+}
+
+pub fn write_config_table(table: &Table) -> Result<(), Box<dyn Error>> {
+    let beaver_folder = format!("{}/.beaver", home_dir().unwrap().display());
+    if !Path::new(&beaver_folder).exists() {
+        fs::create_dir(&beaver_folder)?;
+    }
+    fs::write(get_config_file(), toml::to_string(table)?)?;
+    Ok(())
+}
+
+// Read and validate the config file once, the commands then use get_config
+pub fn load_config() -> Result<&'static Config, Box<dyn Error>> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::new_from_table(&read_config_table()?)?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+// Return the loaded config, or the default settings if the config file hasn't been loaded
+pub fn get_config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+// Return the ANSI value of a color name like red or bright-red
+fn parse_color(name: &str) -> Option<u8> {
+    let name = name.trim().to_lowercase();
+    let (base_name, offset) = match name.strip_prefix("bright-") {
+        Some(base_name) => (base_name, 8),
+        None => (name.as_str(), 0)
+    };
+    COLOR_NAMES.iter().position(|x| *x == base_name).map(|x| x as u8 + offset)
+}
+
+// Return the choices like H, M or L
+fn join_choices(choices: &[&str]) -> String {
+    match choices.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, others)) => format!("{} or {}", others.join(", "), last),
+        None => String::new()
+    }
+}
+
+// Check that the default value of an option like list.sort exists and is valid
+fn validate_default_option(command_name: &str, option_name: &str, value: &Value) -> Result<(), Box<dyn Error>> {
+    let definitions = get_command_definitions();
+    let definition = match definitions.iter().find(|x| x.get_name() == command_name) {
+        Some(definition) => definition,
+        None => {
+            let mut sections = vec!["aliases", "colors"];
+            sections.extend(definitions.iter().map(|x| x.get_name()));
+            return Err(format!("Unknown section {} in the config file{}", command_name, get_suggestion(command_name, &sections, "")).into());
+        }
+    };
+    let key = format!("{}.{}", command_name, option_name);
+    let option = match definition.get_options().iter().find(|x| x.get_name() == option_name) {
+        Some(option) => option,
+        None => {
+            let option_names: Vec<&str> = definition.get_options().iter().map(|x| x.get_name())
+                .filter(|x| DEFAULT_OPTIONS.contains(&format!("{}.{}", command_name, x).as_str()))
+                .collect();
+            let suggestion = get_suggestion(option_name, &option_names, &format!("{}.", command_name));
+            return Err(format!("Unknown key {} in the config file{}", key, suggestion).into());
+        }
+    };
+    if !DEFAULT_OPTIONS.contains(&key.as_str()) {
+        return Err(format!("The option {} cannot have a default value in the config file, give it on the command line", key).into());
+    }
+    match (option.takes_value(), value) {
+        (false, Value::Boolean(_)) => Ok(()),
+        (false, _) => Err(format!("Invalid value for {} in the config file, must be true or false", key).into()),
+        (true, Value::String(option_value)) => match option.get_completion() {
+            ValueCompletion::Choices(choices) if !choices.iter().any(|x| x.eq_ignore_ascii_case(option_value.trim())) =>
+                Err(format!("Invalid value for {} in the config file, must be {}", key, join_choices(choices)).into()),
+            _ => validate_option_value(&key, option_name, option_value)
+                .map_err(|e| format!("Invalid value for {} in the config file: {}", key, e).into())
+        },
+        (true, _) => Err(format!("Invalid value for {} in the config file, must be a text", key).into())
+    }
+}
+
+// Check a default value with the parser of the command, so a wrong default fails when it's set
+fn validate_option_value(key: &str, option_name: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    match key {
+        "add.estimate" => parse_duration(value).map(|_| ()),
+        "agenda.days" => AgendaCommand::parse_days(value).map(|_| ()),
+        "archive.older-than" => parse_age(value).map(|_| ()),
+        "export.columns" | "export.date-format" | "import.date-format" | "import.map" =>
+            CsvFormat::new_from_options(&HashMap::from([(option_name.to_string(), value.to_string())])).map(|_| ()),
+        _ => Ok(())
+    }
+}
+
+// Check that a setting like list.sort has a valid value
+pub fn validate_setting(key: &str, value: &Value) -> Result<(), Box<dyn Error>> {
+    let (section, name) = match key.split_once('.') {
+        Some((section, name)) if !section.is_empty() && !name.is_empty() => (section, name),
+        _ => return Err(format!("Invalid key {}, must be section.name (ex: list.sort)", key).into())
+    };
+    if key == TRASH_EXPIRY_KEY {
+        return match value.as_str() {
+            Some(expiry) if expiry.trim().eq_ignore_ascii_case("never") || parse_age(expiry).is_ok() => Ok(()),
+            _ => Err(format!("Invalid value for {} in the config file, must be an age (ex: 30d or 2w) or never", key).into())
+        };
+    }
+    match section {
+        "aliases" => match value {
+            Value::String(expansion) if !expansion.trim().is_empty() => Ok(()),
+            _ => Err(format!("Invalid value for {} in the config file, must be a command (ex: \"list --all\")", key).into())
+        },
+        "colors" => {
+            if !COLOR_KEYS.contains(&name) {
+                return Err(format!("Unknown key {} in the config file{}", key, get_suggestion(name, &COLOR_KEYS, "colors.")).into());
+            }
+            match value.as_str().and_then(parse_color) {
+                Some(_) => Ok(()),
+                None => Err(format!("Invalid value for {} in the config file, must be {} (or bright-x)", key, join_choices(&COLOR_NAMES)).into())
+            }
+        },
+        _ => validate_default_option(section, name, value)
+    }
+}
+
+// Check that the alias expands to a valid command, its options are known by the command
+pub fn validate_alias(name: &str, aliases: &HashMap<String, String>) -> Result<(), Box<dyn Error>> {
+    match expand_aliases(vec![name.to_string()], aliases).and_then(analyze_args) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Invalid value for aliases.{} in the config file: {}", name, e).into())
+    }
+}
+
+// Convert a value given on the command line to the type expected by the setting
+pub fn to_setting_value(key: &str, value: &str) -> Value {
+    match value.trim() {
+        "true" if !key.starts_with("aliases.") => Value::Boolean(true),
+        "false" if !key.starts_with("aliases.") => Value::Boolean(false),
+        _ => Value::String(value.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::{Command, CommandResult};
+    use crate::args_analyzer::{analyze_args, expand_aliases};
+    use crate::config::{parse_color, parse_config, to_setting_value, validate_setting, Config};
+    use crate::list_command::ListCommand;
+    use chrono::Duration;
+    use std::collections::HashMap;
+    use toml::Value;
+
+    #[test]
+    fn new_from_table_with_sections_return_config() {
+        let table = parse_config("[aliases]\nurgent = \"list --priority=H --sort=creationtime\"\nToday = \"list -a\"\n\
+                                  [colors]\nsuccess = \"bright-blue\"\nhigh = \"red\"\n\
+                                  [list]\nsort = \"creationtime\"\nall = true\n").unwrap();
+        let config = Config::new_from_table(&table).unwrap();
+        assert_eq!(2, config.get_aliases().len());
+        assert_eq!("list --priority=H --sort=creationtime", config.get_aliases()["urgent"]);
+        assert_eq!("list -a", config.get_aliases()["today"]);
+        assert_eq!(Some(12), config.get_color("success"));
+        assert_eq!(Some(1), config.get_color("high"));
+        assert_eq!(None, config.get_color("low"));
+        assert_eq!("creationtime", config.defaults["list"]["sort"]);
+        assert_eq!("", config.defaults["list"]["all"]);
+    }
+
+    #[test]
+    fn new_from_table_with_example_alias_return_valid_list_command() {
+        let table = parse_config("[aliases]\nurgent = \"list --priority=H --sort=creationtime\"\n").unwrap();
+        let config = Config::new_from_table(&table).unwrap();
+        let args = expand_aliases(vec![String::from("urgent"), String::from("-a")], config.get_aliases()).unwrap();
+        let command_result = analyze_args(args).unwrap();
+        assert!(Command::List == command_result.get_command());
+        assert_eq!("H", command_result.get_options()["priority"]);
+        assert_eq!("creationtime", command_result.get_options()["sort"]);
+        assert!(command_result.get_options().contains_key("all"));
+        assert!(ListCommand::new_from_command_result(&command_result).is_ok());
+    }
+
+    #[test]
+    fn new_from_table_with_invalid_alias_return_error() {
+        let table = parse_config("[aliases]\nurgent = \"list --priorty=H\"\n").unwrap();
+        assert_eq!("Invalid value for aliases.urgent in the config file: Unknown option --priorty for list. Did you mean --priority?",
+                   Config::new_from_table(&table).err().unwrap().to_string());
+        let table = parse_config("[aliases]\nhot = \"urgent\"\nurgent = \"hot\"\n").unwrap();
+        assert!(Config::new_from_table(&table).err().unwrap().to_string().contains("refers to itself"));
+    }
+
+    #[test]
+    fn new_from_table_with_invalid_key_return_error_naming_key() {
+        let table = parse_config("[list]\nsrot = \"priority\"\n").unwrap();
+        assert_eq!("Unknown key list.srot in the config file. Did you mean list.sort?",
+                   Config::new_from_table(&table).err().unwrap().to_string());
+        let table = parse_config("[lsit]\nsort = \"priority\"\n").unwrap();
+        assert_eq!("Unknown section lsit in the config file. Did you mean list?",
+                   Config::new_from_table(&table).err().unwrap().to_string());
+        let table = parse_config("sort = \"priority\"\n").unwrap();
+        assert_eq!("Invalid key sort in the config file, settings must be in a section (ex: [list])",
+                   Config::new_from_table(&table).err().unwrap().to_string());
+    }
+
+    #[test]
+    fn validate_setting_with_invalid_values_return_error() {
+        assert_eq!("Invalid value for add.priority in the config file, must be H, M or L",
+                   validate_setting("add.priority", &Value::String(String::from("X"))).err().unwrap().to_string());
+        assert_eq!("Invalid value for list.all in the config file, must be true or false",
+                   validate_setting("list.all", &Value::String(String::from("yes"))).err().unwrap().to_string());
+        assert_eq!("Invalid value for aliases.urgent in the config file, must be a command (ex: \"list --all\")",
+                   validate_setting("aliases.urgent", &Value::Integer(3)).err().unwrap().to_string());
+        assert!(validate_setting("colors.error", &Value::String(String::from("pink"))).is_err());
+        assert!(validate_setting("add.priority", &Value::String(String::from("h"))).is_ok());
+    }
+
+    #[test]
+    fn validate_setting_with_invalid_free_values_return_error_naming_key() {
+        assert_eq!("Invalid value for add.estimate in the config file: Invalid duration abc. Must be like 45m, 2h or 1h30m",
+                   validate_setting("add.estimate", &Value::String(String::from("abc"))).err().unwrap().to_string());
+        assert_eq!("Invalid value for agenda.days in the config file: Invalid number of days 0. Must be between 1 and 366",
+                   validate_setting("agenda.days", &Value::String(String::from("0"))).err().unwrap().to_string());
+        assert!(validate_setting("archive.older-than", &Value::String(String::from("soon"))).is_err());
+        assert!(validate_setting("export.columns", &Value::String(String::from("id,color"))).is_err());
+        assert!(validate_setting("import.map", &Value::String(String::from("title=Summary"))).is_err());
+        assert!(validate_setting("export.date-format", &Value::String(String::from(" "))).is_err());
+        assert!(validate_setting("add.estimate", &Value::String(String::from("1h30m"))).is_ok());
+        let table = parse_config("[add]\nestimate = \"abc\"\n").unwrap();
+        assert!(Config::new_from_table(&table).err().unwrap().to_string().starts_with("Invalid value for add.estimate in the config file"));
+    }
+
+    #[test]
+    fn validate_setting_with_action_options_return_error() {
+        assert_eq!("The option edit.priority cannot have a default value in the config file, give it on the command line",
+                   validate_setting("edit.priority", &Value::String(String::from("H"))).err().unwrap().to_string());
+        assert!(validate_setting("remove.yes", &Value::Boolean(true)).is_err());
+        assert!(validate_setting("done.where", &Value::String(String::from("priority=L"))).is_err());
+        assert!(validate_setting("import.dry-run", &Value::Boolean(true)).is_err());
+        assert!(validate_setting("agenda.days", &Value::String(String::from("14"))).is_ok());
+    }
+
+    #[test]
+    fn apply_defaults_keep_options_given_in_arguments() {
+        let table = parse_config("[add]\npriority = \"H\"\nestimate = \"1h\"\n").unwrap();
+        let config = Config::new_from_table(&table).unwrap();
+        let mut options = HashMap::new();
+        options.insert(String::from("priority"), String::from("L"));
+        let mut command_result = CommandResult::new(Command::Add, "Call mom", options);
+        config.apply_defaults(&mut command_result);
+        assert_eq!("L", command_result.get_options()["priority"]);
+        assert_eq!("1h", command_result.get_options()["estimate"]);
+    }
+
+    #[test]
+    fn get_trash_expiry_return_setting_or_30_days() {
+        assert_eq!(Some(Duration::days(30)), Config::default().get_trash_expiry());
+        let config = Config::new_from_table(&parse_config("[trash]\nexpire-after = \"2w\"\n").unwrap()).unwrap();
+        assert_eq!(Some(Duration::days(14)), config.get_trash_expiry());
+        let config = Config::new_from_table(&parse_config("[trash]\nexpire-after = \"never\"\n").unwrap()).unwrap();
+        assert_eq!(None, config.get_trash_expiry());
+        assert_eq!("Invalid value for trash.expire-after in the config file, must be an age (ex: 30d or 2w) or never",
+                   validate_setting("trash.expire-after", &Value::String(String::from("soon"))).err().unwrap().to_string());
+    }
+
+    #[test]
+    fn parse_color_return_ansi_value() {
+        assert_eq!(Some(1), parse_color("Red"));
+        assert_eq!(Some(10), parse_color("bright-green"));
+        assert_eq!(None, parse_color("pink"));
+    }
+
+    #[test]
+    fn to_setting_value_return_boolean_for_flags() {
+        assert_eq!(Value::Boolean(true), to_setting_value("list.all", "true"));
+        assert_eq!(Value::String(String::from("true")), to_setting_value("aliases.yes", "true"));
+        assert_eq!(Value::String(String::from("H")), to_setting_value("add.priority", " H "));
+    }
+
+    #[test]
+    fn parse_config_with_invalid_toml_return_error() {
+        assert!(parse_config("[aliases\n").err().unwrap().to_string().starts_with("Unable to read the config file"));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::config::{get_config_file, read_config_table, to_setting_value, validate_alias, validate_setting, write_config_table};
+use crate::output::{reset_color, success_color};
+use toml::{Table, Value};
+use std::collections::HashMap;
+use std::error::Error;
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigAction {
+    List,
+    Get(String),
+    Set(String, String),
+    Unset(String)
+}
+
+#[derive(Debug)]
+pub struct ConfigCommand {
+    action: ConfigAction
+}
+
+impl ConfigCommand {
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<ConfigCommand, Box<dyn Error>> {
+        let value = command_result.get_value().trim();
+        let (action_name, rest) = value.split_once(char::is_whitespace).unwrap_or((value, ""));
+        let (key, setting_value) = rest.trim().split_once(char::is_whitespace).unwrap_or((rest.trim(), ""));
+        let key = key.to_lowercase();
+        let action = match (action_name.to_lowercase().as_str(), key.is_empty(), setting_value.trim().is_empty()) {
+            ("", _, _) => return Err("Value cannot be empty".into()),
+            ("list", true, _) => ConfigAction::List,
+            ("get", false, true) => ConfigAction::Get(key),
+            ("set", false, false) => ConfigAction::Set(key, setting_value.trim().to_string()),
+            ("unset", false, true) => ConfigAction::Unset(key),
+            ("list", _, _) | ("get", _, _) | ("set", _, _) | ("unset", _, _) =>
+                return Err("Invalid arguments. Usage: config list, config get key, config set key value or config unset key".into()),
+            _ => return Err("Invalid action. Must be list, get, set or unset".into())
+        };
+        Ok(ConfigCommand { action })
+    }
+}
+
+// Return the settings as section.name = value lines
+pub fn get_setting_lines(table: &Table) -> Vec<String> {
+    let mut lines = vec![];
+    for (section, entries) in table {
+        match entries {
+            Value::Table(entries) => {
+                for (name, value) in entries {
+                    lines.push(format!("{}.{} = {}", section, name, value));
+                }
+            },
+            _ => lines.push(format!("{} = {}", section, entries))
+        }
+    }
+    lines
+}
+
+fn split_key(key: &str) -> Result<(&str, &str), Box<dyn Error>> {
+    match key.split_once('.') {
+        Some((section, name)) if !section.is_empty() && !name.is_empty() => Ok((section, name)),
+        _ => Err(format!("Invalid key {}, must be section.name (ex: list.sort)", key).into())
+    }
+}
+
+// The aliases of the config file by name
+fn get_aliases(table: &Table) -> HashMap<String, String> {
+    let entries = table.get("aliases").and_then(|x| x.as_table());
+    entries.into_iter().flatten()
+        .filter_map(|(name, value)| value.as_str().map(|x| (name.to_lowercase(), x.to_string())))
+        .collect()
+}
+
+impl ExecutableCommand for ConfigCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let mut table = read_config_table()?;
+        match &self.action {
+            ConfigAction::List => {
+                for line in get_setting_lines(&table) {
+                    println!("{}", line);
+                }
+            },
+            ConfigAction::Get(key) => {
+                let (section, name) = split_key(key)?;
+                match table.get(section).and_then(|x| x.get(name)) {
+                    Some(Value::String(value)) => println!("{}", value),
+                    Some(value) => println!("{}", value),
+                    None => return Err(format!("The key {} isn't set in {}", key, get_config_file()).into())
+                }
+            },
+            ConfigAction::Set(key, value) => {
+                let (section, name) = split_key(key)?;
+                let setting_value = to_setting_value(key, value);
+                validate_setting(key, &setting_value)?;
+                let entries = table.entry(section).or_insert_with(|| Value::Table(Table::new()));
+                match entries.as_table_mut() {
+                    Some(entries) => entries.insert(name.to_string(), setting_value),
+                    None => return Err(format!("Invalid key {} in the config file, settings must be in a section (ex: [list])", section).into())
+                };
+                // The alias is checked with the other aliases it may expand to
+                if section == "aliases" {
+                    let aliases = get_aliases(&table);
+                    validate_alias(&name.to_lowercase(), &aliases)?;
+                }
+                write_config_table(&table)?;
+                println!("{}The setting {} has been changed to {}!{}", success_color(), key, value, reset_color());
+            },
+            ConfigAction::Unset(key) => {
+                let (section, name) = split_key(key)?;
+                let removed = table.get_mut(section).and_then(|x| x.as_table_mut()).and_then(|x| x.remove(name));
+                if removed.is_none() {
+                    return Err(format!("The key {} isn't set in {}", key, get_config_file()).into());
+                }
+                if table.get(section).and_then(|x| x.as_table()).map(|x| x.is_empty()).unwrap_or(false) {
+                    table.remove(section);
+                }
+                write_config_table(&table)?;
+                println!("{}The setting {} has been removed!{}", success_color(), key, reset_color());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::{Command, CommandResult};
+    use crate::config_command::{get_setting_lines, ConfigAction, ConfigCommand};
+    use std::collections::HashMap;
+    use toml::Table;
+
+    fn new_config_command(value: &str) -> Result<ConfigCommand, Box<dyn std::error::Error>> {
+        ConfigCommand::new_from_command_result(&CommandResult::new(Command::Config, value, HashMap::new()))
+    }
+
+    #[test]
+    fn new_from_command_result_with_actions_return_action() {
+        assert_eq!(ConfigAction::List, new_config_command("list").unwrap().action);
+        assert_eq!(ConfigAction::Get(String::from("list.sort")), new_config_command("get List.Sort").unwrap().action);
+        assert_eq!(ConfigAction::Set(String::from("aliases.urgent"), String::from("list --priority=H -a")),
+                   new_config_command("set aliases.urgent list --priority=H -a").unwrap().action);
+        assert_eq!(ConfigAction::Unset(String::from("add.priority")), new_config_command("unset add.priority").unwrap().action);
+    }
+
+    #[test]
+    fn new_from_command_result_with_invalid_arguments_return_error() {
+        assert_eq!("Invalid arguments. Usage: config list, config get key, config set key value or config unset key",
+                   new_config_command("set add.priority").err().unwrap().to_string());
+        assert_eq!("Invalid action. Must be list, get, set or unset", new_config_command("show").err().unwrap().to_string());
+    }
+
+    #[test]
+    fn get_setting_lines_return_key_and_value() {
+        let table: Table = "[add]\npriority = \"H\"\n[list]\nall = true\n".parse().unwrap();
+        assert_eq!(vec!["add.priority = \"H\"", "list.all = true"], get_setting_lines(&table));
+    }
+}
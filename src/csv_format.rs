@@ -0,0 +1,290 @@
+use crate::common_structs::Priority;
+use crate::duration_format::{format_duration, parse_duration};
+use crate::todo::Todo;
+use crate::todo_format::{ImportResult, TodoFormat};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use std::error::Error;
+
+const COLUMNS: [&str; 8] = ["id", "title", "priority", "status", "created", "completed", "estimate", "time_spent"];
+const IMPORT_COLUMNS: [&str; 6] = ["title", "priority", "status", "created", "completed", "estimate"];
+const DEFAULT_COLUMNS: [&str; 6] = ["id", "title", "priority", "status", "created", "completed"];
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug)]
+pub struct CsvFormat {
+    columns: Vec<String>,
+    date_format: String,
+    mapping: HashMap<String, String>
+}
+
+impl CsvFormat {
+    pub fn new_from_options(options: &HashMap<String, String>) -> Result<CsvFormat, Box<dyn Error>> {
+        let columns: Vec<String> = match options.get("columns") {
+            Some(value) => value.split(',').map(|x| x.trim().to_lowercase()).filter(|x| !x.is_empty()).collect(),
+            None => DEFAULT_COLUMNS.iter().map(|x| x.to_string()).collect()
+        };
+        if columns.is_empty() {
+            return Err("The columns option cannot be empty".into());
+        }
+        if let Some(column) = columns.iter().find(|x| !COLUMNS.contains(&x.as_str())) {
+            return Err(format!("Unknown column {}. Must be one of {}", column, COLUMNS.join(", ")).into());
+        }
+        let date_format = match options.get("date-format") {
+            Some(value) if !value.trim().is_empty() => value.trim().to_string(),
+            Some(_) => return Err("The date-format option cannot be empty".into()),
+            None => DEFAULT_DATE_FORMAT.to_string()
+        };
+        // chrono panics when it formats a date with an unknown specifier
+        if StrftimeItems::new(&date_format).any(|x| x == Item::Error) {
+            return Err(format!("Invalid date format {}. See the chrono strftime specifiers (ex: %d/%m/%Y)", date_format).into());
+        }
+        let mut mapping = HashMap::new();
+        if let Some(value) = options.get("map") {
+            for pair in value.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                match pair.split_once(':') {
+                    Some((field, header)) if IMPORT_COLUMNS.contains(&field.trim().to_lowercase().as_str()) && !header.trim().is_empty() => {
+                        mapping.insert(field.trim().to_lowercase(), header.trim().to_string());
+                    },
+                    _ => return Err(format!("Invalid mapping {}. Must be field:column where field is one of {}", pair, IMPORT_COLUMNS.join(", ")).into())
+                }
+            }
+        }
+        Ok(CsvFormat { columns, date_format, mapping })
+    }
+
+    fn format_date(&self, date: DateTime<Local>) -> String {
+        date.format(&self.date_format).to_string()
+    }
+
+    fn parse_date(&self, value: &str) -> Result<DateTime<Utc>, String> {
+        let naive_date = match NaiveDateTime::parse_from_str(value, &self.date_format) {
+            Ok(date) => Some(date),
+            Err(_) => {
+                // Also accept the date part alone (2026-10-01 for %Y-%m-%d %H:%M:%S)
+                let date_format = self.date_format.split_whitespace().next().unwrap_or_default();
+                NaiveDate::parse_from_str(value, date_format).ok().and_then(|x| x.and_hms_opt(0, 0, 0))
+            }
+        };
+        match naive_date.and_then(|x| Local.from_local_datetime(&x).earliest()) {
+            Some(date) => Ok(date.with_timezone(&Utc)),
+            None => Err(format!("Invalid date {}. Must be in the format {}", value, self.date_format))
+        }
+    }
+
+    fn parse_priority(value: &str) -> Result<Priority, String> {
+        match value.to_lowercase().as_str() {
+            "" => Ok(Priority::Low),
+            "high" => Ok(Priority::High),
+            "medium" => Ok(Priority::Medium),
+            "low" => Ok(Priority::Low),
+            _ => Priority::from_string(value).ok_or_else(|| String::from("Invalid priority value. Must be H, M or L"))
+        }
+    }
+
+    fn get_column_value(&self, todo: &Todo, column: &str) -> String {
+        match column {
+            "id" => todo.get_id().to_string(),
+            "title" => todo.get_title().to_string(),
+            "priority" => todo.get_priority().to_string(),
+            "status" => match todo.get_completed() {
+                true => String::from("completed"),
+                false => String::from("open")
+            },
+            "created" => self.format_date(todo.get_when_created_in_localtime()),
+            "completed" => match todo.get_completed() {
+                true => self.format_date(todo.get_when_completed_in_localtime()),
+                false => String::new()
+            },
+            "estimate" => todo.get_estimate().map(|x| format_duration(&x).replace(' ', "")).unwrap_or_default(),
+            "time_spent" => match todo.get_time_entries().is_empty() {
+                true => String::new(),
+                false => format_duration(&todo.get_time_spent(Utc::now())).replace(' ', "")
+            },
+            _ => String::new()
+        }
+    }
+
+    // Return the index of the csv column of each todo field
+    fn find_field_indexes(&self, headers: &csv::StringRecord) -> Result<HashMap<String, usize>, Box<dyn Error>> {
+        let mut indexes = HashMap::new();
+        for field in IMPORT_COLUMNS {
+            let header = match self.mapping.get(field) {
+                Some(header) => header.as_str(),
+                None => field
+            };
+            match headers.iter().position(|x| x.trim().eq_ignore_ascii_case(header)) {
+                Some(index) => { indexes.insert(field.to_string(), index); },
+                None if self.mapping.contains_key(field) => return Err(format!("The column {} doesn't exist in the file", header).into()),
+                None => ()
+            }
+        }
+        if !indexes.contains_key("title") {
+            return Err("The file must have a title column. Use --map=title:column to choose it".into());
+        }
+        Ok(indexes)
+    }
+
+    fn parse_record(&self, record: &csv::StringRecord, indexes: &HashMap<String, usize>) -> Result<Todo, String> {
+        let get = |field: &str| indexes.get(field).and_then(|x| record.get(*x)).map(|x| x.trim()).unwrap_or("");
+        let priority = CsvFormat::parse_priority(get("priority"))?;
+        let when_created = match get("created") {
+            "" => Utc::now(),
+            value => self.parse_date(value)?
+        };
+        let mut todo = Todo::new(0, get("title"), priority, when_created).map_err(|e| e.to_string())?;
+        if !get("estimate").is_empty() {
+            let estimate = parse_duration(get("estimate")).map_err(|e| e.to_string())?;
+            todo.set_estimate(Some(estimate)).map_err(|e| e.to_string())?;
+        }
+        let when_completed = match get("completed") {
+            "" => None,
+            value => Some(self.parse_date(value)?)
+        };
+        let completed = match get("status").to_lowercase().as_str() {
+            "" => when_completed.is_some(),
+            "completed" | "done" | "x" => true,
+            "open" => false,
+            value => return Err(format!("Invalid status {}. Must be open or completed", value))
+        };
+        if completed {
+            todo.set_completed(true, Some(when_completed.unwrap_or_else(Utc::now)));
+        }
+        Ok(todo)
+    }
+}
+
+impl TodoFormat for CsvFormat {
+    fn export(&self, todos: &[Todo]) -> Result<String, Box<dyn Error>> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.write_record(&self.columns)?;
+        for todo in todos {
+            writer.write_record(self.columns.iter().map(|x| self.get_column_value(todo, x)))?;
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+
+    fn import(&self, content: &str) -> Result<ImportResult, Box<dyn Error>> {
+        let mut result = ImportResult::new();
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(content.as_bytes());
+        let indexes = self.find_field_indexes(reader.headers()?)?;
+        for record in reader.records() {
+            let record = record?;
+            let line_number = record.position().map(|x| x.line() as usize).unwrap_or(0);
+            match self.parse_record(&record, &indexes) {
+                Ok(todo) => result.add_todo(todo),
+                Err(message) => result.add_issue(line_number, &format!("{}. The row has been skipped", message), &record.iter().collect::<Vec<&str>>().join(","))
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::csv_format::CsvFormat;
+    use crate::todo::Todo;
+    use crate::todo_format::TodoFormat;
+    use chrono::{Duration, Local, TimeZone, Utc};
+    use std::collections::HashMap;
+
+    fn get_format(options: &[(&str, &str)]) -> CsvFormat {
+        let options: HashMap<String, String> = options.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        CsvFormat::new_from_options(&options).unwrap()
+    }
+
+    fn local_date(year: i32, month: u32, day: u32) -> chrono::DateTime<Utc> {
+        Local.with_ymd_and_hms(year, month, day, 9, 30, 0).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn new_from_options_with_unknown_column_return_error() {
+        let options = HashMap::from([(String::from("columns"), String::from("id,color"))]);
+        assert_eq!("Unknown column color. Must be one of id, title, priority, status, created, completed, estimate, time_spent",
+                   CsvFormat::new_from_options(&options).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn new_from_options_with_invalid_date_format_return_error() {
+        let options = HashMap::from([(String::from("date-format"), String::from("%d/%m/%Q"))]);
+        assert_eq!("Invalid date format %d/%m/%Q. See the chrono strftime specifiers (ex: %d/%m/%Y)",
+                   CsvFormat::new_from_options(&options).unwrap_err().to_string());
+        let options = HashMap::from([(String::from("date-format"), String::from("%Y-%m-%d %"))]);
+        assert!(CsvFormat::new_from_options(&options).is_err());
+    }
+
+    #[test]
+    fn new_from_options_with_invalid_mapping_return_error() {
+        let options = HashMap::from([(String::from("map"), String::from("title=Summary"))]);
+        assert_eq!("Invalid mapping title=Summary. Must be field:column where field is one of title, priority, status, created, completed, estimate",
+                   CsvFormat::new_from_options(&options).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn export_with_default_columns_return_csv() {
+        let mut completed = Todo::new(2, "Pay, the \"bills\"", Priority::Medium, local_date(2026, 10, 1)).unwrap();
+        completed.set_completed(true, Some(local_date(2026, 10, 3)));
+        let todos = vec![Todo::new(1, "Call mom", Priority::High, local_date(2026, 10, 1)).unwrap(), completed];
+        assert_eq!("id,title,priority,status,created,completed\n\
+                    1,Call mom,High,open,2026-10-01 09:30:00,\n\
+                    2,\"Pay, the \"\"bills\"\"\",Medium,completed,2026-10-01 09:30:00,2026-10-03 09:30:00\n",
+                   get_format(&[]).export(&todos).unwrap());
+    }
+
+    #[test]
+    fn export_with_columns_and_date_format_return_csv() {
+        let mut todo = Todo::new(1, "Call mom", Priority::High, local_date(2026, 10, 1)).unwrap();
+        todo.set_estimate(Some(Duration::minutes(90))).unwrap();
+        let format = get_format(&[("columns", "title, created,estimate"), ("date-format", "%d/%m/%Y")]);
+        assert_eq!("title,created,estimate\nCall mom,01/10/2026,1h30m\n", format.export(&[todo]).unwrap());
+    }
+
+    #[test]
+    fn import_with_mapping_return_todos() {
+        let content = "Summary,Prio,Due\nCall mom,H,2026-10-01\nPay bills,low,\n";
+        let result = get_format(&[("map", "title:Summary,priority:Prio")]).import(content).unwrap();
+        assert_eq!(2, result.get_todos().len());
+        assert_eq!("Call mom", result.get_todos()[0].get_title());
+        assert_eq!(Priority::High, result.get_todos()[0].get_priority());
+        assert_eq!(Priority::Low, result.get_todos()[1].get_priority());
+        assert!(result.get_issues().is_empty());
+    }
+
+    #[test]
+    fn import_with_dates_and_status_return_completed_todo() {
+        let content = "title,created,completed,estimate\nPay bills,2026-10-01,2026-10-03 09:30:00,2h\n";
+        let result = get_format(&[]).import(content).unwrap();
+        let todo = &result.get_todos()[0];
+        assert!(todo.get_completed());
+        assert_eq!(Local.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap(), todo.get_when_created_in_localtime());
+        assert_eq!(local_date(2026, 10, 3), todo.get_when_completed_in_localtime());
+        assert_eq!(Duration::hours(2), todo.get_estimate().unwrap());
+    }
+
+    #[test]
+    fn import_with_invalid_rows_return_issues_per_row() {
+        let content = "title,priority,created\nCall mom,H,\n,M,\nPay bills,Z,\nWalk,L,yesterday\n";
+        let result = get_format(&[]).import(content).unwrap();
+        assert_eq!(1, result.get_todos().len());
+        assert_eq!(vec!["Line 3: Title is required. The row has been skipped (,M,)",
+                        "Line 4: Invalid priority value. Must be H, M or L. The row has been skipped (Pay bills,Z,)",
+                        "Line 5: Invalid date yesterday. Must be in the format %Y-%m-%d %H:%M:%S. The row has been skipped (Walk,L,yesterday)"],
+                   *result.get_issues());
+    }
+
+    #[test]
+    fn import_without_title_column_return_error() {
+        let content = "Summary,Prio\nCall mom,H\n";
+        assert_eq!("The file must have a title column. Use --map=title:column to choose it",
+                   get_format(&[]).import(content).err().unwrap().to_string());
+    }
+
+    #[test]
+    fn import_with_mapping_to_missing_column_return_error() {
+        let content = "title\nCall mom\n";
+        assert_eq!("The column Prio doesn't exist in the file",
+                   get_format(&[("map", "priority:Prio")]).import(content).err().unwrap().to_string());
+    }
+}
@@ -1,8 +1,26 @@
-use crate::todo::Todo;
+use crate::config::get_config;
+use crate::todo::{Todo, TrashedTodo};
+use chrono::{DateTime, Duration, Utc};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
 use std::path::Path;
 use home::home_dir;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// The todos of a shell session, kept in memory until the session is saved.
+// The archive and the trash changed during the session are kept too, by file path.
+struct Session {
+    todos: Vec<Todo>,
+    files: HashMap<String, String>,
+    changed: bool
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
 
 pub fn get_todos_file() -> String {
     format!("{}/.beaver/todos.json", home_dir().unwrap().display())
@@ -17,29 +35,310 @@ pub fn add_todo(mut todo: Todo) -> Result<u32, Box<dyn Error>> {
     Ok(id_assigned)
 }
 
+// Add the imported todos, or update the existing ones having the same uuid.
+// Return the number of todos added and updated.
+pub fn import_todos(imported_todos: &[Todo]) -> Result<(usize, usize), Box<dyn Error>> {
+    let mut todos = read_all_todos()?;
+    let (added_count, updated_count) = merge_imported_todos(&mut todos, imported_todos);
+    write_todos(&todos)?;
+    Ok((added_count, updated_count))
+}
+
+pub fn merge_imported_todos(todos: &mut Vec<Todo>, imported_todos: &[Todo]) -> (usize, usize) {
+    let mut added_count = 0;
+    let mut updated_count = 0;
+    for imported_todo in imported_todos {
+        match todos.iter().position(|x| x.get_uuid() == imported_todo.get_uuid()) {
+            Some(index) => {
+                let reopened = todos[index].get_completed() && !imported_todo.get_completed();
+                todos[index].update_from(imported_todo);
+                // The id of a completed todo may have been given to another todo since
+                if reopened {
+                    let mut other_todos = todos.clone();
+                    other_todos.remove(index);
+                    if other_todos.iter().any(|x| x.get_id() == todos[index].get_id() && !x.get_completed()) {
+                        let id = find_next_available_todo_id(&other_todos);
+                        todos[index].set_id(id);
+                    }
+                }
+                updated_count += 1;
+            },
+            None => {
+                let mut todo = imported_todo.clone();
+                todo.set_id(find_next_available_todo_id(todos));
+                todos.push(todo);
+                added_count += 1;
+            }
+        }
+    }
+    (added_count, updated_count)
+}
+
 pub fn read_all_todos() -> Result<Vec<Todo>, Box<dyn Error>> {
-    let todo_file_path = get_todos_file();
-    if std::fs::metadata(&todo_file_path).is_ok() {
-        let content = std::fs::read_to_string(&todo_file_path)?;
-        let todos: Vec<Todo> = serde_json::from_str(&content)?;
-        Ok(todos)
+    if let Some(todos) = SESSION.with(|x| x.borrow().as_ref().map(|session| session.todos.clone())) {
+        return Ok(todos);
+    }
+    read_todos_file()
+}
+
+fn read_todos_file() -> Result<Vec<Todo>, Box<dyn Error>> {
+    read_json_file(&get_todos_file())
+}
+
+// Return the items of a json file, or no item when the file doesn't exist
+fn read_json_file<T: DeserializeOwned>(file_path: &str) -> Result<Vec<T>, Box<dyn Error>> {
+    if let Some(content) = SESSION.with(|x| x.borrow().as_ref().and_then(|session| session.files.get(file_path).cloned())) {
+        return Ok(serde_json::from_str(&content)?);
+    }
+    if std::fs::metadata(file_path).is_ok() {
+        let content = std::fs::read_to_string(file_path)?;
+        let items: Vec<T> = serde_json::from_str(&content)?;
+        Ok(items)
     }
     else {
         Ok(vec![])
     }
 }
 
-pub fn write_todos(todos: &Vec<Todo>) -> Result<(), Box<dyn Error>> {
-    let todos_str = serde_json::to_string_pretty(todos)?;
+pub fn write_todos(todos: &[Todo]) -> Result<(), Box<dyn Error>> {
+    let mut todos = todos.to_vec();
+    record_history(&read_all_todos()?, &mut todos, Utc::now(), &get_user_name());
+    let in_session = SESSION.with(|x| match x.borrow_mut().as_mut() {
+        Some(session) => {
+            session.todos = todos.clone();
+            session.changed = true;
+            true
+        },
+        None => false
+    });
+    if in_session {
+        return Ok(());
+    }
+    write_todos_file(&todos)
+}
+
+// Append to the history of each todo the changes made since the previous version of the todos
+pub fn record_history(previous_todos: &[Todo], todos: &mut [Todo], when: DateTime<Utc>, user: &str) {
+    for todo in todos.iter_mut() {
+        let previous = previous_todos.iter().find(|x| x.get_uuid() == todo.get_uuid());
+        todo.record_changes(previous, when, user);
+    }
+}
+
+// The name of the user recorded in the history of the todos
+fn get_user_name() -> String {
+    std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_else(|_| String::from("unknown"))
+}
+
+fn write_todos_file(todos: &[Todo]) -> Result<(), Box<dyn Error>> {
+    write_file(&get_todos_file(), &serde_json::to_string_pretty(todos)?)
+}
+
+// Write the archive or the trash, in a session the file is written with the todos by save_session
+// so an interrupted session can't lose the todos moved between the files
+fn write_store<T: Serialize>(file_path: &str, items: &[T]) -> Result<(), Box<dyn Error>> {
+    let items_str = serde_json::to_string_pretty(items)?;
+    let in_session = SESSION.with(|x| match x.borrow_mut().as_mut() {
+        Some(session) => {
+            session.files.insert(file_path.to_string(), items_str.clone());
+            session.changed = true;
+            true
+        },
+        None => false
+    });
+    if in_session {
+        return Ok(());
+    }
+    write_file(file_path, &items_str)
+}
+
+// Write the content to a temporary file renamed over the file, so the file is never left half written
+fn write_file(file_path: &str, items_str: &str) -> Result<(), Box<dyn Error>> {
     //Check if the .beaver folder exist in the home directory
     let beaver_folder = format!("{}/.beaver", home_dir().unwrap().display());
     if !Path::new(&beaver_folder).exists() {
         fs::create_dir(&beaver_folder)?;
     }
-    std::fs::write(get_todos_file(), todos_str)?;
+    let temporary_file = format!("{}.tmp", file_path);
+    fs::write(&temporary_file, items_str)?;
+    fs::rename(&temporary_file, file_path)?;
     Ok(())
 }
 
+pub fn get_archive_file() -> String {
+    format!("{}/.beaver/archive.json", home_dir().unwrap().display())
+}
+
+pub fn read_archived_todos() -> Result<Vec<Todo>, Box<dyn Error>> {
+    read_json_file(&get_archive_file())
+}
+
+// Return the todos and the archived todos, used by the reports covering the history
+pub fn read_todos_with_archive() -> Result<Vec<Todo>, Box<dyn Error>> {
+    let mut todos = read_all_todos()?;
+    let archived_todos = read_archived_todos()?;
+    for archived_todo in archived_todos {
+        if !todos.iter().any(|x| x.get_uuid() == archived_todo.get_uuid()) {
+            todos.push(archived_todo);
+        }
+    }
+    Ok(todos)
+}
+
+// Move the todos completed before the date to the archive file, return the number of todos archived.
+// The archive is written first so an interruption can't lose the todos.
+pub fn archive_todos(completed_before: DateTime<Utc>) -> Result<usize, Box<dyn Error>> {
+    let (archived_todos, todos) = split_archivable_todos(read_all_todos()?, completed_before);
+    if archived_todos.is_empty() {
+        return Ok(0);
+    }
+    let mut archive = read_archived_todos()?;
+    archive.retain(|x| !archived_todos.iter().any(|todo| todo.get_uuid() == x.get_uuid()));
+    archive.extend(archived_todos.iter().cloned());
+    write_store(&get_archive_file(), &archive)?;
+    write_todos(&todos)?;
+    Ok(archived_todos.len())
+}
+
+// Return the todos to archive and the todos to keep
+pub fn split_archivable_todos(todos: Vec<Todo>, completed_before: DateTime<Utc>) -> (Vec<Todo>, Vec<Todo>) {
+    todos.into_iter().partition(|x| x.get_completed() && x.get_when_completed_in_localtime() < completed_before)
+}
+
+// Move an archived todo back to the todo list, the uuid can be shortened to its first characters
+pub fn unarchive_todo(uuid: &str) -> Result<Todo, Box<dyn Error>> {
+    let mut archive = read_archived_todos()?;
+    let index = find_archived_todo(&archive, uuid)?;
+    let todo = archive.remove(index);
+    let mut todos = read_all_todos()?;
+    if !todos.iter().any(|x| x.get_uuid() == todo.get_uuid()) {
+        todos.push(todo.clone());
+        write_todos(&todos)?;
+    }
+    write_store(&get_archive_file(), &archive)?;
+    Ok(todo)
+}
+
+pub fn find_archived_todo(archive: &[Todo], uuid: &str) -> Result<usize, Box<dyn Error>> {
+    let uuid = uuid.trim().to_lowercase();
+    if uuid.is_empty() {
+        return Err("Value cannot be empty".into());
+    }
+    let indexes: Vec<usize> = (0..archive.len()).filter(|x| archive[*x].get_uuid().to_lowercase().starts_with(&uuid)).collect();
+    match indexes.as_slice() {
+        [] => Err(format!("Unable to find the archived todo with uuid {}", uuid).into()),
+        [index] => Ok(*index),
+        _ => Err(format!("The uuid {} matches {} archived todos, give more characters", uuid, indexes.len()).into())
+    }
+}
+
+pub fn get_trash_file() -> String {
+    format!("{}/.beaver/trash.json", home_dir().unwrap().display())
+}
+
+// Return the todos of the trash without the expired ones
+pub fn read_trash() -> Result<Vec<TrashedTodo>, Box<dyn Error>> {
+    let mut trash: Vec<TrashedTodo> = read_json_file(&get_trash_file())?;
+    remove_expired_todos(&mut trash, Utc::now(), get_config().get_trash_expiry());
+    Ok(trash)
+}
+
+pub fn remove_expired_todos(trash: &mut Vec<TrashedTodo>, now: DateTime<Utc>, expiry: Option<Duration>) {
+    if let Some(expiry) = expiry {
+        trash.retain(|x| !is_removed_before(x, now, expiry));
+    }
+}
+
+// Return true when the todo has been removed for longer than the age, never for an age going before the first date
+fn is_removed_before(trashed_todo: &TrashedTodo, now: DateTime<Utc>, age: Duration) -> bool {
+    now.checked_sub_signed(age).map(|limit| trashed_todo.get_when_deleted() <= limit).unwrap_or(false)
+}
+
+// Move the removed todos to the trash, the todos list must be written after so an interruption can't lose them
+pub fn move_to_trash(todos: &[Todo]) -> Result<(), Box<dyn Error>> {
+    let mut trash = read_trash()?;
+    let now = Utc::now();
+    trash.extend(todos.iter().map(|x| TrashedTodo::new(x.clone(), now)));
+    write_store(&get_trash_file(), &trash)
+}
+
+// Move the todo most recently removed with the id back to the todo list.
+// The todo gets another id when an open todo took its id since.
+pub fn restore_from_trash(id: u32) -> Result<Todo, Box<dyn Error>> {
+    let mut trash = read_trash()?;
+    let index = match find_trashed_todo(&trash, id) {
+        Some(index) => index,
+        None => return Err(format!("Unable to find the todo with id {} in the trash", id).into())
+    };
+    let mut todo = trash.remove(index).into_todo();
+    let mut todos = read_all_todos()?;
+    if !todo.get_completed() && todos.iter().any(|x| x.get_id() == todo.get_id() && !x.get_completed()) {
+        todo.set_id(find_next_available_todo_id(&todos));
+    }
+    todos.push(todo.clone());
+    write_todos(&todos)?;
+    write_store(&get_trash_file(), &trash)?;
+    Ok(todo)
+}
+
+pub fn find_trashed_todo(trash: &[TrashedTodo], id: u32) -> Option<usize> {
+    (0..trash.len()).filter(|x| trash[*x].get_todo().get_id() == id).max_by_key(|x| trash[*x].get_when_deleted())
+}
+
+// Return the indexes of the todos of the trash removed for longer than the age, or of all of them
+pub fn find_todos_to_empty(trash: &[TrashedTodo], now: DateTime<Utc>, older_than: Option<Duration>) -> Vec<usize> {
+    (0..trash.len()).filter(|x| older_than.map(|age| is_removed_before(&trash[*x], now, age)).unwrap_or(true)).collect()
+}
+
+// Delete the todos at the indexes from the trash read by read_trash
+pub fn empty_trash(trash: Vec<TrashedTodo>, indexes: &[usize]) -> Result<(), Box<dyn Error>> {
+    let kept: Vec<TrashedTodo> = trash.into_iter().enumerate().filter(|(index, _)| !indexes.contains(index)).map(|(_, x)| x).collect();
+    write_store(&get_trash_file(), &kept)
+}
+
+// Read the todos once, the following reads and writes use the todos in memory until save_session
+pub fn start_session() -> Result<(), Box<dyn Error>> {
+    if is_session_started() {
+        return Err("A session is already started".into());
+    }
+    let todos = read_todos_file()?;
+    SESSION.with(|x| *x.borrow_mut() = Some(Session { todos, files: HashMap::new(), changed: false }));
+    Ok(())
+}
+
+pub fn is_session_started() -> bool {
+    SESSION.with(|x| x.borrow().is_some())
+}
+
+pub fn has_unsaved_changes() -> bool {
+    SESSION.with(|x| x.borrow().as_ref().map(|session| session.changed).unwrap_or(false))
+}
+
+// Write the todos of the session if they have changed, return true when they have been written.
+// The archive and the trash are written first, like outside a session.
+pub fn save_session() -> Result<bool, Box<dyn Error>> {
+    let changes = SESSION.with(|x| x.borrow().as_ref().filter(|session| session.changed)
+        .map(|session| (session.todos.clone(), session.files.clone())));
+    match changes {
+        Some((todos, files)) => {
+            for (file_path, content) in &files {
+                write_file(file_path, content)?;
+            }
+            write_todos_file(&todos)?;
+            SESSION.with(|x| if let Some(session) = x.borrow_mut().as_mut() {
+                session.files.clear();
+                session.changed = false;
+            });
+            Ok(true)
+        },
+        None => Ok(false)
+    }
+}
+
+pub fn end_session() {
+    SESSION.with(|x| *x.borrow_mut() = None);
+}
+
 pub fn find_next_available_todo_id(todos: &Vec<Todo>) -> u32 {
     let mut available_id = 1;
     let mut is_id_available = false;
@@ -60,9 +359,97 @@ pub fn find_next_available_todo_id(todos: &Vec<Todo>) -> u32 {
 #[cfg(test)]
 mod tests {
     use crate::common_structs::Priority;
-    use crate::todo::Todo;
-    use crate::data_service::find_next_available_todo_id;
-    use chrono::Utc;
+    use crate::todo::{Todo, TrashedTodo};
+    use crate::data_service::{end_session, find_archived_todo, find_next_available_todo_id, find_trashed_todo, has_unsaved_changes,
+                              find_todos_to_empty, merge_imported_todos, read_all_todos, read_json_file, remove_expired_todos, split_archivable_todos,
+                              write_store, write_todos, Session, SESSION};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    #[test]
+    fn write_todos_in_session_keep_todos_in_memory() {
+        SESSION.with(|x| *x.borrow_mut() = Some(Session { todos: vec![], files: HashMap::new(), changed: false }));
+        assert!(!has_unsaved_changes());
+        write_todos(&[Todo::new(1, "Call mom", Priority::High, Utc::now()).unwrap()]).unwrap();
+        assert!(has_unsaved_changes());
+        let todos = read_all_todos().unwrap();
+        assert_eq!(1, todos.len());
+        assert_eq!("Call mom", todos[0].get_title());
+        end_session();
+        assert!(!has_unsaved_changes());
+    }
+
+    #[test]
+    fn write_store_in_session_keep_file_in_memory() {
+        SESSION.with(|x| *x.borrow_mut() = Some(Session { todos: vec![], files: HashMap::new(), changed: false }));
+        let file_path = "/nonexistent/.beaver/trash.json";
+        write_store(file_path, &[Todo::new(1, "Call mom", Priority::High, Utc::now()).unwrap()]).unwrap();
+        assert!(has_unsaved_changes());
+        let todos: Vec<Todo> = read_json_file(file_path).unwrap();
+        assert_eq!("Call mom", todos[0].get_title());
+        end_session();
+        assert!(read_json_file::<Todo>(file_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn split_archivable_todos_return_todos_completed_before_date() {
+        let now = Utc::now();
+        let mut old_todo = Todo::new(1, "Old", Priority::Low, now).unwrap();
+        old_todo.set_completed(true, Some(now - Duration::days(40)));
+        let mut recent_todo = Todo::new(2, "Recent", Priority::Low, now).unwrap();
+        recent_todo.set_completed(true, Some(now - Duration::days(2)));
+        let open_todo = Todo::new(3, "Open", Priority::Low, now).unwrap();
+        let (archived, kept) = split_archivable_todos(vec![old_todo, recent_todo, open_todo], now - Duration::days(30));
+        assert_eq!(vec!["Old"], archived.iter().map(|x| x.get_title()).collect::<Vec<&str>>());
+        assert_eq!(vec!["Recent", "Open"], kept.iter().map(|x| x.get_title()).collect::<Vec<&str>>());
+    }
+
+    #[test]
+    fn find_archived_todo_with_uuid_prefix_return_index() {
+        let mut archive = vec![Todo::new(1, "First", Priority::Low, Utc::now()).unwrap(),
+                               Todo::new(2, "Second", Priority::Low, Utc::now()).unwrap()];
+        archive[0].set_uuid("4f1c2a9e-0000-4000-8000-000000000001").unwrap();
+        archive[1].set_uuid("4f1c7b00-0000-4000-8000-000000000002").unwrap();
+        assert_eq!(1, find_archived_todo(&archive, "4F1C7").unwrap());
+        assert_eq!(0, find_archived_todo(&archive, "4f1c2a9e-0000-4000-8000-000000000001").unwrap());
+        assert_eq!("The uuid 4f1c matches 2 archived todos, give more characters",
+                   find_archived_todo(&archive, "4f1c").err().unwrap().to_string());
+        assert_eq!("Unable to find the archived todo with uuid abc",
+                   find_archived_todo(&archive, "abc").err().unwrap().to_string());
+    }
+
+    #[test]
+    fn remove_expired_todos_keep_recently_removed_todos() {
+        let now = Utc::now();
+        let mut trash = vec![TrashedTodo::new(Todo::new(1, "Old", Priority::Low, now).unwrap(), now - Duration::days(31)),
+                             TrashedTodo::new(Todo::new(2, "Recent", Priority::Low, now).unwrap(), now - Duration::days(1))];
+        remove_expired_todos(&mut trash, now, None);
+        assert_eq!(2, trash.len());
+        remove_expired_todos(&mut trash, now, Some(Duration::days(i64::MAX / 86_400_000)));
+        assert_eq!(2, trash.len());
+        remove_expired_todos(&mut trash, now, Some(Duration::days(30)));
+        assert_eq!(1, trash.len());
+        assert_eq!("Recent", trash[0].get_todo().get_title());
+    }
+
+    #[test]
+    fn find_todos_to_empty_return_todos_removed_before_age() {
+        let now = Utc::now();
+        let trash = vec![TrashedTodo::new(Todo::new(1, "Old", Priority::Low, now).unwrap(), now - Duration::days(31)),
+                         TrashedTodo::new(Todo::new(2, "Recent", Priority::Low, now).unwrap(), now - Duration::days(1))];
+        assert_eq!(vec![0, 1], find_todos_to_empty(&trash, now, None));
+        assert_eq!(vec![0], find_todos_to_empty(&trash, now, Some(Duration::days(30))));
+    }
+
+    #[test]
+    fn find_trashed_todo_return_most_recently_removed() {
+        let now = Utc::now();
+        let trash = vec![TrashedTodo::new(Todo::new(3, "First", Priority::Low, now).unwrap(), now - Duration::days(2)),
+                         TrashedTodo::new(Todo::new(3, "Second", Priority::Low, now).unwrap(), now - Duration::days(1)),
+                         TrashedTodo::new(Todo::new(4, "Other", Priority::Low, now).unwrap(), now)];
+        assert_eq!(Some(1), find_trashed_todo(&trash, 3));
+        assert_eq!(None, find_trashed_todo(&trash, 5));
+    }
 
     #[test]
     fn find_next_available_todo_id_with_empty_return_one() {
@@ -134,4 +521,34 @@ mod tests {
         todos[1].set_completed(true, None);
         assert_eq!(2, find_next_available_todo_id(&todos));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn merge_imported_todos_with_same_uuid_update_todo() {
+        let mut todos: Vec<Todo> = vec![
+            Todo::new(1, "a", Priority::Low, Utc::now()).unwrap(),
+            Todo::new(2, "b", Priority::Low, Utc::now()).unwrap()
+        ];
+        let mut updated = Todo::new(0, "b updated", Priority::High, Utc::now()).unwrap();
+        updated.set_uuid(todos[1].get_uuid()).unwrap();
+        let added = Todo::new(0, "c", Priority::Low, Utc::now()).unwrap();
+        assert_eq!((1, 1), merge_imported_todos(&mut todos, &[updated, added]));
+        assert_eq!(3, todos.len());
+        assert_eq!(2, todos[1].get_id());
+        assert_eq!("b updated", todos[1].get_title());
+        assert_eq!(3, todos[2].get_id());
+    }
+
+    #[test]
+    fn merge_imported_todos_with_reopened_todo_assign_available_id() {
+        let mut todos: Vec<Todo> = vec![
+            Todo::new(1, "a", Priority::Low, Utc::now()).unwrap(),
+            Todo::new(1, "b", Priority::Low, Utc::now()).unwrap()
+        ];
+        todos[0].set_completed(true, None);
+        let mut reopened = Todo::new(0, "a", Priority::Low, Utc::now()).unwrap();
+        reopened.set_uuid(todos[0].get_uuid()).unwrap();
+        assert_eq!((0, 1), merge_imported_todos(&mut todos, &[reopened]));
+        assert!(!todos[0].get_completed());
+        assert_eq!(2, todos[0].get_id());
+    }
+}
@@ -1,49 +1,82 @@
 use crate::common_structs::{CommandResult, ExecutableCommand};
 use crate::data_service::{read_all_todos, write_todos};
-use termion::color;
+use crate::output::{reset_color, success_color};
+use crate::todo::Todo;
+use crate::todo_selection::{confirm_action, TodoSelection};
+use chrono::{DateTime, Utc};
 use std::error::Error;
 
 #[derive(Debug)]
 pub struct DoneCommand {
-    id: u32
+    selection: TodoSelection,
+    yes: bool
 }
 
 impl DoneCommand {
     pub fn new_from_command_result(command_result : &CommandResult) -> Result<DoneCommand, Box<dyn Error>> {
-        let value = command_result.get_value().trim();
-        if value.is_empty() {
-            return Err("Value cannot be empty".into());
-        }
-        let id = value.parse::<u32>()?;
-        Ok(DoneCommand { id: id })
+        let selection = TodoSelection::new_from_command_result(command_result)?;
+        let yes = command_result.get_options().contains_key("yes");
+        Ok(DoneCommand { selection, yes })
     }
 }
 
 impl ExecutableCommand for DoneCommand {
     fn execute(&self) -> Result<(), Box<dyn Error>> {
         let mut todos = read_all_todos()?;
-        let todo_title;
-        //Find the todo to complete
-        let mut iter = todos.iter_mut();
-        match iter.find(|x| x.get_id() == self.id && !x.get_completed()) {
-            Some(todo) => {
-                todo.set_completed(true, None);
-                todo_title = String::from(todo.get_title());
-            },
-            None => return Err(format!("Unable to find the todo with id {}", self.id).into())
+        //Find the todos to complete, the completed ones keep their completion date
+        let mut indexes = self.selection.find_indexes(&todos)?;
+        indexes.retain(|x| !todos[*x].get_completed());
+        if indexes.is_empty() {
+            println!("The todos are already completed");
+            return Ok(());
+        }
+        if indexes.len() > 1 && !self.yes && !confirm_action("completed", &todos, &indexes)? {
+            return Ok(());
         }
+        complete_todos(&mut todos, &indexes, Utc::now())?;
         write_todos(&todos)?;
-        println!("{}The todo {} has been completed!{}", color::Fg(color::Green), todo_title, color::Fg(color::Reset));
+        for index in &indexes {
+            println!("{}The todo {} has been completed!{}", success_color(), todos[*index].get_title(), reset_color());
+        }
         Ok(())
     }
 }
 
+fn complete_todos(todos: &mut [Todo], indexes: &[usize], now: DateTime<Utc>) -> Result<(), Box<dyn Error>> {
+    for index in indexes {
+        let todo = &mut todos[*index];
+        if todo.get_completed() {
+            continue;
+        }
+        if todo.is_tracking() {
+            todo.stop_tracking(now)?;
+        }
+        todo.set_completed(true, Some(now));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::done_command::DoneCommand;
-    use crate::common_structs::{Command, CommandResult};
+    use crate::done_command::{complete_todos, DoneCommand};
+    use crate::todo::Todo;
+    use crate::todo_selection::TodoSelection;
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use chrono::{Duration, Utc};
     use std::collections::HashMap;
 
+    #[test]
+    fn complete_todos_with_completed_todo_keep_completion_date() {
+        let now = Utc::now();
+        let mut completed = Todo::new(1, "Done", Priority::Low, now - Duration::days(3)).unwrap();
+        completed.set_completed(true, Some(now - Duration::days(2)));
+        let mut todos = vec![completed, Todo::new(2, "Open", Priority::Low, now - Duration::days(3)).unwrap()];
+        complete_todos(&mut todos, &[0, 1], now).unwrap();
+        assert_eq!(now - Duration::days(2), todos[0].get_when_completed_in_localtime());
+        assert!(todos[1].get_completed());
+        assert_eq!(now, todos[1].get_when_completed_in_localtime());
+    }
+
     #[test]
     fn done_command_new_from_command_result_with_empty_value_return_error() {
         let command = DoneCommand::new_from_command_result(&CommandResult::new(Command::Done, 
@@ -65,7 +98,7 @@ mod tests {
         let command = DoneCommand::new_from_command_result(&CommandResult::new(Command::Done, 
             "1", 
             HashMap::new()));
-        assert_eq!(1, command.unwrap().id);
+        assert_eq!(TodoSelection::Ids(vec![1]), command.unwrap().selection);
     }
 
     #[test]
@@ -73,7 +106,7 @@ mod tests {
         let command = DoneCommand::new_from_command_result(&CommandResult::new(Command::Done, 
             "100", 
             HashMap::new()));
-        assert_eq!(100, command.unwrap().id);
+        assert_eq!(TodoSelection::Ids(vec![100]), command.unwrap().selection);
     }
 
     #[test]
@@ -91,4 +124,12 @@ mod tests {
             HashMap::new()));
         assert_eq!("invalid digit found in string", command.unwrap_err().to_string());
     }
+
+    #[test]
+    fn done_command_new_from_command_result_with_list_and_range_return_success() {
+        let command = DoneCommand::new_from_command_result(&CommandResult::new(Command::Done, 
+            "1 3 5-8", 
+            HashMap::new()));
+        assert_eq!(TodoSelection::Ids(vec![1, 3, 5, 6, 7, 8]), command.unwrap().selection);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,169 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::error::Error;
+
+// The longest duration accepted, the sums of the durations can't overflow
+const MAX_DURATION_MINUTES: i64 = 100_000 * 60;
+
+// Parse a duration like 45m, 2h or 1h30m.
+pub fn parse_duration(value: &str) -> Result<Duration, Box<dyn Error>> {
+    let invalid_duration_error = format!("Invalid duration {}. Must be like 45m, 2h or 1h30m", value);
+    let mut minutes: i64 = 0;
+    let mut number = String::new();
+    let mut unit_found = false;
+    for c in value.trim().to_lowercase().chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 'm' if !number.is_empty() => {
+                let amount = number.parse::<i64>().map_err(|_| invalid_duration_error.clone())?;
+                let unit_minutes = if c == 'h' { amount.checked_mul(60) } else { Some(amount) };
+                minutes = unit_minutes.and_then(|x| minutes.checked_add(x)).ok_or_else(|| invalid_duration_error.clone())?;
+                number.clear();
+                unit_found = true;
+            },
+            _ => return Err(invalid_duration_error.into())
+        }
+    }
+    if !number.is_empty() || !unit_found || minutes == 0 || minutes > MAX_DURATION_MINUTES {
+        return Err(invalid_duration_error.into());
+    }
+    Duration::try_minutes(minutes).ok_or_else(|| invalid_duration_error.into())
+}
+
+// Parse an age like 30d or 2w, used to select the old todos.
+pub fn parse_age(value: &str) -> Result<Duration, Box<dyn Error>> {
+    let value = value.trim().to_lowercase();
+    let invalid_age_error = format!("Invalid age {}. Must be like 30d or 2w", value);
+    let (number, days_per_unit) = match (value.strip_suffix('d'), value.strip_suffix('w')) {
+        (Some(number), _) => (number, 1),
+        (_, Some(number)) => (number, 7),
+        _ => return Err(invalid_age_error.into())
+    };
+    match number.parse::<i64>().ok().filter(|x| *x > 0).and_then(|x| x.checked_mul(days_per_unit)).and_then(Duration::try_days) {
+        Some(age) => Ok(age),
+        None => Err(invalid_age_error.into())
+    }
+}
+
+// Parse a moment like 2026-11-05 (at midnight), 2026-11-05 14:00, tomorrow, or a delay like 3d, 2w or 4h
+// from now. The dates are in the local time.
+pub fn parse_date_time(value: &str, now: DateTime<Local>) -> Result<DateTime<Utc>, Box<dyn Error>> {
+    let value = value.trim().to_lowercase();
+    let invalid_date_error = format!("Invalid date {}. Must be like 2026-11-05, 2026-11-05 14:00, tomorrow, 3d or 4h", value);
+    if let Ok(delay) = parse_age(&value).or_else(|_| parse_duration(&value)) {
+        return match now.checked_add_signed(delay) {
+            Some(date_time) => Ok(date_time.with_timezone(&Utc)),
+            None => Err(invalid_date_error.into())
+        };
+    }
+    let date_time = match value.as_str() {
+        "tomorrow" => now.date_naive().succ_opt().and_then(|x| x.and_hms_opt(0, 0, 0)),
+        _ => NaiveDateTime::parse_from_str(&value, "%Y-%m-%d %H:%M").ok()
+                .or_else(|| NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok().and_then(|x| x.and_hms_opt(0, 0, 0)))
+    };
+    match date_time.and_then(|x| Local.from_local_datetime(&x).earliest()) {
+        Some(date_time) => Ok(date_time.with_timezone(&Utc)),
+        None => Err(invalid_date_error.into())
+    }
+}
+
+// Format a duration as 45m or 2h 05m.
+pub fn format_duration(duration: &Duration) -> String {
+    let total_minutes = duration.num_minutes();
+    if total_minutes < 60 {
+        format!("{}m", total_minutes)
+    }
+    else {
+        format!("{}h {:02}m", total_minutes / 60, total_minutes % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::duration_format::{format_duration, parse_age, parse_date_time, parse_duration};
+    use chrono::{Duration, Local, TimeZone};
+
+    #[test]
+    fn parse_duration_with_45m_return_45_minutes() {
+        assert_eq!(Duration::minutes(45), parse_duration("45m").unwrap());
+    }
+
+    #[test]
+    fn parse_duration_with_2h_return_120_minutes() {
+        assert_eq!(Duration::minutes(120), parse_duration("2H").unwrap());
+    }
+
+    #[test]
+    fn parse_duration_with_1h30m_return_90_minutes() {
+        assert_eq!(Duration::minutes(90), parse_duration("1h30m").unwrap());
+    }
+
+    #[test]
+    fn parse_duration_with_number_only_return_error() {
+        assert_eq!("Invalid duration 45. Must be like 45m, 2h or 1h30m", parse_duration("45").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_duration_with_unit_only_return_error() {
+        assert_eq!("Invalid duration h. Must be like 45m, 2h or 1h30m", parse_duration("h").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_duration_with_zero_return_error() {
+        assert_eq!("Invalid duration 0m. Must be like 45m, 2h or 1h30m", parse_duration("0m").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_duration_with_overflow_return_error() {
+        assert_eq!("Invalid duration 99999999999999999h. Must be like 45m, 2h or 1h30m",
+                   parse_duration("99999999999999999h").unwrap_err().to_string());
+        assert!(parse_duration("200000000000000m").is_err());
+        assert!(parse_duration("100001h").is_err());
+        assert_eq!(Duration::hours(100_000), parse_duration("100000h").unwrap());
+    }
+
+    #[test]
+    fn parse_duration_with_empty_return_error() {
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn format_duration_with_45_minutes_return_45m() {
+        assert_eq!("45m", format_duration(&Duration::minutes(45)));
+    }
+
+    #[test]
+    fn format_duration_with_125_minutes_return_2h_05m() {
+        assert_eq!("2h 05m", format_duration(&Duration::minutes(125)));
+    }
+
+    #[test]
+    fn parse_age_with_days_and_weeks_return_duration() {
+        assert_eq!(Duration::days(30), parse_age("30d").unwrap());
+        assert_eq!(Duration::days(14), parse_age("2W").unwrap());
+        assert_eq!("Invalid age 30. Must be like 30d or 2w", parse_age("30").unwrap_err().to_string());
+        assert!(parse_age("0d").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("9223372036854775807w").is_err());
+        assert!(parse_age("999999999999d").is_err());
+    }
+
+    #[test]
+    fn parse_date_time_with_dates_and_delays_return_moment() {
+        let now = Local.with_ymd_and_hms(2026, 10, 19, 15, 30, 0).unwrap();
+        assert_eq!(Local.with_ymd_and_hms(2026, 11, 5, 0, 0, 0).unwrap(), parse_date_time("2026-11-05", now).unwrap());
+        assert_eq!(Local.with_ymd_and_hms(2026, 11, 5, 14, 0, 0).unwrap(), parse_date_time("2026-11-05 14:00", now).unwrap());
+        assert_eq!(Local.with_ymd_and_hms(2026, 10, 20, 0, 0, 0).unwrap(), parse_date_time("Tomorrow", now).unwrap());
+        assert_eq!(now + Duration::days(3), parse_date_time("3d", now).unwrap());
+        assert_eq!(now + Duration::hours(4), parse_date_time("4h", now).unwrap());
+    }
+
+    #[test]
+    fn parse_date_time_with_invalid_value_return_error() {
+        let now = Local.with_ymd_and_hms(2026, 10, 19, 15, 30, 0).unwrap();
+        assert_eq!("Invalid date 2026-13-01. Must be like 2026-11-05, 2026-11-05 14:00, tomorrow, 3d or 4h",
+                   parse_date_time("2026-13-01", now).unwrap_err().to_string());
+        assert!(parse_date_time("someday", now).is_err());
+        assert_eq!("Invalid date 99999999d. Must be like 2026-11-05, 2026-11-05 14:00, tomorrow, 3d or 4h",
+                   parse_date_time("99999999d", now).unwrap_err().to_string());
+    }
+}
@@ -1,59 +1,115 @@
 use crate::common_structs::{CommandResult, ExecutableCommand, Priority};
 use crate::data_service::{read_all_todos, write_todos};
-use termion::color;
+use crate::duration_format::{parse_date_time, parse_duration};
+use crate::output::{error_color, reset_color, reset_error_color, success_color};
+use crate::todo::Todo;
+use crate::todo_editor;
+use crate::todo_selection::{confirm_action, TodoSelection};
+use chrono::{DateTime, Duration, Local, Utc};
 use std::error::Error;
 
 #[derive(Debug)]
 pub struct EditCommand {
-    id: u32,
+    selection: TodoSelection,
     title: Option<String>,
-    priority: Option<Priority>
+    priority: Option<Priority>,
+    estimate: Option<Duration>,
+    // Some(None) removes the due date
+    due: Option<Option<DateTime<Utc>>>,
+    interactive: bool,
+    yes: bool
 }
 
 impl EditCommand {
     pub fn new_from_command_result(command_result: &CommandResult) -> Result<EditCommand, Box<dyn Error>> {
-        let value = command_result.get_value().trim();
-        if value.is_empty() {
-            return Err("Value cannot be empty".into());
-        }
-        let id = value.parse::<u32>()?;
+        let selection = TodoSelection::new_from_command_result(command_result)?;
         let priority = match command_result.get_options().get("priority") {
             Some(val) => Priority::from_string(val),
             _ => None
         };
-        let title: Option<String> = match command_result.get_options().get("title") {
-            Some(s) => Some(s.to_string()),
-            _ => None
-        };
+        let title: Option<String> = command_result.get_options().get("title").map(|s| s.to_string());
         if title.is_some() && title.as_ref().unwrap().trim().is_empty() {
             return Err("The title cannot be empty".into());
         }
+        let estimate = match command_result.get_options().get("estimate") {
+            Some(val) => Some(parse_duration(val)?),
+            _ => None
+        };
+        let due = match command_result.get_options().get("due") {
+            Some(val) if val.trim().eq_ignore_ascii_case("none") => Some(None),
+            Some(val) => Some(Some(parse_date_time(val, Local::now())?)),
+            _ => None
+        };
+        let interactive = command_result.get_options().contains_key("interactive");
+        if interactive && (title.is_some() || priority.is_some() || estimate.is_some() || due.is_some()) {
+            return Err("The interactive option cannot be combined with other options".into());
+        }
 
-        if title.is_none() && priority.is_none() {
+        if title.is_none() && priority.is_none() && estimate.is_none() && due.is_none() && !interactive {
             return Err("At least one option must be supplied".into());
         }
-        Ok(EditCommand {id: id, title: title, priority: priority})
+        let yes = command_result.get_options().contains_key("yes");
+        Ok(EditCommand {selection, title, priority, estimate, due, interactive, yes})
+    }
+
+    fn edit_interactively(&self, todo: &mut Todo) -> Result<Vec<String>, Box<dyn Error>> {
+        let file_name = format!("beaver-todo-{}", todo.get_id());
+        let mut document = todo_editor::to_document(todo);
+        loop {
+            let edited_document = todo_editor::edit_in_editor(&document, &file_name)?;
+            if edited_document.trim().is_empty() {
+                return Err("The edit has been cancelled".into());
+            }
+            match todo_editor::apply_document(todo, &edited_document) {
+                Ok(changes) => return Ok(changes),
+                Err(errors) => {
+                    eprintln!("{}The todo contains {} error(s), reopening the editor{}", error_color(), errors.len(), reset_error_color());
+                    document = todo_editor::annotate_errors(&edited_document, &errors);
+                }
+            }
+        }
     }
 }
 
 impl ExecutableCommand for EditCommand {
     fn execute(&self) -> Result<(), Box<dyn Error>> {
         let mut todos = read_all_todos()?;
-        //Find the todo to update
-        let mut iter = todos.iter_mut();
-        match iter.find(|x| x.get_id() == self.id && !x.get_completed()) {
-            Some(todo) => {
-                if self.title.is_some() {
-                    todo.set_title(self.title.as_ref().unwrap().as_str())?;
-                }
-                if self.priority.is_some() {
-                    todo.set_priority(self.priority.unwrap())
+        //Find the todos to update
+        let indexes = self.selection.find_indexes(&todos)?;
+        if indexes.len() > 1 {
+            if self.title.is_some() || self.interactive {
+                return Err("The title and interactive options can only be used with a single todo".into());
+            }
+            if !self.yes && !confirm_action("updated", &todos, &indexes)? {
+                return Ok(());
+            }
+        }
+        for index in &indexes {
+            let todo = &mut todos[*index];
+            if self.interactive {
+                let changes = self.edit_interactively(todo)?;
+                if changes.is_empty() {
+                    println!("No changes were made to the todo {}", todo.get_id());
+                    return Ok(());
                 }
-            },
-            None => return Err(format!("Unable to find the todo with id {}", self.id).into())
+            }
+            if let Some(title) = &self.title {
+                todo.set_title(title.as_str())?;
+            }
+            if let Some(priority) = self.priority {
+                todo.set_priority(priority)
+            }
+            if self.estimate.is_some() {
+                todo.set_estimate(self.estimate)?;
+            }
+            if let Some(due) = self.due {
+                todo.set_due(due);
+            }
         }
         write_todos(&todos)?;
-        println!("{}The todo {} has been updated!{}", color::Fg(color::Green), self.id, color::Fg(color::Reset));
+        for index in &indexes {
+            println!("{}The todo {} has been updated!{}", success_color(), todos[*index].get_id(), reset_color());
+        }
         Ok(())
     }
 }
@@ -62,6 +118,7 @@ impl ExecutableCommand for EditCommand {
 mod tests {
     use crate::common_structs::{Command, CommandResult, Priority};
     use crate::edit_command::EditCommand;
+    use crate::todo_selection::TodoSelection;
     use std::collections::HashMap;
 
     #[test]
@@ -85,7 +142,7 @@ mod tests {
         let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
             "1", 
             HashMap::from([(String::from("title"), String::from("test"))])));
-        assert_eq!(1, command.unwrap().id);
+        assert_eq!(TodoSelection::Ids(vec![1]), command.unwrap().selection);
     }
 
     #[test]
@@ -93,7 +150,7 @@ mod tests {
         let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
             "100", 
             HashMap::from([(String::from("title"), String::from("test"))])));
-        assert_eq!(100, command.unwrap().id);
+        assert_eq!(TodoSelection::Ids(vec![100]), command.unwrap().selection);
     }
 
     #[test]
@@ -141,7 +198,7 @@ mod tests {
         let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
             "1", 
             HashMap::from([(String::from("title"), String::from("test"))]))).unwrap();
-        assert_eq!(1, command.id);
+        assert_eq!(TodoSelection::Ids(vec![1]), command.selection);
         assert_eq!("test", command.title.unwrap());
         assert!(command.priority.is_none());
     }
@@ -151,7 +208,7 @@ mod tests {
         let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
             "1", 
             HashMap::from([(String::from("priority"), String::from("m"))]))).unwrap();
-        assert_eq!(1, command.id);
+        assert_eq!(TodoSelection::Ids(vec![1]), command.selection);
         assert_eq!(Priority::Medium, command.priority.unwrap());
         assert!(command.title.is_none());
     }
@@ -162,8 +219,45 @@ mod tests {
             "1", 
             HashMap::from([(String::from("title"), String::from("test")),
                            (String::from("priority"), String::from("m"))]))).unwrap();
-        assert_eq!(1, command.id);
+        assert_eq!(TodoSelection::Ids(vec![1]), command.selection);
         assert_eq!(Priority::Medium, command.priority.unwrap());
         assert_eq!("test", command.title.unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn edit_command_new_from_command_result_with_interactive_return_success() {
+        let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
+            "1", 
+            HashMap::from([(String::from("interactive"), String::from(""))]))).unwrap();
+        assert_eq!(TodoSelection::Ids(vec![1]), command.selection);
+        assert!(command.interactive);
+        assert!(command.title.is_none());
+        assert!(command.priority.is_none());
+    }
+
+    #[test]
+    fn edit_command_new_from_command_result_with_interactive_and_title_return_error() {
+        let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
+            "1", 
+            HashMap::from([(String::from("interactive"), String::from("")),
+                           (String::from("title"), String::from("test"))])));
+        assert_eq!("The interactive option cannot be combined with other options", command.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn edit_command_new_from_command_result_with_id_list_and_priority_return_success() {
+        let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
+            "2,4", 
+            HashMap::from([(String::from("priority"), String::from("H"))]))).unwrap();
+        assert_eq!(TodoSelection::Ids(vec![2, 4]), command.selection);
+        assert_eq!(Priority::High, command.priority.unwrap());
+    }
+
+    #[test]
+    fn edit_command_new_from_command_result_with_estimate_return_success() {
+        let command = EditCommand::new_from_command_result(&CommandResult::new(Command::Edit, 
+            "1", 
+            HashMap::from([(String::from("estimate"), String::from("1h30m"))]))).unwrap();
+        assert_eq!(90, command.estimate.unwrap().num_minutes());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::read_all_todos;
+use crate::output::{reset_color, success_color};
+use crate::todo::Todo;
+use crate::todo_format::{new_format, split_format_and_file, TodoFormat};
+use crate::todo_selection::{parse_criteria, Criterion};
+use std::error::Error;
+use std::fs;
+
+pub struct ExportCommand {
+    file_path: Option<String>,
+    format: Box<dyn TodoFormat>,
+    criteria: Vec<Criterion>
+}
+
+impl ExportCommand {
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<ExportCommand, Box<dyn Error>> {
+        let (format_name, file_path) = match command_result.get_options().get("to") {
+            Some(to) => split_format_and_file(to, command_result.get_value()),
+            None => return Err("The to option is required".into())
+        };
+        let format = new_format(&format_name, command_result.get_options())?;
+        let file_path = match file_path.is_empty() {
+            true => None,
+            false => Some(file_path)
+        };
+        let criteria = match command_result.get_options().get("where") {
+            Some(criteria) => parse_criteria(criteria)?,
+            None => vec![]
+        };
+        Ok(ExportCommand { file_path, format, criteria })
+    }
+}
+
+impl ExecutableCommand for ExportCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let todos: Vec<Todo> = read_all_todos()?
+            .into_iter()
+            .filter(|x| self.criteria.iter().all(|c| c.matches(x)))
+            .collect();
+        let content = self.format.export(&todos)?;
+        match &self.file_path {
+            Some(file_path) => {
+                fs::write(file_path, content)?;
+                println!("{}{} todo(s) exported to {}!{}", success_color(), todos.len(), file_path, reset_color());
+            },
+            None => print!("{}", content)
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export_command::ExportCommand;
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use crate::todo_selection::Criterion;
+    use std::collections::HashMap;
+
+    #[test]
+    fn export_command_new_from_command_result_without_to_return_error() {
+        let command = ExportCommand::new_from_command_result(&CommandResult::new(Command::Export,
+            "",
+            HashMap::new()));
+        assert_eq!("The to option is required", command.err().unwrap().to_string());
+    }
+
+    #[test]
+    fn export_command_new_from_command_result_without_file_return_stdout() {
+        let command = ExportCommand::new_from_command_result(&CommandResult::new(Command::Export,
+            "",
+            HashMap::from([(String::from("to"), String::from("todotxt"))])));
+        assert!(command.ok().unwrap().file_path.is_none());
+    }
+
+    #[test]
+    fn export_command_new_from_command_result_with_where_return_criteria() {
+        let command = ExportCommand::new_from_command_result(&CommandResult::new(Command::Export,
+            "backlog.csv",
+            HashMap::from([(String::from("to"), String::from("csv")), (String::from("where"), String::from("status=open,priority=H"))])));
+        assert_eq!(vec![Criterion::Completed(false), Criterion::Priority(Priority::High)], command.ok().unwrap().criteria);
+    }
+
+    #[test]
+    fn export_command_new_from_command_result_with_file_return_file() {
+        let command = ExportCommand::new_from_command_result(&CommandResult::new(Command::Export,
+            "todo.txt",
+            HashMap::from([(String::from("to"), String::from("TodoTxt"))])));
+        assert_eq!("todo.txt", command.ok().unwrap().file_path.unwrap());
+    }
+}
@@ -1,11 +1,15 @@
 use crate::common_structs::{CommandResult, ExecutableCommand};
 use crate::data_service::read_all_todos;
-use termion::style;
+use crate::duration_format::format_duration;
+use crate::output::{bold, reset_style};
+use crate::todo::Todo;
+use chrono::{DateTime, Utc};
 use std::error::Error;
 
 #[derive(Debug)]
 pub struct FetchCommand {
-    id: u32
+    id: u32,
+    show_history: bool
 }
 impl FetchCommand {
     pub fn new_from_command_result(command_result : &CommandResult) -> Result<FetchCommand, Box<dyn Error>> {
@@ -14,20 +18,68 @@ impl FetchCommand {
             return Err("Value cannot be empty".into());
         }
         let id = value.parse::<u32>()?;
-        Ok(FetchCommand { id: id })
+        let show_history = command_result.get_options().contains_key("history");
+        Ok(FetchCommand { id, show_history })
     }
 }
 
+// Return the history of the todo as lines like: 2026-10-19 09:30  alice  Priority changed from Low to High
+pub fn get_history_lines(todo: &Todo) -> Vec<String> {
+    let user_width = todo.get_history().iter().map(|x| x.get_user().chars().count()).max().unwrap_or(0);
+    todo.get_history().iter()
+        .map(|x| format!("{}  {:<width$}  {}", x.get_when_in_localtime().format("%Y-%m-%d %H:%M"), x.get_user(), x.get_description(),
+                         width=user_width))
+        .collect()
+}
+
+// Return the label and the value of each detail of the todo, the details that aren't set are skipped
+pub fn get_todo_details(todo: &Todo, now: DateTime<Utc>) -> Vec<(&'static str, String)> {
+    let mut details = vec![("Title", todo.get_title().to_string()),
+                           ("ID", todo.get_id().to_string()),
+                           ("Priority", todo.get_priority().to_string()),
+                           ("Created on", todo.get_when_created_in_localtime().to_string())];
+    if let Some(due) = todo.get_due_in_localtime() {
+        details.push(("Due on", due.to_string()));
+    }
+    if let Some(wait_until) = todo.get_wait_until_in_localtime().filter(|_| todo.is_waiting(now)) {
+        details.push(("Snoozed until", wait_until.to_string()));
+    }
+    if let Some(project) = todo.get_project() {
+        details.push(("Project", project.to_string()));
+    }
+    if !todo.get_tags().is_empty() {
+        details.push(("Tags", todo.get_tags().join(", ")));
+    }
+    if !todo.get_time_entries().is_empty() {
+        let mut time_spent = format_duration(&todo.get_time_spent(now));
+        if todo.is_tracking() {
+            time_spent.push_str(" (tracking in progress)");
+        }
+        details.push(("Time spent", time_spent));
+    }
+    details
+}
+
 impl ExecutableCommand for FetchCommand {
     fn execute(&self) -> Result<(), Box<dyn Error>> {
         let todos = read_all_todos()?;
-        let mut iter = todos.iter();
-        match iter.find(|x| x.get_id() == self.id && !x.get_completed()) {
+        // A completed todo keeps its id until it's given to another todo, the open todo is preferred
+        let todo = todos.iter().find(|x| x.get_id() == self.id && !x.get_completed())
+            .or_else(|| todos.iter().filter(|x| x.get_id() == self.id).max_by_key(|x| x.get_when_completed_in_localtime()));
+        match todo {
             Some(todo) => {
-                println!("Title: {}{}{}", style::Bold, todo.get_title(), style::Reset);
-                println!("ID: {}", todo.get_id());
-                println!("Priority: {}", todo.get_priority().to_string());
-                println!("Created on: {}", todo.get_when_created_in_localtime());
+                for (label, value) in get_todo_details(todo, Utc::now()) {
+                    match label {
+                        "Title" => println!("{}: {}{}{}", label, bold(), value, reset_style()),
+                        _ => println!("{}: {}", label, value)
+                    }
+                }
+                if self.show_history {
+                    println!("History:");
+                    for line in get_history_lines(todo) {
+                        println!("    {}", line);
+                    }
+                }
             },
             None => return Err(format!("Unable to find the todo with id {}", self.id).into())
         }
@@ -37,10 +89,39 @@ impl ExecutableCommand for FetchCommand {
 
 #[cfg(test)]
 mod tests {
-    use crate::fetch_command::FetchCommand;
-    use crate::common_structs::{Command, CommandResult};
+    use crate::fetch_command::{get_history_lines, get_todo_details, FetchCommand};
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use crate::todo::Todo;
+    use chrono::{Duration, Utc};
     use std::collections::HashMap;
 
+    #[test]
+    fn get_todo_details_return_only_details_set() {
+        let mut todo = Todo::new(3, "Call mom", Priority::High, Utc::now()).unwrap();
+        todo.set_tags(vec![String::from("family"), String::from("phone")]);
+        let details = get_todo_details(&todo, Utc::now());
+        let labels: Vec<&str> = details.iter().map(|(label, _)| *label).collect();
+        assert_eq!(vec!["Title", "ID", "Priority", "Created on", "Tags"], labels);
+        assert_eq!("High", details[2].1);
+        assert_eq!("family, phone", details[4].1);
+    }
+
+    #[test]
+    fn get_history_lines_return_each_change() {
+        let created = Utc::now() - Duration::days(1);
+        let previous = Todo::new(3, "Call mom", Priority::Low, created).unwrap();
+        let mut todo = previous.clone();
+        todo.record_changes(None, created, "bob");
+        todo.set_priority(Priority::High);
+        todo.set_title("Call mom and dad").unwrap();
+        todo.record_changes(Some(&previous), Utc::now(), "alice");
+        let lines = get_history_lines(&todo);
+        assert_eq!(3, lines.len());
+        assert!(lines[0].ends_with("  bob    Created"));
+        assert!(lines[1].ends_with("  alice  Title changed from \"Call mom\" to \"Call mom and dad\""));
+        assert!(lines[2].ends_with("  alice  Priority changed from Low to High"));
+    }
+
     #[test]
     fn fetch_command_new_from_command_result_with_empty_value_return_error() {
         let command = FetchCommand::new_from_command_result(&CommandResult::new(Command::Fetch, 
@@ -0,0 +1,329 @@
+use crate::common_structs::Priority;
+use crate::todo::Todo;
+use crate::todo_format::{ImportResult, TodoFormat};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::error::Error;
+
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const MAX_LINE_LENGTH: usize = 75;
+
+#[derive(Debug)]
+pub struct IcsFormat {
+}
+
+// A content line of the iCalendar file (NAME;PARAM=x:value)
+#[derive(Debug, PartialEq)]
+struct Property {
+    name: String,
+    value: String
+}
+
+impl IcsFormat {
+    // RFC 5545 priorities go from 1 (highest) to 9 (lowest), 0 being undefined
+    fn priority_to_number(priority: Priority) -> u8 {
+        match priority {
+            Priority::High => 1,
+            Priority::Medium => 5,
+            Priority::Low => 9
+        }
+    }
+
+    fn priority_from_number(value: &str) -> Option<Priority> {
+        match value.trim().parse::<u8>().ok()? {
+            0 => Some(Priority::Low),
+            1..=4 => Some(Priority::High),
+            5 => Some(Priority::Medium),
+            6..=9 => Some(Priority::Low),
+            _ => None
+        }
+    }
+
+    fn format_date(date: DateTime<Utc>) -> String {
+        date.format(DATE_TIME_FORMAT).to_string()
+    }
+
+    // Dates are either UTC (20261001T093000Z), floating local times (20261001T093000)
+    // or dates only (20261001).
+    fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+        let value = value.trim();
+        if let Some(utc_value) = value.strip_suffix('Z') {
+            return NaiveDateTime::parse_from_str(utc_value, "%Y%m%dT%H%M%S").ok().map(|x| x.and_utc());
+        }
+        let naive_date = match NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+            Ok(date) => date,
+            Err(_) => NaiveDate::parse_from_str(value, "%Y%m%d").ok()?.and_hms_opt(0, 0, 0)?
+        };
+        Local.from_local_datetime(&naive_date).earliest().map(|x| x.with_timezone(&Utc))
+    }
+
+    fn escape_text(value: &str) -> String {
+        value.replace('\\', "\\\\")
+             .replace(';', "\\;")
+             .replace(',', "\\,")
+             .replace('\n', "\\n")
+    }
+
+    fn unescape_text(value: &str) -> String {
+        let mut result = String::new();
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                result.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') | Some('N') => result.push('\n'),
+                Some(escaped) => result.push(escaped),
+                None => result.push('\\')
+            }
+        }
+        result
+    }
+
+    // Lines longer than 75 octets are folded with a CRLF followed by a space
+    fn fold_line(line: &str) -> String {
+        let mut result = String::new();
+        let mut line_length = 0;
+        for c in line.chars() {
+            if line_length + c.len_utf8() > MAX_LINE_LENGTH {
+                result.push_str("\r\n ");
+                line_length = 1;
+            }
+            result.push(c);
+            line_length += c.len_utf8();
+        }
+        result.push_str("\r\n");
+        result
+    }
+
+    // Return the unfolded lines with the number of the line where each one starts
+    fn unfold_lines(content: &str) -> Vec<(usize, String)> {
+        let mut lines: Vec<(usize, String)> = vec![];
+        for (index, line) in content.lines().enumerate() {
+            match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+                (Some(continuation), Some(last_line)) => last_line.1.push_str(continuation),
+                _ if line.trim().is_empty() => (),
+                _ => lines.push((index + 1, line.to_string()))
+            }
+        }
+        lines
+    }
+
+    fn parse_property(line: &str) -> Option<Property> {
+        // The parameters (VALUE=DATE, LANGUAGE=en...) are ignored, dates are recognized by their format
+        let (name_and_parameters, value) = line.split_once(':')?;
+        let name = name_and_parameters.split(';').next()?.trim().to_uppercase();
+        Some(Property { name, value: value.to_string() })
+    }
+
+    fn export_at(todos: &[Todo], now: DateTime<Utc>) -> String {
+        let mut content = String::new();
+        content.push_str("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//beaver//beaver todos//EN\r\n");
+        for todo in todos {
+            let created = todo.get_when_created_in_localtime().with_timezone(&Utc);
+            content.push_str("BEGIN:VTODO\r\n");
+            content.push_str(&format!("UID:{}\r\n", todo.get_uuid()));
+            content.push_str(&format!("DTSTAMP:{}\r\n", IcsFormat::format_date(now)));
+            content.push_str(&IcsFormat::fold_line(&format!("SUMMARY:{}", IcsFormat::escape_text(todo.get_title()))));
+            content.push_str(&format!("PRIORITY:{}\r\n", IcsFormat::priority_to_number(todo.get_priority())));
+            content.push_str(&format!("CREATED:{}\r\n", IcsFormat::format_date(created)));
+            if todo.get_completed() {
+                let completed = todo.get_when_completed_in_localtime().with_timezone(&Utc);
+                content.push_str(&format!("COMPLETED:{}\r\n", IcsFormat::format_date(completed)));
+                content.push_str("STATUS:COMPLETED\r\n");
+            }
+            else {
+                content.push_str("STATUS:NEEDS-ACTION\r\n");
+            }
+            content.push_str("END:VTODO\r\n");
+        }
+        content.push_str("END:VCALENDAR\r\n");
+        content
+    }
+
+    fn parse_vtodo(properties: &[Property], line_number: usize, result: &mut ImportResult) {
+        let get = |name: &str| properties.iter().find(|x| x.name == name);
+        let source = match get("SUMMARY").or_else(|| get("UID")) {
+            Some(property) => format!("{}:{}", property.name, property.value),
+            None => String::from("VTODO")
+        };
+        let title = get("SUMMARY").map(|x| IcsFormat::unescape_text(&x.value)).unwrap_or_default();
+        let priority = match get("PRIORITY") {
+            Some(property) => match IcsFormat::priority_from_number(&property.value) {
+                Some(priority) => priority,
+                None => {
+                    result.add_issue(line_number, &format!("The priority {} is invalid and has been imported as Low", property.value), &source);
+                    Priority::Low
+                }
+            },
+            None => Priority::Low
+        };
+        let when_created = get("CREATED").or_else(|| get("DTSTAMP")).and_then(|x| IcsFormat::parse_date(&x.value));
+        let mut todo = match Todo::new(0, title.trim(), priority, when_created.unwrap_or_else(Utc::now)) {
+            Ok(todo) => todo,
+            Err(_) => {
+                result.add_issue(line_number, "The VTODO has no summary and has been skipped", &source);
+                return;
+            }
+        };
+        let when_completed = get("COMPLETED").and_then(|x| IcsFormat::parse_date(&x.value));
+        let completed = match get("STATUS").map(|x| x.value.trim().to_uppercase()) {
+            Some(status) if status == "COMPLETED" => true,
+            Some(status) if status == "CANCELLED" => {
+                result.add_issue(line_number, "The status CANCELLED has no equivalent and has been imported as completed", &source);
+                true
+            },
+            Some(_) => false,
+            None => when_completed.is_some()
+        };
+        if completed {
+            todo.set_completed(true, Some(when_completed.unwrap_or_else(Utc::now)));
+        }
+        // The UID identifies the todo when the file is imported again
+        if get("UID").map(|x| todo.set_uuid(&x.value)).and_then(|x| x.ok()).is_none() {
+            result.add_issue(line_number, "The VTODO has no valid UID, importing it again will duplicate it", &source);
+        }
+        result.add_todo(todo);
+    }
+}
+
+impl TodoFormat for IcsFormat {
+    fn export(&self, todos: &[Todo]) -> Result<String, Box<dyn Error>> {
+        Ok(IcsFormat::export_at(todos, Utc::now()))
+    }
+
+    fn import(&self, content: &str) -> Result<ImportResult, Box<dyn Error>> {
+        let lines = IcsFormat::unfold_lines(content);
+        if !lines.iter().any(|(_, line)| line.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+            return Err("The file is not an iCalendar file".into());
+        }
+        let mut result = ImportResult::new();
+        let mut vtodo: Option<(usize, Vec<Property>)> = None;
+        let mut nested_components = 0;
+        for (line_number, line) in lines {
+            let property = match IcsFormat::parse_property(&line) {
+                Some(property) => property,
+                None => continue
+            };
+            match (property.name.as_str(), property.value.trim().to_uppercase().as_str(), vtodo.as_mut()) {
+                ("BEGIN", "VTODO", None) => vtodo = Some((line_number, vec![])),
+                ("END", "VTODO", Some(_)) => {
+                    let (start_line_number, properties) = vtodo.take().unwrap();
+                    IcsFormat::parse_vtodo(&properties, start_line_number, &mut result);
+                },
+                // Components nested in a VTODO (VALARM) have their own properties
+                ("BEGIN", _, Some(_)) => nested_components += 1,
+                ("END", _, Some(_)) => nested_components -= 1,
+                (_, _, Some((_, properties))) if nested_components == 0 => properties.push(property),
+                _ => ()
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::data_service::merge_imported_todos;
+    use crate::ics_format::IcsFormat;
+    use crate::todo::Todo;
+    use crate::todo_format::TodoFormat;
+    use chrono::{Local, TimeZone, Utc};
+
+    const UUID: &str = "7a3c1f0e-5d2b-4c8e-9f1a-2b3c4d5e6f70";
+    const OTHER_UUID: &str = "1f0e5d7a-3b2c-4d1e-8f9a-0b1c2d3e4f50";
+
+    #[test]
+    fn export_return_vtodo_components() {
+        let created = Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap();
+        let mut todo = Todo::new(1, "Call mom", Priority::High, created).unwrap();
+        todo.set_uuid(UUID).unwrap();
+        let mut completed = Todo::new(2, "Pay the bills, rent; water", Priority::Medium, created).unwrap();
+        completed.set_uuid(OTHER_UUID).unwrap();
+        completed.set_completed(true, Some(Utc.with_ymd_and_hms(2026, 10, 3, 18, 0, 0).unwrap()));
+        let todos = vec![todo, completed];
+        let content = IcsFormat::export_at(&todos, Utc.with_ymd_and_hms(2026, 10, 19, 8, 0, 0).unwrap());
+        assert_eq!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//beaver//beaver todos//EN\r\n\
+                    BEGIN:VTODO\r\nUID:7a3c1f0e-5d2b-4c8e-9f1a-2b3c4d5e6f70\r\nDTSTAMP:20261019T080000Z\r\nSUMMARY:Call mom\r\n\
+                    PRIORITY:1\r\nCREATED:20261001T093000Z\r\nSTATUS:NEEDS-ACTION\r\nEND:VTODO\r\n\
+                    BEGIN:VTODO\r\nUID:1f0e5d7a-3b2c-4d1e-8f9a-0b1c2d3e4f50\r\nDTSTAMP:20261019T080000Z\r\nSUMMARY:Pay the bills\\, rent\\; water\r\n\
+                    PRIORITY:5\r\nCREATED:20261001T093000Z\r\nCOMPLETED:20261003T180000Z\r\nSTATUS:COMPLETED\r\nEND:VTODO\r\n\
+                    END:VCALENDAR\r\n", content);
+    }
+
+    #[test]
+    fn fold_line_with_long_line_return_folded_line() {
+        let line = format!("SUMMARY:{}", "a".repeat(80));
+        assert_eq!(format!("SUMMARY:{}\r\n {}\r\n", "a".repeat(67), "a".repeat(13)), IcsFormat::fold_line(&line));
+    }
+
+    #[test]
+    fn import_with_vtodos_return_todos() {
+        let content = format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+                       BEGIN:VTODO\r\nUID:{}\r\nSUMMARY:Call mom\\, then dad\r\nPRIORITY:3\r\nCREATED:20261001T093000Z\r\nEND:VTODO\r\n\
+                       BEGIN:VTODO\r\nUID:{}\r\nSUMMARY;LANGUAGE=en:Pay the \r\n bills\r\nPRIORITY:7\r\nCREATED;VALUE=DATE:20261001\r\n\
+                       COMPLETED:20261003T180000Z\r\nSTATUS:COMPLETED\r\n\
+                       BEGIN:VALARM\r\nACTION:DISPLAY\r\nSUMMARY:Reminder\r\nEND:VALARM\r\nEND:VTODO\r\n\
+                       END:VCALENDAR\r\n", UUID, OTHER_UUID);
+        let result = IcsFormat {}.import(&content).unwrap();
+        assert_eq!(2, result.get_todos().len());
+        let todo = &result.get_todos()[0];
+        assert_eq!("Call mom, then dad", todo.get_title());
+        assert_eq!(UUID, todo.get_uuid());
+        assert_eq!(Priority::High, todo.get_priority());
+        assert_eq!(Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap(), todo.get_when_created_in_localtime());
+        assert!(!todo.get_completed());
+        let todo = &result.get_todos()[1];
+        assert_eq!("Pay the bills", todo.get_title());
+        assert_eq!(Priority::Low, todo.get_priority());
+        assert_eq!(Local.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap(), todo.get_when_created_in_localtime());
+        assert!(todo.get_completed());
+        assert_eq!(Utc.with_ymd_and_hms(2026, 10, 3, 18, 0, 0).unwrap(), todo.get_when_completed_in_localtime());
+        assert!(result.get_issues().is_empty());
+    }
+
+    #[test]
+    fn import_with_invalid_vtodos_return_issues() {
+        let content = "BEGIN:VCALENDAR\n\
+                       BEGIN:VTODO\nUID:abc\nEND:VTODO\n\
+                       BEGIN:VTODO\nSUMMARY:Old task\nPRIORITY:12\nSTATUS:CANCELLED\nEND:VTODO\n\
+                       END:VCALENDAR\n";
+        let result = IcsFormat {}.import(content).unwrap();
+        assert_eq!(1, result.get_todos().len());
+        assert!(result.get_todos()[0].get_completed());
+        assert_eq!(vec!["Line 2: The VTODO has no summary and has been skipped (UID:abc)",
+                        "Line 5: The priority 12 is invalid and has been imported as Low (SUMMARY:Old task)",
+                        "Line 5: The status CANCELLED has no equivalent and has been imported as completed (SUMMARY:Old task)",
+                        "Line 5: The VTODO has no valid UID, importing it again will duplicate it (SUMMARY:Old task)"],
+                   *result.get_issues());
+    }
+
+    #[test]
+    fn import_without_vcalendar_return_error() {
+        assert_eq!("The file is not an iCalendar file", IcsFormat {}.import("Call mom\n").err().unwrap().to_string());
+    }
+
+    #[test]
+    fn export_then_import_return_same_todos() {
+        let created = Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap();
+        let todos = vec![Todo::new(1, format!("Write the report; {}", "long ".repeat(20)).trim(), Priority::Medium, created).unwrap()];
+        let content = IcsFormat {}.export(&todos).unwrap();
+        let result = IcsFormat {}.import(&content).unwrap();
+        assert_eq!(todos[0].get_title(), result.get_todos()[0].get_title());
+        assert_eq!(todos[0].get_priority(), result.get_todos()[0].get_priority());
+        assert_eq!(todos[0].get_when_created_in_localtime(), result.get_todos()[0].get_when_created_in_localtime());
+        assert_eq!(todos[0].get_uuid(), result.get_todos()[0].get_uuid());
+        assert!(result.get_issues().is_empty());
+    }
+
+    #[test]
+    fn import_twice_update_imported_todos() {
+        let created = Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap();
+        let content = IcsFormat {}.export(&[Todo::new(1, "Call mom", Priority::High, created).unwrap()]).unwrap();
+        let mut todos = vec![];
+        assert_eq!((1, 0), merge_imported_todos(&mut todos, IcsFormat {}.import(&content).unwrap().get_todos()));
+        assert_eq!((0, 1), merge_imported_todos(&mut todos, IcsFormat {}.import(&content).unwrap().get_todos()));
+        assert_eq!(1, todos.len());
+    }
+}
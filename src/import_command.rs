@@ -0,0 +1,108 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::import_todos;
+use crate::output::{reset_color, reset_error_color, success_color, warning_color};
+use crate::todo_format::{new_format, split_format_and_file, TodoFormat};
+use std::error::Error;
+use std::fs;
+
+pub struct ImportCommand {
+    file_path: String,
+    format: Box<dyn TodoFormat>,
+    dry_run: bool
+}
+
+impl ImportCommand {
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<ImportCommand, Box<dyn Error>> {
+        let (format_name, file_path) = match command_result.get_options().get("from") {
+            Some(from) => split_format_and_file(from, command_result.get_value()),
+            None => return Err("The from option is required".into())
+        };
+        if file_path.is_empty() {
+            return Err("Value cannot be empty".into());
+        }
+        let format = new_format(&format_name, command_result.get_options())?;
+        let dry_run = command_result.get_options().contains_key("dry-run");
+        Ok(ImportCommand { file_path, format, dry_run })
+    }
+}
+
+impl ExecutableCommand for ImportCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let content = match fs::read_to_string(&self.file_path) {
+            Ok(content) => content,
+            Err(e) => return Err(format!("Unable to read the file {}: {}", self.file_path, e).into())
+        };
+        let result = self.format.import(&content)?;
+        for issue in result.get_issues() {
+            eprintln!("{}{}{}", warning_color(), issue, reset_error_color());
+        }
+        if self.dry_run {
+            for todo in result.get_todos() {
+                println!("    {} ({}){}", todo.get_title(), todo.get_priority(), if todo.get_completed() { " - completed" } else { "" });
+            }
+            println!("{} todo(s) would be imported from {}. Nothing has been saved", result.get_todos().len(), self.file_path);
+            return Ok(());
+        }
+        let (added_count, updated_count) = import_todos(result.get_todos())?;
+        println!("{}{} todo(s) imported from {}!{}", success_color(), added_count, self.file_path, reset_color());
+        if updated_count > 0 {
+            println!("{} todo(s) already imported have been updated", updated_count);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import_command::ImportCommand;
+    use crate::common_structs::{Command, CommandResult};
+    use std::collections::HashMap;
+
+    #[test]
+    fn import_command_new_from_command_result_with_empty_value_return_error() {
+        let command = ImportCommand::new_from_command_result(&CommandResult::new(Command::Import,
+            "",
+            HashMap::from([(String::from("from"), String::from("todotxt"))])));
+        assert_eq!("Value cannot be empty", command.err().unwrap().to_string());
+    }
+
+    #[test]
+    fn import_command_new_from_command_result_without_from_return_error() {
+        let command = ImportCommand::new_from_command_result(&CommandResult::new(Command::Import,
+            "todo.txt",
+            HashMap::new()));
+        assert_eq!("The from option is required", command.err().unwrap().to_string());
+    }
+
+    #[test]
+    fn import_command_new_from_command_result_with_unknown_format_return_error() {
+        let command = ImportCommand::new_from_command_result(&CommandResult::new(Command::Import,
+            "todo.txt",
+            HashMap::from([(String::from("from"), String::from("blabla"))])));
+        assert_eq!("Unknown format blabla. Must be csv, ics, markdown, org, taskwarrior or todotxt", command.err().unwrap().to_string());
+    }
+
+    #[test]
+    fn import_command_new_from_command_result_with_file_after_from_return_success() {
+        let command = ImportCommand::new_from_command_result(&CommandResult::new(Command::Import,
+            "",
+            HashMap::from([(String::from("from"), String::from("todotxt my todo.txt"))])));
+        assert_eq!("my todo.txt", command.ok().unwrap().file_path);
+    }
+
+    #[test]
+    fn import_command_new_from_command_result_with_dry_run_return_dry_run() {
+        let command = ImportCommand::new_from_command_result(&CommandResult::new(Command::Import,
+            "backlog.csv",
+            HashMap::from([(String::from("from"), String::from("csv")), (String::from("dry-run"), String::new())])));
+        assert!(command.ok().unwrap().dry_run);
+    }
+
+    #[test]
+    fn import_command_new_from_command_result_with_todotxt_return_success() {
+        let command = ImportCommand::new_from_command_result(&CommandResult::new(Command::Import,
+            "todo.txt",
+            HashMap::from([(String::from("from"), String::from("todotxt"))])));
+        assert_eq!("todo.txt", command.ok().unwrap().file_path);
+    }
+}
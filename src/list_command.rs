@@ -1,7 +1,9 @@
 use crate::data_service;
 use crate::common_structs::{CommandResult, ExecutableCommand, Priority};
+use crate::duration_format::format_duration;
+use crate::output::{priority_style, reset_style, underline};
 use crate::todo::Todo;
-use termion::style;
+use chrono::{Duration, Utc};
 use std::error::Error;
 use std::io;
 use std::io::Write;
@@ -16,14 +18,26 @@ pub enum SortOrder {
 
 pub struct ListCommand {
     sort_order: SortOrder,
-    all: bool
+    all: bool,
+    archived: bool,
+    waiting: bool,
+    priority: Option<Priority>
 }
 
 impl ListCommand {
     const ID_WIDTH: usize = 3;
+    const UUID_WIDTH: usize = 36;
     const PRIORITY_WIDTH: usize = 10;
+    const TIME_WIDTH: usize = 10;
     const CREATION_WIDTH: usize = 33;
     const COMPLETED_DATE_WIDTH: usize = 33;
+    const WAIT_DATE_WIDTH: usize = 18;
+    const REAPPEARED_MARKER: &'static str = "(back) ";
+
+    // A list of the open todos, used by the commands displaying the todos with the same columns
+    pub fn new(sort_order: SortOrder) -> ListCommand {
+        ListCommand { sort_order, all: false, archived: false, waiting: false, priority: None }
+    }
 
     pub fn new_from_command_result(command_result : &CommandResult) -> Result<ListCommand, Box<dyn Error>> {
         let sort_order_choosen = match command_result.get_options().get("sort") {
@@ -40,20 +54,44 @@ impl ListCommand {
             }
             None => SortOrder::PriorityDESC
         };
-        let show_all = command_result.get_options().contains_key("all");
+        // The archived todos are all completed
+        let archived = command_result.get_options().contains_key("archived");
+        let show_all = command_result.get_options().contains_key("all") || archived;
+        let waiting = command_result.get_options().contains_key("waiting");
+        if archived && waiting {
+            return Err("The options --archived and --waiting cannot be used together".into());
+        }
+        let priority = match command_result.get_options().get("priority") {
+            Some(value) => match Priority::from_string(value.trim()) {
+                Some(priority) => Some(priority),
+                None => return Err("Invalid priority value. Must be H, M or L".into())
+            },
+            None => None
+        };
         
-        Ok(ListCommand { sort_order: sort_order_choosen, all: show_all })
+        Ok(ListCommand { sort_order: sort_order_choosen, all: show_all, archived, waiting, priority })
     }
 
-    fn print_todos(&self, todos: &Vec<Todo>, include_creation: bool) {
-        let terminal_size = match termion::terminal_size() {
-            Ok(size) => size,
-            Err(_) => (100, 100)
-        };
+    // The archived todos are shown with their uuid, used by unarchive
+    fn get_id_width(&self) -> usize {
+        match self.archived {
+            true => ListCommand::UUID_WIDTH,
+            false => ListCommand::ID_WIDTH
+        }
+    }
+
+    pub fn print_todos(&self, todos: &[Todo], include_creation: bool) {
+        self.print_table(todos, include_creation);
+        self.print_estimate_total(todos);
+    }
+
+    // Print the todos without the total of their estimates
+    pub fn print_table(&self, todos: &[Todo], include_creation: bool) {
+        let terminal_size = termion::terminal_size().unwrap_or((100, 100));
         
         let mut title_width = usize::from(terminal_size.0);
-        if title_width >= ListCommand::ID_WIDTH + ListCommand::PRIORITY_WIDTH + 3 {
-            title_width -= ListCommand::ID_WIDTH + ListCommand::PRIORITY_WIDTH + 3;
+        if title_width >= self.get_id_width() + ListCommand::PRIORITY_WIDTH + ListCommand::TIME_WIDTH + 3 {
+            title_width -= self.get_id_width() + ListCommand::PRIORITY_WIDTH + ListCommand::TIME_WIDTH + 3;
         }
         if include_creation && title_width >= ListCommand::CREATION_WIDTH {
             title_width -= ListCommand::CREATION_WIDTH;
@@ -61,6 +99,9 @@ impl ListCommand {
         if self.all && title_width >= ListCommand::COMPLETED_DATE_WIDTH {
             title_width -= ListCommand::COMPLETED_DATE_WIDTH;
         }
+        if self.waiting && title_width >= ListCommand::WAIT_DATE_WIDTH {
+            title_width -= ListCommand::WAIT_DATE_WIDTH;
+        }
 
         self.print_header(title_width, include_creation);
 
@@ -69,11 +110,25 @@ impl ListCommand {
         }
     }
 
+    pub fn print_estimate_total(&self, todos: &[Todo]) {
+        let open_todos: Vec<&Todo> = todos.iter().filter(|x| !x.get_completed()).collect();
+        let estimates: Vec<Duration> = open_todos.iter().filter_map(|x| x.get_estimate()).collect();
+        if !estimates.is_empty() {
+            let total = estimates.iter().fold(Duration::zero(), |total, x| total + *x);
+            print!("Total estimate of the open todos: {}", format_duration(&total));
+            if estimates.len() < open_todos.len() {
+                print!(" ({} todo(s) without estimate)", open_todos.len() - estimates.len());
+            }
+            println!();
+        }
+    }
+
     fn print_header(&self, title_width: usize, include_creation: bool) {
         print!("{}{id:<widthi$} {title:<widtht$} {priority:widthp$}", 
-                style::Underline, id="ID", widthi=ListCommand::ID_WIDTH, 
+                underline(), id="ID", widthi=self.get_id_width(), 
                 title="Title", widtht=title_width, 
                 priority="Priority", widthp=ListCommand::PRIORITY_WIDTH);
+        print!("{time:widthti$}", time="Time", widthti=ListCommand::TIME_WIDTH);
         if include_creation {
             print!("{creation:widthc$}", creation="Creation date", widthc=ListCommand::CREATION_WIDTH)
         }
@@ -81,26 +136,36 @@ impl ListCommand {
             print!("{when_completed:widthw$}", when_completed="Completed date", widthw=ListCommand::COMPLETED_DATE_WIDTH)
 
         }
-        print!("\n{}", style::Reset);
+        if self.waiting {
+            print!("{wait_until:widthw$}", wait_until="Snoozed until", widthw=ListCommand::WAIT_DATE_WIDTH)
+        }
+        print!("\n{}", reset_style());
         io::stdout().flush().unwrap();
     }
 
     fn print_todo_line(&self, todo: &Todo, title_width: usize, include_creation: bool) {
         let mut title = todo.get_title().to_string();
+            if todo.has_reappeared(Utc::now()) {
+                title.insert_str(0, ListCommand::REAPPEARED_MARKER);
+            }
             if title.len() > title_width {
                 title = title[..title_width].to_string();
             }
-            let id = match todo.get_completed() { 
-                true => String::from("[X]"), 
-                false => todo.get_id().to_string()
+            let id = match (self.archived, todo.get_completed()) { 
+                (true, _) => todo.get_uuid().to_string(),
+                (false, true) => String::from("[X]"), 
+                (false, false) => todo.get_id().to_string()
             };
-            if todo.get_priority() == Priority::High {
-                print!("{}", style::Bold);
-            }
+            print!("{}", priority_style(todo.get_priority()));
             print!("{id:<widthi$} {title:<widtht$} {priority:widthp$}", 
-                    id=id, widthi=ListCommand::ID_WIDTH, 
+                    id=id, widthi=self.get_id_width(), 
                     title=title, widtht=title_width, 
                     priority=todo.get_priority().to_string(), widthp=ListCommand::PRIORITY_WIDTH);
+            let time_spent = match todo.get_time_entries().is_empty() {
+                true => String::new(),
+                false => format_duration(&todo.get_time_spent(Utc::now()))
+            };
+            print!("{time:widthti$}", time=time_spent, widthti=ListCommand::TIME_WIDTH);
             if include_creation {
                 print!("{creation:widthc$}", 
                        creation=todo.get_when_created_in_localtime().to_rfc2822(), 
@@ -115,27 +180,43 @@ impl ListCommand {
                        when_completed=completed_date, 
                        widthw=ListCommand::COMPLETED_DATE_WIDTH);
             }
-            print!("\n{}", style::Reset);
+            if self.waiting {
+                let wait_until = todo.get_wait_until_in_localtime().map(|x| x.format("%Y-%m-%d %H:%M").to_string());
+                print!("{wait_until:widthw$}", 
+                       wait_until=wait_until.unwrap_or_default(), 
+                       widthw=ListCommand::WAIT_DATE_WIDTH);
+            }
+            print!("\n{}", reset_style());
             io::stdout().flush().unwrap();
     }
 }
 
 impl ExecutableCommand for ListCommand {
     fn execute(&self,) -> Result<(), Box<dyn Error>> {
-        let mut todos = data_service::read_all_todos()?;
+        let mut todos = match self.archived {
+            true => data_service::read_archived_todos()?,
+            false => data_service::read_all_todos()?
+        };
         //Remove all the completed todos
         if !self.all {
             todos.retain(|x| !x.get_completed());
         }
+        if let Some(priority) = self.priority {
+            todos.retain(|x| x.get_priority() == priority);
+        }
+        //The snoozed todos are only displayed with --waiting
+        let now = Utc::now();
+        let snoozed_count = todos.iter().filter(|x| x.is_waiting(now)).count();
+        todos.retain(|x| x.is_waiting(now) == self.waiting);
         match self.sort_order {
-            SortOrder::CreationTimeASC => todos.sort_by(|a, b| a.get_when_created_in_localtime().cmp(&b.get_when_created_in_localtime())),
-            SortOrder::CreationTimeDESC => todos.sort_by(|a, b| b.get_when_created_in_localtime().cmp(&a.get_when_created_in_localtime())),
-            SortOrder::PriorityASC => todos.sort_by(|a, b| b.get_priority().cmp(&a.get_priority())),
+            SortOrder::CreationTimeASC => todos.sort_by_key(|a| a.get_when_created_in_localtime()),
+            SortOrder::CreationTimeDESC => todos.sort_by_key(|a| std::cmp::Reverse(a.get_when_created_in_localtime())),
+            SortOrder::PriorityASC => todos.sort_by_key(|a| std::cmp::Reverse(a.get_priority())),
             //default -> Sort be Priority DESC, then by creation time
             SortOrder::PriorityDESC => todos.sort_unstable_by_key(|item| (item.get_priority(), item.get_when_created_in_localtime()))          
         }
         //Sort todos by priority by default (Highest to lowest)
-        if todos.len() > 0 {
+        if !todos.is_empty() {
             match self.sort_order {
                 SortOrder::PriorityASC => self.print_todos(&todos, false),
                 SortOrder::PriorityDESC => self.print_todos(&todos, false),
@@ -143,9 +224,18 @@ impl ExecutableCommand for ListCommand {
                 SortOrder::CreationTimeDESC => self.print_todos(&todos, true)
             }
         }
+        else if self.archived {
+            println!("The archive is empty");
+        }
+        else if self.waiting {
+            println!("No todo is snoozed");
+        }
         else {
             println!("Your todo list is empty! :)");
         }
+        if !self.waiting && snoozed_count > 0 {
+            println!("{} snoozed todo(s) hidden, see list --waiting", snoozed_count);
+        }
         Ok(())
     }
 }
\ No newline at end of file
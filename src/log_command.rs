@@ -0,0 +1,85 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::{read_all_todos, write_todos};
+use crate::duration_format::{format_duration, parse_duration};
+use crate::output::{reset_color, success_color};
+use chrono::{Duration, Utc};
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct LogCommand {
+    id: u32,
+    duration: Duration
+}
+
+impl LogCommand {
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<LogCommand, Box<dyn Error>> {
+        let value = command_result.get_value().trim();
+        if value.is_empty() {
+            return Err("Value cannot be empty".into());
+        }
+        let (id, duration) = match value.split_once(char::is_whitespace) {
+            Some((id, duration)) => (id.parse::<u32>()?, parse_duration(duration)?),
+            None => return Err("The duration is required (ex: log 1 45m)".into())
+        };
+        Ok(LogCommand { id, duration })
+    }
+}
+
+impl ExecutableCommand for LogCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let mut todos = read_all_todos()?;
+        let now = Utc::now();
+        let mut iter = todos.iter_mut();
+        let total = match iter.find(|x| x.get_id() == self.id && !x.get_completed()) {
+            Some(todo) => {
+                todo.log_time(self.duration, now)?;
+                todo.get_time_spent(now)
+            },
+            None => return Err(format!("Unable to find the todo with id {}", self.id).into())
+        };
+        write_todos(&todos)?;
+        println!("{}{} logged on the todo {} (total {})!{}", success_color(), format_duration(&self.duration), self.id, format_duration(&total), reset_color());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::log_command::LogCommand;
+    use crate::common_structs::{Command, CommandResult};
+    use chrono::Duration;
+    use std::collections::HashMap;
+
+    #[test]
+    fn log_command_new_from_command_result_with_empty_value_return_error() {
+        let command = LogCommand::new_from_command_result(&CommandResult::new(Command::Log, 
+            "", 
+            HashMap::new()));
+        assert_eq!("Value cannot be empty", command.unwrap_err().to_string())
+    }
+
+    #[test]
+    fn log_command_new_from_command_result_with_id_only_return_error() {
+        let command = LogCommand::new_from_command_result(&CommandResult::new(Command::Log, 
+            "1", 
+            HashMap::new()));
+        assert_eq!("The duration is required (ex: log 1 45m)", command.unwrap_err().to_string())
+    }
+
+    #[test]
+    fn log_command_new_from_command_result_with_id_and_duration_return_success() {
+        let command = LogCommand::new_from_command_result(&CommandResult::new(Command::Log, 
+            "3 1h15m", 
+            HashMap::new())).unwrap();
+        assert_eq!(3, command.id);
+        assert_eq!(Duration::minutes(75), command.duration);
+    }
+
+    #[test]
+    fn log_command_new_from_command_result_with_invalid_duration_return_error() {
+        let command = LogCommand::new_from_command_result(&CommandResult::new(Command::Log, 
+            "3 abc", 
+            HashMap::new()));
+        assert_eq!("Invalid duration abc. Must be like 45m, 2h or 1h30m", command.unwrap_err().to_string())
+    }
+}
@@ -1,89 +1,159 @@
 mod args_analyzer;
 mod add_command;
+mod agenda_command;
+mod archive_command;
+mod calendar_command;
+mod cli_definition;
 mod common_structs;
+mod completions_command;
+mod config;
+mod config_command;
+mod csv_format;
 mod data_service;
 mod done_command;
+mod duration_format;
 mod edit_command;
+mod export_command;
 mod fetch_command;
+mod ics_format;
+mod import_command;
 mod list_command;
+mod log_command;
+mod markdown_format;
 mod next_command;
+mod org_format;
+mod output;
 mod purge_command;
 mod remove_command;
+mod report_command;
+mod shell_command;
+mod snooze_command;
+mod start_command;
+mod stats_command;
+mod stop_command;
+mod taskwarrior_format;
 mod todo;
+mod todo_editor;
+mod todo_format;
+mod todo_selection;
+mod todotxt_format;
+mod trash_command;
+mod tui_command;
+mod unarchive_command;
 
-use crate::common_structs::{Command, ExecutableCommand};
-use termion::{color, style};
+use crate::args_analyzer::{COLOR_OPTION, HELP_OPTION};
+use crate::cli_definition::find_command_definition;
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::output::{error_color, reset_error_color, ColorMode};
 use std::env;
 use std::error::Error;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COMMAND_FAILED: AtomicBool = AtomicBool::new(false);
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() == 1 || (args[1] == "-h" || args[1] == "--help") {
-        print_usage();
+    // Stop quietly when the output is piped to a command that exits early (beaver list | head)
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+    let args = match output::extract_color_option(env::args().skip(1).collect()) {
+        Ok((args, mode)) => {
+            if let Some(mode) = mode {
+                output::set_color_mode(mode);
+            }
+            args
+        },
+        Err(e) => {
+            print_command_error(e);
+            process::exit(1);
+        }
+    };
+    if args.is_empty() || (args[0] == "-h" || args[0] == "--help") {
+        print!("{}", cli_definition::get_usage());
         return;
     }
-    if args.len() >= 1 && args[1] == "-v" || args[1] == "--version" {
+    if args[0] == "-v" || args[0] == "--version" {
         print_version();
         return;
     }
-    let result_command = args_analyzer::analyze_args(args[1..].to_vec());
-    match result_command {
-        Some(result_command)=> 
-            match result_command.get_command() {
-                Command::Unknown => eprintln!("{}beaver: Unknown command{}", color::Fg(color::Red), color::Fg(color::Reset)),
-                Command::Add => {
-                    match add_command::AddCommand::new_from_command_result(&result_command) {
-                        Ok(command) => execute_command(command),
-                        Err(e) => print_command_error(e)
-                    }
-                },
-                Command::List => {
-                    match list_command::ListCommand::new_from_command_result(&result_command) {
-                        Ok(command) => execute_command(command),
-                        Err(e) => print_command_error(e)
-                    }
-                },
-                Command::Done => {
-                    match done_command::DoneCommand::new_from_command_result(&result_command) {
-                        Ok(command) => execute_command(command),
-                        Err(e) => print_command_error(e)
-                    }
-                },
-                Command::Fetch => {
-                    match fetch_command::FetchCommand::new_from_command_result(&result_command) {
-                        Ok(command) => execute_command(command),
-                        Err(e) => print_command_error(e)
-                    }
-                },
-                Command::Edit => {
-                    match edit_command::EditCommand::new_from_command_result(&result_command) {
-                        Ok(command) => execute_command(command),
-                        Err(e) => print_command_error(e)
-                    }
-                },
-                Command::Remove => {
-                    match remove_command::RemoveCommand::new_from_command_result(&result_command) {
-                        Ok(command) => execute_command(command),
-                        Err(e) => print_command_error(e)
-                    }
-                },
-                Command::Purge => {
-                    let command = purge_command::PurgeCommand {};
-                    execute_command(command);
-                },
-                Command::Next => {
-                    let command = next_command::NextCommand {};
-                    execute_command(command);
+    // An invalid config file can still be fixed with the config command
+    if let Err(e) = config::load_config() {
+        print_command_error(e);
+        if !args[0].trim().eq_ignore_ascii_case("config") {
+            process::exit(1);
+        }
+    }
+    run_args(args);
+    // Scripts and cron jobs can check that every command succeeded
+    if COMMAND_FAILED.load(Ordering::Relaxed) {
+        process::exit(1);
+    }
+}
+
+// Run the command given by the arguments following beaver, also used by the shell for each line
+fn run_args(args: Vec<String>) {
+    // A shell line can give its own --color, the other lines use the mode given to beaver
+    let (args, color_mode) = match output::extract_color_option(args) {
+        Ok(result) => result,
+        Err(e) => {
+            print_command_error(e);
+            return;
+        }
+    };
+    output::set_line_color_mode(color_mode);
+    let config = config::get_config();
+    let args = match args_analyzer::expand_aliases(args, config.get_aliases()) {
+        Ok(args) => args,
+        Err(e) => {
+            print_command_error(e);
+            return;
+        }
+    };
+    match args_analyzer::analyze_args(args) {
+        Ok(mut command_result) => {
+            match take_color_mode(&mut command_result, color_mode) {
+                Ok(mode) => output::set_line_color_mode(mode),
+                Err(e) => {
+                    print_command_error(e);
+                    return;
                 }
-                _ => eprintln!("{}Not implemented command{}", color::Fg(color::Red), color::Fg(color::Reset))
-            } 
+            }
+            config.apply_defaults(&mut command_result);
+            run_command(&command_result)
+        },
+        Err(e) => print_command_error(e)
+    }
+}
+
+// The mode of a --color given after the command name, or else the one given before it
+fn take_color_mode(command_result: &mut CommandResult, color_mode: Option<ColorMode>) -> Result<Option<ColorMode>, Box<dyn Error>> {
+    match (command_result.remove_option(COLOR_OPTION), color_mode) {
+        (Some(_), Some(_)) => Err("The option --color is given more than once".into()),
+        (Some(value), None) => Ok(Some(output::parse_color_option(&value)?)),
+        (None, color_mode) => Ok(color_mode)
+    }
+}
+
+fn run_command(command_result: &CommandResult) {
+    let definition = match find_command_definition(command_result.get_command()) {
+        Some(definition) => definition,
         None => {
-            eprintln!("{}beaver: no command provided{}", color::Fg(color::Red), color::Fg(color::Reset))
+            print_command_error("Not implemented command".into());
+            return;
         }
+    };
+    if command_result.get_options().contains_key(HELP_OPTION) {
+        print!("{}", definition.get_help());
+        return;
+    }
+    match definition.create_command(command_result) {
+        Ok(command) => execute_command(command),
+        Err(e) => print_command_error(e)
     }
 }
 
-fn execute_command(command: impl ExecutableCommand) {
+fn execute_command(command: Box<dyn ExecutableCommand>) {
     match command.execute() {
         Ok(_) => (),
         Err(e) => print_command_error(e)
@@ -91,7 +161,8 @@ fn execute_command(command: impl ExecutableCommand) {
 }
 
 fn print_command_error(error: Box<dyn Error>) {
-    eprintln!("{}beaver: {}{}", color::Fg(color::Red), error, color::Fg(color::Reset));
+    COMMAND_FAILED.store(true, Ordering::Relaxed);
+    eprintln!("{}beaver: {}{}", error_color(), error, reset_error_color());
 }
 
 fn print_version() {
@@ -99,44 +170,3 @@ fn print_version() {
     println!("Version 0.2.2");
     println!("Source : https://github.com/jeremydumais/BeaverTodos");
 }
-
-fn print_usage()
-{
-    println!("Beaver todos\n");
-    println!("USAGE:");
-    println!("beaver command [OPTIONS]\n");
-    println!("COMMANDS:");
-    println!("    -v, --version                  Print version info and exit");
-    println!("    -h, --help                     Prints help information");
-    println!("    list                           Display the todo list");
-    println!("    add                            Add a new todo");
-    println!("    edit                           Edit an existing todo");
-    println!("    done                           Complete a todo");
-    println!("    next                           Display the next todo to work on");
-    println!("    fetch                          Display the details of a specific todo");
-    println!("    remove                         Delete a todo");
-    println!("    purge                          Delete all completed todos");
-    println!("");
-    println!("USAGE BY COMMAND:");
-    println!("    {}add{} title [OPTIONS]", style::Underline, style::NoUnderline);
-    println!("        title                      The title (text) of the todo");
-    println!("        -p=x, --priority=x         The priority of the todo, possible values are H, M and L");
-    println!("                                   for High, Medium and Low");
-    println!("    {}edit{} id [OPTIONS]", style::Underline, style::NoUnderline);
-    println!("        -t=x, --title=x            The title (text) of the todo");
-    println!("        -p=x, --priority=x         The priority of the todo, possible values are H, M and L");
-    println!("                                   for High, Medium and Low");
-    println!("    {}list{} [OPTIONS]", style::Underline, style::NoUnderline);
-    println!("        -a, --all                  Display all todos even those who are completed");
-    println!("        -s=x, --sort=x             Sort the todo list by one of the following:");
-    println!("                                   {}prioritydesc:{} Sort by priority from High to Low (Default)", style::Underline, style::NoUnderline);
-    println!("                                   {}priority:{} Sort by priority from Low to High", style::Underline, style::NoUnderline);
-    println!("                                   {}creationtimedesc:{} Sort by creation time by more to less recent", style::Underline, style::NoUnderline);
-    println!("                                   {}creationtime:{} Sort by creation time by less to more recent", style::Underline, style::NoUnderline);
-    println!("    {}done{} id                        The id of the todo to complete", style::Underline, style::NoUnderline);
-    println!("    {}next{}                           <No argument required>", style::Underline, style::NoUnderline);
-    println!("    {}fetch{} id                       The id of the todo to display", style::Underline, style::NoUnderline);
-    println!("    {}remove{} id                      The id of the todo to delete", style::Underline, style::NoUnderline);
-    println!("    {}purge{}                          <No argument required>", style::Underline, style::NoUnderline);
-    println!("\n");
-}
\ No newline at end of file
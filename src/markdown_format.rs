@@ -0,0 +1,136 @@
+use crate::common_structs::Priority;
+use crate::todo::Todo;
+use crate::todo_format::{ImportResult, TodoFormat};
+use chrono::Utc;
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct MarkdownFormat {
+}
+
+impl MarkdownFormat {
+    // Return the priority of a heading like ## High or ### Medium priority
+    fn parse_heading(line: &str) -> Option<Priority> {
+        let text = line.trim_start_matches('#').trim().to_lowercase();
+        let text = text.strip_suffix("priority").unwrap_or(&text).trim();
+        match text {
+            "high" => Some(Priority::High),
+            "medium" => Some(Priority::Medium),
+            "low" => Some(Priority::Low),
+            _ => None
+        }
+    }
+
+    // Return the completion and the title of a checklist item like - [ ] title or * [x] title
+    fn parse_item(line: &str) -> Option<(bool, &str)> {
+        let line = line.trim_start().strip_prefix(['-', '*', '+'])?.trim_start();
+        let completed = match line.get(..3)? {
+            "[ ]" => false,
+            "[x]" | "[X]" => true,
+            _ => return None
+        };
+        Some((completed, line[3..].trim()))
+    }
+}
+
+impl TodoFormat for MarkdownFormat {
+    fn export(&self, todos: &[Todo]) -> Result<String, Box<dyn Error>> {
+        let mut sections = vec![];
+        for priority in [Priority::High, Priority::Medium, Priority::Low] {
+            let items: Vec<String> = todos.iter()
+                .filter(|x| x.get_priority() == priority)
+                .map(|x| format!("- [{}] {}\n", if x.get_completed() { "x" } else { " " }, x.get_title()))
+                .collect();
+            if !items.is_empty() {
+                sections.push(format!("## {}\n\n{}", priority, items.concat()));
+            }
+        }
+        Ok(sections.join("\n"))
+    }
+
+    fn import(&self, content: &str) -> Result<ImportResult, Box<dyn Error>> {
+        let mut result = ImportResult::new();
+        let mut priority = Priority::Low;
+        for (index, line) in content.lines().enumerate() {
+            if line.starts_with('#') {
+                // Items under a heading that isn't a priority are imported as Low
+                priority = MarkdownFormat::parse_heading(line).unwrap_or(Priority::Low);
+                continue;
+            }
+            let (completed, title) = match MarkdownFormat::parse_item(line) {
+                Some(item) => item,
+                None => continue
+            };
+            match Todo::new(0, title, priority, Utc::now()) {
+                Ok(mut todo) => {
+                    if completed {
+                        todo.set_completed(true, None);
+                    }
+                    result.add_todo(todo);
+                },
+                Err(_) => result.add_issue(index + 1, "The item has no title and has been skipped", line)
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::markdown_format::MarkdownFormat;
+    use crate::todo::Todo;
+    use crate::todo_format::TodoFormat;
+    use chrono::Utc;
+
+    #[test]
+    fn export_return_checklists_grouped_by_priority() {
+        let mut completed = Todo::new(2, "Pay the bills", Priority::Low, Utc::now()).unwrap();
+        completed.set_completed(true, None);
+        let todos = vec![Todo::new(1, "Write the report", Priority::Low, Utc::now()).unwrap(),
+                         completed,
+                         Todo::new(3, "Call mom", Priority::High, Utc::now()).unwrap()];
+        assert_eq!("## High\n\n- [ ] Call mom\n\n## Low\n\n- [ ] Write the report\n- [x] Pay the bills\n",
+                   MarkdownFormat {}.export(&todos).unwrap());
+    }
+
+    #[test]
+    fn import_with_checklists_return_todos_with_heading_priority() {
+        let content = "# Meeting notes\n\
+                       - [ ] Book a room\n\
+                       Some text\n\
+                       - Not a task\n\
+                       ### Medium priority\n\
+                       * [X] Send the agenda\n  \
+                         + [ ] Review the slides\n\
+                       ## High\n\
+                       - [ ] Call mom\n";
+        let result = MarkdownFormat {}.import(content).unwrap();
+        let todos = result.get_todos();
+        assert_eq!(4, todos.len());
+        assert_eq!(("Book a room", Priority::Low, false), (todos[0].get_title(), todos[0].get_priority(), todos[0].get_completed()));
+        assert_eq!(("Send the agenda", Priority::Medium, true), (todos[1].get_title(), todos[1].get_priority(), todos[1].get_completed()));
+        assert_eq!(("Review the slides", Priority::Medium, false), (todos[2].get_title(), todos[2].get_priority(), todos[2].get_completed()));
+        assert_eq!(("Call mom", Priority::High, false), (todos[3].get_title(), todos[3].get_priority(), todos[3].get_completed()));
+        assert!(result.get_issues().is_empty());
+    }
+
+    #[test]
+    fn import_with_empty_item_skip_line_and_report_issue() {
+        let result = MarkdownFormat {}.import("- [ ] Buy milk\n- [ ]  \n").unwrap();
+        assert_eq!(1, result.get_todos().len());
+        assert_eq!(vec!["Line 2: The item has no title and has been skipped (- [ ])"], *result.get_issues());
+    }
+
+    #[test]
+    fn export_then_import_return_same_todos() {
+        let todos = vec![Todo::new(1, "Call mom", Priority::High, Utc::now()).unwrap(),
+                         Todo::new(2, "Write the report", Priority::Medium, Utc::now()).unwrap()];
+        let content = MarkdownFormat {}.export(&todos).unwrap();
+        let result = MarkdownFormat {}.import(&content).unwrap();
+        for (expected, actual) in todos.iter().zip(result.get_todos()) {
+            assert_eq!(expected.get_title(), actual.get_title());
+            assert_eq!(expected.get_priority(), actual.get_priority());
+        }
+    }
+}
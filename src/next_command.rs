@@ -1,6 +1,7 @@
 use crate::common_structs::ExecutableCommand;
 use crate::data_service::read_all_todos;
-use termion::style;
+use crate::output::{bold, reset_style};
+use chrono::Utc;
 use std::error::Error;
 
 #[derive(Debug)]
@@ -10,13 +11,14 @@ pub struct NextCommand {
 impl ExecutableCommand for NextCommand {
     fn execute(&self) -> Result<(), Box<dyn Error>> {
         let mut todos = read_all_todos()?;
-        todos.retain(|x| !x.get_completed());
+        let now = Utc::now();
+        todos.retain(|x| !x.get_completed() && !x.is_waiting(now));
         if !todos.is_empty() {
             todos.sort_unstable_by_key(|item| (item.get_priority(), item.get_when_created_in_localtime()));
             let todo = &todos[0]; 
-            println!("Title: {}{}{}", style::Bold, todo.get_title(), style::Reset);
+            println!("Title: {}{}{}", bold(), todo.get_title(), reset_style());
             println!("ID: {}", todo.get_id());
-            println!("Priority: {}", todo.get_priority().to_string());
+            println!("Priority: {}", todo.get_priority());
             println!("Created on: {}", todo.get_when_created_in_localtime());
         }
         else {
@@ -0,0 +1,275 @@
+use crate::common_structs::Priority;
+use crate::todo::Todo;
+use crate::todo_format::{ImportResult, TodoFormat};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct OrgFormat {
+}
+
+impl OrgFormat {
+    fn priority_to_letter(priority: Priority) -> char {
+        match priority {
+            Priority::High => 'A',
+            Priority::Medium => 'B',
+            Priority::Low => 'C'
+        }
+    }
+
+    // Inactive timestamp like [2026-10-01 Thu 09:30]
+    fn format_timestamp(date: DateTime<Local>) -> String {
+        date.format("[%Y-%m-%d %a %H:%M]").to_string()
+    }
+
+    // Parse an active or inactive timestamp, with or without the time
+    fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+        let value = value.trim().trim_start_matches(['[', '<']).trim_end_matches([']', '>']);
+        let mut parts = value.split_whitespace();
+        let date = NaiveDate::parse_from_str(parts.next()?, "%Y-%m-%d").ok()?;
+        let time = parts.find_map(|x| NaiveTime::parse_from_str(x, "%H:%M").ok()).unwrap_or(NaiveTime::MIN);
+        Local.from_local_datetime(&date.and_time(time)).earliest().map(|x| x.with_timezone(&Utc))
+    }
+
+    // Return the timestamp following a keyword of a planning line (CLOSED: [...] DEADLINE: <...>)
+    fn find_planning_timestamp(line: &str, keyword: &str) -> Option<DateTime<Utc>> {
+        let start = line.find(keyword)? + keyword.len();
+        let rest = line[start..].trim_start();
+        let closing = match rest.chars().next()? {
+            '[' => ']',
+            '<' => '>',
+            _ => return None
+        };
+        let end = rest.find(closing)?;
+        OrgFormat::parse_timestamp(&rest[..=end])
+    }
+
+    // Return the completion, the priority letter, the title and the tags of a TODO or DONE headline
+    fn parse_headline(line: &str) -> Option<(bool, Option<char>, String, Vec<String>)> {
+        let rest = line.trim_start_matches('*');
+        if rest.len() == line.len() || !rest.starts_with(' ') {
+            return None;
+        }
+        let mut words: Vec<&str> = rest.split_whitespace().collect();
+        let completed = match words.first() {
+            Some(&"TODO") => false,
+            Some(&"DONE") => true,
+            _ => return None
+        };
+        words.remove(0);
+        let priority_letter = match words.first() {
+            Some(word) if word.len() == 4 && word.starts_with("[#") && word.ends_with(']') => {
+                let letter = word.chars().nth(2);
+                words.remove(0);
+                letter
+            },
+            _ => None
+        };
+        let mut tags = vec![];
+        if let Some(last) = words.last() {
+            if last.len() > 2 && last.starts_with(':') && last.ends_with(':') {
+                tags = last.trim_matches(':').split(':').filter(|x| !x.is_empty()).map(|x| x.to_string()).collect();
+                words.pop();
+            }
+        }
+        Some((completed, priority_letter, words.join(" "), tags))
+    }
+
+    fn add_todo(todo: Option<(usize, String, Todo)>, result: &mut ImportResult) {
+        if let Some((_, _, todo)) = todo {
+            result.add_todo(todo);
+        }
+    }
+}
+
+impl TodoFormat for OrgFormat {
+    fn export(&self, todos: &[Todo]) -> Result<String, Box<dyn Error>> {
+        let mut content = String::new();
+        for todo in todos {
+            let keyword = if todo.get_completed() { "DONE" } else { "TODO" };
+            let mut headline = format!("* {} [#{}] {}", keyword, OrgFormat::priority_to_letter(todo.get_priority()), todo.get_title());
+            if !todo.get_tags().is_empty() {
+                headline.push_str(&format!(" :{}:", todo.get_tags().join(":")));
+            }
+            content.push_str(&headline);
+            content.push('\n');
+            let mut planning = vec![];
+            if todo.get_completed() {
+                planning.push(format!("CLOSED: {}", OrgFormat::format_timestamp(todo.get_when_completed_in_localtime())));
+            }
+            if let Some(due) = todo.get_due_in_localtime() {
+                planning.push(format!("DEADLINE: {}", due.format("<%Y-%m-%d %a>")));
+            }
+            if !planning.is_empty() {
+                content.push_str(&format!("  {}\n", planning.join(" ")));
+            }
+            content.push_str("  :PROPERTIES:\n");
+            content.push_str(&format!("  :ID:       {}\n", todo.get_uuid()));
+            content.push_str(&format!("  :CREATED:  {}\n", OrgFormat::format_timestamp(todo.get_when_created_in_localtime())));
+            content.push_str("  :END:\n");
+        }
+        Ok(content)
+    }
+
+    // Read the TODO and DONE headlines with their planning line and their properties drawer.
+    // The other headlines and the text are ignored.
+    fn import(&self, content: &str) -> Result<ImportResult, Box<dyn Error>> {
+        let mut result = ImportResult::new();
+        // The todo being read, with the number and the content of its headline
+        let mut current: Option<(usize, String, Todo)> = None;
+        let mut in_drawer = false;
+        for (index, line) in content.lines().enumerate() {
+            if line.starts_with('*') {
+                OrgFormat::add_todo(current.take(), &mut result);
+                in_drawer = false;
+                let (completed, priority_letter, title, tags) = match OrgFormat::parse_headline(line) {
+                    Some(headline) => headline,
+                    None => continue
+                };
+                // Org mode considers headlines without priority as B
+                let priority = match priority_letter {
+                    Some('A') => Priority::High,
+                    Some('B') | None => Priority::Medium,
+                    Some('C') => Priority::Low,
+                    Some(letter) => {
+                        result.add_issue(index + 1, &format!("The priority {} has no equivalent and has been imported as Low", letter), line);
+                        Priority::Low
+                    }
+                };
+                match Todo::new(0, &title, priority, Utc::now()) {
+                    Ok(mut todo) => {
+                        todo.set_tags(tags);
+                        if completed {
+                            todo.set_completed(true, None);
+                        }
+                        current = Some((index + 1, line.to_string(), todo));
+                    },
+                    Err(_) => result.add_issue(index + 1, "The headline has no title and has been skipped", line)
+                }
+                continue;
+            }
+            let (line_number, headline, todo) = match current.as_mut() {
+                Some(current) => current,
+                None => continue
+            };
+            let trimmed_line = line.trim();
+            match trimmed_line {
+                ":PROPERTIES:" => in_drawer = true,
+                ":END:" => in_drawer = false,
+                _ if in_drawer => {
+                    let (name, value) = match trimmed_line.trim_start_matches(':').split_once(':') {
+                        Some((name, value)) => (name.to_uppercase(), value.trim()),
+                        None => continue
+                    };
+                    match name.as_str() {
+                        "ID" if todo.set_uuid(value).is_err() => {
+                            result.add_issue(*line_number, &format!("The ID {} isn't a uuid, importing the file again will duplicate the todo", value), headline);
+                        },
+                        "CREATED" => if let Some(date) = OrgFormat::parse_timestamp(value) {
+                            todo.set_when_created(date);
+                        },
+                        _ => ()
+                    }
+                },
+                _ => {
+                    if let Some(date) = OrgFormat::find_planning_timestamp(trimmed_line, "CLOSED:") {
+                        if todo.get_completed() {
+                            todo.set_completed(true, Some(date));
+                        }
+                    }
+                    if let Some(date) = OrgFormat::find_planning_timestamp(trimmed_line, "DEADLINE:") {
+                        todo.set_due(Some(date));
+                    }
+                }
+            }
+        }
+        OrgFormat::add_todo(current, &mut result);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::org_format::OrgFormat;
+    use crate::todo::Todo;
+    use crate::todo_format::TodoFormat;
+    use chrono::{Local, TimeZone, Utc};
+
+    const UUID: &str = "5c2a7a4e-8d1c-4b8e-9f3e-2d6f1a0b9c11";
+
+    fn local_date(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> chrono::DateTime<Utc> {
+        Local.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn export_return_org_headlines() {
+        let mut todo = Todo::new(1, "Pay the bills", Priority::Medium, local_date(2026, 10, 1, 9, 30)).unwrap();
+        todo.set_uuid(UUID).unwrap();
+        todo.set_tags(vec![String::from("home"), String::from("money")]);
+        todo.set_completed(true, Some(local_date(2026, 10, 3, 18, 0)));
+        let open = Todo::new(2, "Call mom", Priority::High, local_date(2026, 10, 1, 9, 30)).unwrap();
+        let content = OrgFormat {}.export(&[todo, open.clone()]).unwrap();
+        assert_eq!(format!("* DONE [#B] Pay the bills :home:money:\n  CLOSED: [2026-10-03 Sat 18:00]\n\
+                            \x20 :PROPERTIES:\n  :ID:       {}\n  :CREATED:  [2026-10-01 Thu 09:30]\n  :END:\n\
+                            * TODO [#A] Call mom\n  :PROPERTIES:\n  :ID:       {}\n  :CREATED:  [2026-10-01 Thu 09:30]\n  :END:\n",
+                           UUID, open.get_uuid()),
+                   content);
+    }
+
+    #[test]
+    fn import_with_org_file_return_todos() {
+        let content = format!("#+TITLE: Tasks\n\
+                               * Work\n\
+                               ** TODO [#A] Call mom :family:\n   \
+                                  DEADLINE: <2026-10-20 Tue>\n   \
+                                  Some notes\n\
+                               ** DONE Pay the bills\n   \
+                                  CLOSED: [2026-10-03 Sat 18:00]\n   \
+                                  :PROPERTIES:\n   \
+                                  :ID: {}\n   \
+                                  :CREATED: [2026-10-01 Thu 09:30]\n   \
+                                  :END:\n\
+                               ** Not a todo\n", UUID);
+        let result = OrgFormat {}.import(&content).unwrap();
+        assert_eq!(2, result.get_todos().len());
+        let todo = &result.get_todos()[0];
+        assert_eq!("Call mom", todo.get_title());
+        assert_eq!(Priority::High, todo.get_priority());
+        assert_eq!(vec!["family"], *todo.get_tags());
+        assert_eq!(local_date(2026, 10, 20, 0, 0), todo.get_due_in_localtime().unwrap());
+        assert!(!todo.get_completed());
+        let todo = &result.get_todos()[1];
+        assert_eq!("Pay the bills", todo.get_title());
+        assert_eq!(Priority::Medium, todo.get_priority());
+        assert_eq!(UUID, todo.get_uuid());
+        assert_eq!(local_date(2026, 10, 1, 9, 30), todo.get_when_created_in_localtime());
+        assert!(todo.get_completed());
+        assert_eq!(local_date(2026, 10, 3, 18, 0), todo.get_when_completed_in_localtime());
+        assert!(result.get_issues().is_empty());
+    }
+
+    #[test]
+    fn import_with_invalid_headlines_return_issues() {
+        let content = "* TODO [#D] Someday\n* TODO\n* DONE Old\n  :PROPERTIES:\n  :ID: old-1\n  :END:\n";
+        let result = OrgFormat {}.import(content).unwrap();
+        assert_eq!(2, result.get_todos().len());
+        assert_eq!(Priority::Low, result.get_todos()[0].get_priority());
+        assert_eq!(vec!["Line 1: The priority D has no equivalent and has been imported as Low (* TODO [#D] Someday)",
+                        "Line 2: The headline has no title and has been skipped (* TODO)",
+                        "Line 3: The ID old-1 isn't a uuid, importing the file again will duplicate the todo (* DONE Old)"],
+                   *result.get_issues());
+    }
+
+    #[test]
+    fn export_then_import_return_same_todos() {
+        let todos = vec![Todo::new(1, "Call mom", Priority::Low, local_date(2026, 10, 1, 9, 30)).unwrap()];
+        let content = OrgFormat {}.export(&todos).unwrap();
+        let result = OrgFormat {}.import(&content).unwrap();
+        let todo = &result.get_todos()[0];
+        assert_eq!(todos[0].get_title(), todo.get_title());
+        assert_eq!(todos[0].get_priority(), todo.get_priority());
+        assert_eq!(todos[0].get_uuid(), todo.get_uuid());
+        assert_eq!(todos[0].get_when_created_in_localtime(), todo.get_when_created_in_localtime());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::common_structs::Priority;
+use crate::config::get_config;
+use termion::{color, style};
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::sync::OnceLock;
+
+static COLOR_MODE: OnceLock<ColorMode> = OnceLock::new();
+static STDOUT_COLORS: OnceLock<bool> = OnceLock::new();
+static STDERR_COLORS: OnceLock<bool> = OnceLock::new();
+
+thread_local! {
+    // The --color option of the current shell line, it wins over the one given before the command
+    static LINE_COLOR_MODE: Cell<Option<ColorMode>> = const { Cell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    Auto,
+    Always,
+    Never
+}
+
+impl ColorMode {
+    pub fn from_name(name: &str) -> Result<ColorMode, Box<dyn Error>> {
+        match name.trim().to_lowercase().as_str() {
+            "auto" => Ok(ColorMode::Auto),
+            "always" => Ok(ColorMode::Always),
+            "never" => Ok(ColorMode::Never),
+            _ => Err("Invalid color value. Must be auto, always or never".into())
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Stream {
+    Stdout,
+    Stderr
+}
+
+// An escape sequence written only when the colors are enabled on its stream
+pub struct Style(String);
+
+impl Style {
+    fn new(stream: Stream, escape: impl fmt::Display) -> Style {
+        match colors_enabled(stream) {
+            true => Style(escape.to_string()),
+            false => Style(String::new())
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// Set the mode given by --color, must be called before anything is printed
+pub fn set_color_mode(mode: ColorMode) {
+    let _ = COLOR_MODE.set(mode);
+}
+
+// Set the color mode of a single command, None goes back to the mode given to beaver
+pub fn set_line_color_mode(mode: Option<ColorMode>) {
+    LINE_COLOR_MODE.with(|x| x.set(mode));
+}
+
+// Decide if the colors are used, NO_COLOR wins over CLICOLOR_FORCE and both are ignored with --color=always|never
+pub fn should_use_colors(mode: ColorMode, no_color: Option<String>, clicolor_force: Option<String>, is_tty: bool) -> bool {
+    let is_set = |value: &Option<String>| value.as_deref().map(|x| !x.is_empty()).unwrap_or(false);
+    match mode {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto if is_set(&no_color) => false,
+        ColorMode::Auto if is_set(&clicolor_force) && clicolor_force.as_deref() != Some("0") => true,
+        ColorMode::Auto => is_tty
+    }
+}
+
+fn colors_enabled(stream: Stream) -> bool {
+    let (cache, is_tty) = match stream {
+        Stream::Stdout => (&STDOUT_COLORS, termion::is_tty(&io::stdout())),
+        Stream::Stderr => (&STDERR_COLORS, termion::is_tty(&io::stderr()))
+    };
+    if let Some(mode) = LINE_COLOR_MODE.with(|x| x.get()) {
+        return should_use_colors(mode, std::env::var("NO_COLOR").ok(), std::env::var("CLICOLOR_FORCE").ok(), is_tty);
+    }
+    *cache.get_or_init(|| {
+        let mode = *COLOR_MODE.get().unwrap_or(&ColorMode::Auto);
+        should_use_colors(mode, std::env::var("NO_COLOR").ok(), std::env::var("CLICOLOR_FORCE").ok(), is_tty)
+    })
+}
+
+// Remove --color=x (or --color meaning always) given before the command name (beaver --color=never list).
+// After the command name, --color is read by analyze_args like the other options.
+pub fn extract_color_option(args: Vec<String>) -> Result<(Vec<String>, Option<ColorMode>), Box<dyn Error>> {
+    let mut mode = None;
+    let mut remaining_args = args.into_iter().peekable();
+    while let Some(arg) = remaining_args.next_if(|x| x == "--color" || x.starts_with("--color=")) {
+        if mode.is_some() {
+            return Err("The option --color is given more than once".into());
+        }
+        mode = Some(parse_color_option(arg.strip_prefix("--color=").unwrap_or(""))?);
+    }
+    Ok((remaining_args.collect(), mode))
+}
+
+// Return the mode of a --color value, --color alone means always
+pub fn parse_color_option(value: &str) -> Result<ColorMode, Box<dyn Error>> {
+    match value.trim() {
+        "" => Ok(ColorMode::Always),
+        value => ColorMode::from_name(value)
+    }
+}
+
+pub fn success_color() -> Style {
+    Style::new(Stream::Stdout, color::Fg(color::AnsiValue(get_config().get_color("success").unwrap_or(2))))
+}
+
+pub fn warning_color() -> Style {
+    Style::new(Stream::Stderr, color::Fg(color::AnsiValue(get_config().get_color("warning").unwrap_or(3))))
+}
+
+pub fn error_color() -> Style {
+    Style::new(Stream::Stderr, color::Fg(color::AnsiValue(get_config().get_color("error").unwrap_or(1))))
+}
+
+pub fn reset_color() -> Style {
+    Style::new(Stream::Stdout, color::Fg(color::Reset))
+}
+
+pub fn reset_error_color() -> Style {
+    Style::new(Stream::Stderr, color::Fg(color::Reset))
+}
+
+// The style of a todo line, bold for the high priority and the color of the priority set in the config
+pub fn priority_style(priority: Priority) -> Style {
+    let (key, bold) = match priority {
+        Priority::High => ("high", true),
+        Priority::Medium => ("medium", false),
+        Priority::Low => ("low", false)
+    };
+    let mut escape = String::new();
+    if bold {
+        escape.push_str(style::Bold.as_ref());
+    }
+    if let Some(value) = get_config().get_color(key) {
+        escape.push_str(&color::Fg(color::AnsiValue(value)).to_string());
+    }
+    Style::new(Stream::Stdout, escape)
+}
+
+pub fn bold() -> Style {
+    Style::new(Stream::Stdout, style::Bold)
+}
+
+pub fn underline() -> Style {
+    Style::new(Stream::Stdout, style::Underline)
+}
+
+pub fn no_underline() -> Style {
+    Style::new(Stream::Stdout, style::NoUnderline)
+}
+
+pub fn reset_style() -> Style {
+    Style::new(Stream::Stdout, style::Reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{colors_enabled, extract_color_option, set_line_color_mode, should_use_colors, ColorMode, Stream};
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn colors_enabled_with_line_color_mode_return_mode() {
+        set_line_color_mode(Some(ColorMode::Always));
+        assert!(colors_enabled(Stream::Stdout));
+        set_line_color_mode(Some(ColorMode::Never));
+        assert!(!colors_enabled(Stream::Stderr));
+        set_line_color_mode(None);
+    }
+
+    #[test]
+    fn should_use_colors_with_environment_return_expected() {
+        let set = || Some(String::from("1"));
+        assert!(should_use_colors(ColorMode::Auto, None, None, true));
+        assert!(!should_use_colors(ColorMode::Auto, None, None, false));
+        assert!(!should_use_colors(ColorMode::Auto, set(), None, true));
+        assert!(should_use_colors(ColorMode::Auto, Some(String::new()), None, true));
+        assert!(should_use_colors(ColorMode::Auto, None, set(), false));
+        assert!(!should_use_colors(ColorMode::Auto, None, Some(String::from("0")), false));
+        assert!(!should_use_colors(ColorMode::Auto, set(), set(), false));
+        assert!(should_use_colors(ColorMode::Always, set(), None, false));
+        assert!(!should_use_colors(ColorMode::Never, None, set(), true));
+    }
+
+    #[test]
+    fn extract_color_option_remove_option_from_args() {
+        let (args, mode) = extract_color_option(to_args(&["--color=never", "list", "-a"])).unwrap();
+        assert_eq!(to_args(&["list", "-a"]), args);
+        assert_eq!(Some(ColorMode::Never), mode);
+        let (args, mode) = extract_color_option(to_args(&["--color", "add", "--", "--color=never"])).unwrap();
+        assert_eq!(to_args(&["add", "--", "--color=never"]), args);
+        assert_eq!(Some(ColorMode::Always), mode);
+        assert_eq!(None, extract_color_option(to_args(&["list"])).unwrap().1);
+    }
+
+    #[test]
+    fn extract_color_option_keep_option_after_command_name() {
+        let (args, mode) = extract_color_option(to_args(&["add", "Fix", "the", "--color", "flag"])).unwrap();
+        assert_eq!(to_args(&["add", "Fix", "the", "--color", "flag"]), args);
+        assert_eq!(None, mode);
+    }
+
+    #[test]
+    fn extract_color_option_with_invalid_value_return_error() {
+        assert_eq!("Invalid color value. Must be auto, always or never",
+                   extract_color_option(to_args(&["--color=yes", "list"])).err().unwrap().to_string());
+        assert_eq!("The option --color is given more than once",
+                   extract_color_option(to_args(&["--color", "--color=never", "list"])).err().unwrap().to_string());
+    }
+}
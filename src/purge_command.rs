@@ -1,28 +1,34 @@
-use crate::common_structs::ExecutableCommand;
+use crate::common_structs::{CommandResult, ExecutableCommand};
 use crate::data_service::{read_all_todos, write_todos};
-use question::{Question, Answer};
-use termion::color;
+use crate::output::{reset_color, success_color};
+use crate::todo_selection::{confirm_deletion, ConfirmMode};
 use std::error::Error;
 
 #[derive(Debug)]
 pub struct PurgeCommand {
+    confirm_mode: ConfirmMode
+}
+
+impl PurgeCommand {
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<PurgeCommand, Box<dyn Error>> {
+        let confirm_mode = ConfirmMode::new_from_command_result(command_result)?;
+        Ok(PurgeCommand { confirm_mode })
+    }
 }
 
 impl ExecutableCommand for PurgeCommand {
     fn execute(&self) -> Result<(), Box<dyn Error>> {
         let mut todos = read_all_todos()?;
-        let before_count = todos.len();
-        todos.retain(|x| !x.get_completed());
-        let after_count = todos.len();
-        let todos_count_to_remove = before_count - after_count;
-        let answer = Question::new(format!("Are you sure you want to delete {} completed todos? (y/n)", todos_count_to_remove).as_str())
-                                   .yes_no()
-                                   .until_acceptable()
-                                   .ask();
-        if answer.unwrap_or(Answer::NO) == Answer::YES {
+        let indexes: Vec<usize> = (0..todos.len()).filter(|x| todos[*x].get_completed()).collect();
+        if indexes.is_empty() {
+            println!("There is no completed todo to purge");
+            return Ok(());
+        }
+        if confirm_deletion(&todos, &indexes, self.confirm_mode)? {
+            todos.retain(|x| !x.get_completed());
             write_todos(&todos)?;
-            println!("{}The purge has removed {} completed todo(s)!{}", color::Fg(color::Green), todos_count_to_remove, color::Fg(color::Reset));
+            println!("{}The purge has removed {} completed todo(s)!{}", success_color(), indexes.len(), reset_color());
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,47 +1,41 @@
 use crate::common_structs::{CommandResult, ExecutableCommand};
-use crate::data_service::{read_all_todos, write_todos};
-use question::{Question, Answer};
-use termion::color;
+use crate::data_service::{move_to_trash, read_all_todos, write_todos};
+use crate::output::{reset_color, success_color};
+use crate::todo::Todo;
+use crate::todo_selection::{confirm_deletion, ConfirmMode, TodoSelection};
 use std::error::Error;
 
 #[derive(Debug)]
 pub struct RemoveCommand {
-    id: u32
+    selection: TodoSelection,
+    confirm_mode: ConfirmMode
 }
 
 impl RemoveCommand {
     pub fn new_from_command_result(command_result : &CommandResult) -> Result<RemoveCommand, Box<dyn Error>> {
-        let value = command_result.get_value().trim();
-        if value.is_empty() {
-            return Err("Value cannot be empty".into());
-        }
-        let id = value.parse::<u32>()?;
-        Ok(RemoveCommand { id: id })
+        let selection = TodoSelection::new_from_command_result(command_result)?;
+        let confirm_mode = ConfirmMode::new_from_command_result(command_result)?;
+        Ok(RemoveCommand { selection, confirm_mode })
     }
 }
 
 impl ExecutableCommand for RemoveCommand {
     fn execute(&self) -> Result<(), Box<dyn Error>> {
         let mut todos = read_all_todos()?;
-        //Find the todo to remove
-        let mut todo_index = 0;
-        for (i, todo) in todos.iter().enumerate() {
-            if todo.get_id() == self.id && !todo.get_completed() {
-                todo_index = i;
-                break;
+        //Find the todos to remove
+        let indexes = self.selection.find_indexes(&todos)?;
+        if confirm_deletion(&todos, &indexes, self.confirm_mode)? {
+            let removed_todos: Vec<Todo> = indexes.iter().map(|i| todos[*i].clone()).collect();
+            let mut sorted_indexes = indexes.clone();
+            sorted_indexes.sort_unstable_by(|a, b| b.cmp(a));
+            for index in sorted_indexes {
+                todos.remove(index);
             }
-        }
-        if todo_index == 0 {
-            return Err(format!("Unable to find the todo with id {}", self.id).into());
-        }
-        let answer = Question::new(format!("Are you sure you want to delete the todo with id {0}? (y/n)", self.id).as_str())
-                                   .yes_no()
-                                   .until_acceptable()
-                                   .ask();
-        if answer.unwrap_or(Answer::NO) == Answer::YES {
-            todos.remove(todo_index);
+            move_to_trash(&removed_todos)?;
             write_todos(&todos)?;
-            println!("{}The todo with id {} has been removed!{}", color::Fg(color::Green), self.id, color::Fg(color::Reset));
+            for todo in removed_todos {
+                println!("{}The todo with id {} has been moved to the trash!{}", success_color(), todo.get_id(), reset_color());
+            }
         }
         Ok(())
     }
@@ -50,6 +44,8 @@ impl ExecutableCommand for RemoveCommand {
 #[cfg(test)]
 mod tests {
     use crate::remove_command::RemoveCommand;
+    use crate::todo_selection::{ConfirmMode, Criterion, TodoSelection};
+    use crate::common_structs::Priority;
     use crate::common_structs::{Command, CommandResult};
     use std::collections::HashMap;
 
@@ -74,7 +70,7 @@ mod tests {
         let command = RemoveCommand::new_from_command_result(&CommandResult::new(Command::Remove, 
             "1", 
             HashMap::new()));
-        assert_eq!(1, command.unwrap().id);
+        assert_eq!(TodoSelection::Ids(vec![1]), command.unwrap().selection);
     }
 
     #[test]
@@ -82,7 +78,7 @@ mod tests {
         let command = RemoveCommand::new_from_command_result(&CommandResult::new(Command::Remove, 
             "100", 
             HashMap::new()));
-        assert_eq!(100, command.unwrap().id);
+        assert_eq!(TodoSelection::Ids(vec![100]), command.unwrap().selection);
     }
 
     #[test]
@@ -100,4 +96,24 @@ mod tests {
             HashMap::new()));
         assert_eq!("invalid digit found in string", command.unwrap_err().to_string());
     }
+
+    #[test]
+    fn remove_command_new_from_command_result_with_where_return_success() {
+        let command = RemoveCommand::new_from_command_result(&CommandResult::new(Command::Remove, 
+            "", 
+            HashMap::from([(String::from("where"), String::from("priority=L"))])));
+        assert_eq!(TodoSelection::Criteria(vec![Criterion::Priority(Priority::Low)]), command.unwrap().selection);
+    }
+
+    #[test]
+    fn remove_command_new_from_command_result_with_yes_and_dry_run_return_confirm_mode() {
+        let command = RemoveCommand::new_from_command_result(&CommandResult::new(Command::Remove, 
+            "1", 
+            HashMap::from([(String::from("yes"), String::new())])));
+        assert_eq!(ConfirmMode::Yes, command.unwrap().confirm_mode);
+        let command = RemoveCommand::new_from_command_result(&CommandResult::new(Command::Remove, 
+            "1", 
+            HashMap::from([(String::from("yes"), String::new()), (String::from("dry-run"), String::new())])));
+        assert_eq!("The options --yes and --dry-run cannot be used together", command.unwrap_err().to_string());
+    }
 }
\ No newline at end of file
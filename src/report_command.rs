@@ -0,0 +1,207 @@
+use crate::common_structs::{CommandResult, ExecutableCommand, Priority};
+use crate::data_service::read_todos_with_archive;
+use crate::duration_format::format_duration;
+use crate::output::{bold, reset_style, underline};
+use crate::todo::Todo;
+use chrono::{Datelike, Duration};
+use std::error::Error;
+
+#[derive(Debug, PartialEq)]
+pub enum ReportType {
+    Estimates
+}
+
+#[derive(Debug)]
+pub struct ReportCommand {
+    report_type: ReportType
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EstimateRow {
+    label: String,
+    count: usize,
+    estimated: Duration,
+    actual: Duration
+}
+
+impl EstimateRow {
+    fn new(label: &str) -> EstimateRow {
+        EstimateRow { label: label.to_string(), count: 0, estimated: Duration::zero(), actual: Duration::zero() }
+    }
+
+    fn add(&mut self, estimated: Duration, actual: Duration) {
+        self.count += 1;
+        self.estimated += estimated;
+        self.actual += actual;
+    }
+
+    // Return the actual duration in percentage of the estimated duration
+    pub fn get_accuracy(&self) -> i64 {
+        if self.estimated.num_minutes() == 0 {
+            return 0;
+        }
+        self.actual.num_minutes() * 100 / self.estimated.num_minutes()
+    }
+}
+
+impl ReportCommand {
+    const LABEL_WIDTH: usize = 10;
+    const COUNT_WIDTH: usize = 8;
+    const DURATION_WIDTH: usize = 12;
+
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<ReportCommand, Box<dyn Error>> {
+        let report_type = match command_result.get_value().trim().to_lowercase().as_str() {
+            "estimates" => ReportType::Estimates,
+            "" => return Err("Value cannot be empty".into()),
+            _ => return Err("Invalid report. Must be estimates".into())
+        };
+        Ok(ReportCommand { report_type })
+    }
+
+    fn print_estimate_report(&self, todos: &[Todo]) {
+        let estimated_todos: Vec<&Todo> = todos.iter().filter(|x| x.get_completed() && x.get_estimate().is_some()).collect();
+        if estimated_todos.is_empty() {
+            println!("No completed todo has an estimate yet");
+            return;
+        }
+        println!("{}Estimates versus actual durations of the completed todos{}", bold(), reset_style());
+        println!("The actual duration is the time tracked, or the time between creation and completion\n");
+        self.print_estimate_rows("Priority", &build_rows_by_priority(&estimated_todos));
+        println!();
+        self.print_estimate_rows("Week", &build_rows_by_week(&estimated_todos));
+    }
+
+    fn print_estimate_rows(&self, label_title: &str, rows: &[EstimateRow]) {
+        println!("{}{label:<widthl$} {count:>widthc$} {estimated:>widthd$} {actual:>widthd$} {accuracy:>widthd$}{}",
+                 underline(), reset_style(),
+                 label=label_title, widthl=ReportCommand::LABEL_WIDTH,
+                 count="Todos", widthc=ReportCommand::COUNT_WIDTH,
+                 estimated="Estimated", actual="Actual", accuracy="Actual/Est.",
+                 widthd=ReportCommand::DURATION_WIDTH);
+        for row in rows {
+            println!("{label:<widthl$} {count:>widthc$} {estimated:>widthd$} {actual:>widthd$} {accuracy:>widthd$}",
+                     label=row.label, widthl=ReportCommand::LABEL_WIDTH,
+                     count=row.count, widthc=ReportCommand::COUNT_WIDTH,
+                     estimated=format_duration(&row.estimated),
+                     actual=format_duration(&row.actual),
+                     accuracy=format!("{}%", row.get_accuracy()),
+                     widthd=ReportCommand::DURATION_WIDTH);
+        }
+    }
+}
+
+pub fn build_rows_by_priority(todos: &[&Todo]) -> Vec<EstimateRow> {
+    let mut rows = vec![];
+    for priority in [Priority::High, Priority::Medium, Priority::Low] {
+        let mut row = EstimateRow::new(&priority.to_string());
+        for todo in todos.iter().filter(|x| x.get_priority() == priority) {
+            add_todo_to_row(&mut row, todo);
+        }
+        if row.count > 0 {
+            rows.push(row);
+        }
+    }
+    rows
+}
+
+pub fn build_rows_by_week(todos: &[&Todo]) -> Vec<EstimateRow> {
+    let mut rows: Vec<EstimateRow> = vec![];
+    let mut sorted_todos = todos.to_vec();
+    sorted_todos.sort_by_key(|x| x.get_when_completed_in_localtime());
+    for todo in sorted_todos {
+        let week = todo.get_when_completed_in_localtime().iso_week();
+        let label = format!("{}-W{:02}", week.year(), week.week());
+        if rows.last().map(|x| x.label != label).unwrap_or(true) {
+            rows.push(EstimateRow::new(&label));
+        }
+        add_todo_to_row(rows.last_mut().unwrap(), todo);
+    }
+    rows
+}
+
+fn add_todo_to_row(row: &mut EstimateRow, todo: &Todo) {
+    if let (Some(estimated), Some(actual)) = (todo.get_estimate(), todo.get_actual_duration()) {
+        row.add(estimated, actual);
+    }
+}
+
+impl ExecutableCommand for ReportCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let todos = read_todos_with_archive()?;
+        match self.report_type {
+            ReportType::Estimates => self.print_estimate_report(&todos)
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use crate::report_command::{build_rows_by_priority, build_rows_by_week, ReportCommand, ReportType};
+    use crate::todo::Todo;
+    use chrono::{Duration, TimeZone, Utc};
+    use std::collections::HashMap;
+
+    fn get_completed_todo(priority: Priority, day: u32, estimate_minutes: i64, actual_minutes: i64) -> Todo {
+        let created = Utc.with_ymd_and_hms(2026, 10, day, 10, 0, 0).unwrap();
+        let mut todo = Todo::new(1, "Test", priority, created).unwrap();
+        todo.set_estimate(Some(Duration::minutes(estimate_minutes))).unwrap();
+        todo.set_completed(true, Some(created + Duration::minutes(actual_minutes)));
+        todo
+    }
+
+    #[test]
+    fn report_command_new_from_command_result_with_estimates_return_success() {
+        let command = ReportCommand::new_from_command_result(&CommandResult::new(Command::Report,
+            "Estimates",
+            HashMap::new()));
+        assert_eq!(ReportType::Estimates, command.unwrap().report_type);
+    }
+
+    #[test]
+    fn report_command_new_from_command_result_with_empty_value_return_error() {
+        let command = ReportCommand::new_from_command_result(&CommandResult::new(Command::Report,
+            "",
+            HashMap::new()));
+        assert_eq!("Value cannot be empty", command.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn report_command_new_from_command_result_with_unknown_report_return_error() {
+        let command = ReportCommand::new_from_command_result(&CommandResult::new(Command::Report,
+            "blabla",
+            HashMap::new()));
+        assert_eq!("Invalid report. Must be estimates", command.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn build_rows_by_priority_return_totals_per_priority() {
+        let todos = [get_completed_todo(Priority::Low, 5, 60, 90),
+                      get_completed_todo(Priority::High, 5, 60, 30),
+                      get_completed_todo(Priority::Low, 6, 60, 30)];
+        let rows = build_rows_by_priority(&todos.iter().collect::<Vec<&Todo>>());
+        assert_eq!(2, rows.len());
+        assert_eq!("High", rows[0].label);
+        assert_eq!(1, rows[0].count);
+        assert_eq!(50, rows[0].get_accuracy());
+        assert_eq!("Low", rows[1].label);
+        assert_eq!(2, rows[1].count);
+        assert_eq!(Duration::minutes(120), rows[1].estimated);
+        assert_eq!(Duration::minutes(120), rows[1].actual);
+        assert_eq!(100, rows[1].get_accuracy());
+    }
+
+    #[test]
+    fn build_rows_by_week_return_totals_per_iso_week() {
+        let todos = [get_completed_todo(Priority::Low, 20, 60, 90),
+                      get_completed_todo(Priority::High, 7, 60, 30),
+                      get_completed_todo(Priority::Low, 6, 60, 30)];
+        let rows = build_rows_by_week(&todos.iter().collect::<Vec<&Todo>>());
+        assert_eq!(2, rows.len());
+        assert_eq!("2026-W41", rows[0].label);
+        assert_eq!(2, rows[0].count);
+        assert_eq!("2026-W43", rows[1].label);
+        assert_eq!(150, rows[1].get_accuracy());
+    }
+}
@@ -0,0 +1,510 @@
+use crate::cli_definition::{get_command_definitions, ValueCompletion};
+use crate::common_structs::ExecutableCommand;
+use crate::config::get_config;
+use crate::data_service::{end_session, has_unsaved_changes, is_session_started, read_all_todos, save_session, start_session};
+use crate::output::{reset_color, success_color};
+use home::home_dir;
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::{clear, cursor};
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+const PROMPT: &str = "beaver> ";
+const HISTORY_SIZE: usize = 500;
+const SHELL_COMMANDS: [&str; 4] = ["exit", "help", "quit", "save"];
+
+#[derive(Debug, PartialEq)]
+pub enum EditorEvent {
+    Continue,
+    Submit(String),
+    Complete,
+    Cancel,
+    Exit
+}
+
+// The line being typed with the position of the cursor and the history of the previous lines
+#[derive(Debug)]
+pub struct LineEditor {
+    line: Vec<char>,
+    cursor: usize,
+    history: Vec<String>,
+    // The position in the history while browsing it with up and down
+    history_index: usize,
+    // The line typed before browsing the history
+    draft: String
+}
+
+impl LineEditor {
+    pub fn new(history: Vec<String>) -> LineEditor {
+        let history_index = history.len();
+        LineEditor { line: vec![], cursor: 0, history, history_index, draft: String::new() }
+    }
+
+    pub fn get_line(&self) -> String {
+        self.line.iter().collect()
+    }
+
+    pub fn get_history(&self) -> &Vec<String> {
+        &self.history
+    }
+
+    fn get_text_before_cursor(&self) -> String {
+        self.line[..self.cursor].iter().collect()
+    }
+
+    fn set_line(&mut self, line: &str) {
+        self.line = line.chars().collect();
+        self.cursor = self.line.len();
+    }
+
+    // Replace the text between start and the cursor, used by the completion
+    pub fn replace_before_cursor(&mut self, start: usize, text: &str) {
+        let inserted: Vec<char> = text.chars().collect();
+        let inserted_count = inserted.len();
+        self.line.splice(start..self.cursor, inserted);
+        self.cursor = start + inserted_count;
+    }
+
+    fn browse_history(&mut self, offset: isize) {
+        let index = (self.history_index as isize + offset).clamp(0, self.history.len() as isize) as usize;
+        if index == self.history_index {
+            return;
+        }
+        if self.history_index == self.history.len() {
+            self.draft = self.get_line();
+        }
+        self.history_index = index;
+        let line = match self.history.get(index) {
+            Some(line) => line.clone(),
+            None => self.draft.clone()
+        };
+        self.set_line(&line);
+    }
+
+    pub fn handle_key(&mut self, key: Key) -> EditorEvent {
+        match key {
+            Key::Char('\n') => {
+                let line = self.get_line();
+                let entry = line.trim().to_string();
+                if !entry.is_empty() && self.history.last() != Some(&entry) {
+                    self.history.push(entry);
+                }
+                self.history_index = self.history.len();
+                self.set_line("");
+                return EditorEvent::Submit(line);
+            },
+            Key::Char('\t') => return EditorEvent::Complete,
+            Key::Ctrl('c') => {
+                self.set_line("");
+                self.history_index = self.history.len();
+                return EditorEvent::Cancel;
+            },
+            Key::Ctrl('d') if self.line.is_empty() => return EditorEvent::Exit,
+            Key::Char(c) if !c.is_control() => {
+                self.line.insert(self.cursor, c);
+                self.cursor += 1;
+            },
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.line.remove(self.cursor);
+            },
+            Key::Delete | Key::Ctrl('d') if self.cursor < self.line.len() => {
+                self.line.remove(self.cursor);
+            },
+            Key::Left if self.cursor > 0 => self.cursor -= 1,
+            Key::Right if self.cursor < self.line.len() => self.cursor += 1,
+            Key::Home | Key::Ctrl('a') => self.cursor = 0,
+            Key::End | Key::Ctrl('e') => self.cursor = self.line.len(),
+            Key::Ctrl('u') => {
+                self.line.drain(..self.cursor);
+                self.cursor = 0;
+            },
+            Key::Up => self.browse_history(-1),
+            Key::Down => self.browse_history(1),
+            _ => ()
+        }
+        EditorEvent::Continue
+    }
+}
+
+// A value that can complete the word being typed, with a description like the title of a todo
+#[derive(Debug, PartialEq)]
+pub struct Completion {
+    value: String,
+    description: Option<String>
+}
+
+impl Completion {
+    fn new(value: &str) -> Completion {
+        Completion { value: value.to_string(), description: None }
+    }
+}
+
+// Return the position of the word before the cursor and the values that can complete it
+pub fn get_completions(text_before_cursor: &str, open_todo_ids: &[(u32, String)]) -> (usize, Vec<Completion>) {
+    let word_start = text_before_cursor.rfind(char::is_whitespace).map(|x| x + 1).unwrap_or(0);
+    let word = &text_before_cursor[word_start..];
+    let previous_words: Vec<&str> = text_before_cursor[..word_start].split_whitespace().collect();
+    let mut completions = vec![];
+    let definitions = get_command_definitions();
+    match previous_words.first() {
+        None => {
+            let mut names: Vec<String> = definitions.iter()
+                .flat_map(|x| std::iter::once(x.get_name()).chain(x.get_aliases().iter().copied()))
+                .chain(SHELL_COMMANDS.iter().copied())
+                .map(|x| x.to_string())
+                .collect();
+            names.extend(get_config().get_aliases().keys().cloned());
+            names.sort();
+            completions.extend(names.iter().map(|x| Completion::new(x)));
+        },
+        Some(command_name) => {
+            let definition = match definitions.iter().find(|x| x.is_named(&command_name.to_lowercase())) {
+                Some(definition) => definition,
+                None => return (word_start, vec![])
+            };
+            match word.split_once('=') {
+                Some((flag, _)) if word.starts_with('-') => {
+                    let option = definition.get_options().iter()
+                        .find(|x| format!("--{}", x.get_name()) == flag || x.get_short().map(|s| format!("-{}", s)).as_deref() == Some(flag));
+                    if let Some(ValueCompletion::Choices(choices)) = option.map(|x| x.get_completion()) {
+                        completions.extend(choices.iter().map(|x| Completion::new(&format!("{}={}", flag, x))));
+                    }
+                },
+                _ if word.starts_with('-') => {
+                    completions.push(Completion::new("--help"));
+                    for option in definition.get_options() {
+                        let suffix = if option.takes_value() { "=" } else { "" };
+                        completions.push(Completion { value: format!("--{}{}", option.get_name(), suffix),
+                                                      description: Some(option.get_description().to_string()) });
+                    }
+                },
+                _ => match definition.get_value().map(|x| x.get_completion()) {
+                    Some(ValueCompletion::Choices(choices)) => completions.extend(choices.iter().map(|x| Completion::new(x))),
+                    Some(ValueCompletion::OpenTodoIds) => completions.extend(open_todo_ids.iter()
+                        .map(|(id, title)| Completion { value: id.to_string(), description: Some(title.clone()) })),
+                    _ => ()
+                }
+            }
+        }
+    }
+    completions.retain(|x| x.value.starts_with(word));
+    (word_start, completions)
+}
+
+// Return the longest text starting every completion
+fn get_common_prefix(completions: &[Completion]) -> String {
+    let first = match completions.first() {
+        Some(completion) => &completion.value,
+        None => return String::new()
+    };
+    let mut length = first.len();
+    for completion in &completions[1..] {
+        length = first.char_indices().zip(completion.value.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((index, a), _)| index + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(length);
+    }
+    first[..length].to_string()
+}
+
+// Split a line into arguments like a shell, with quotes keeping spaces and \ escaping the next character
+pub fn split_line(line: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                in_word = true;
+            },
+            (Some('\''), c) => current.push(c),
+            (_, '\\') => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+                in_word = true;
+            },
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            },
+            (_, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err("Missing closing quote".into());
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+fn get_history_file() -> String {
+    format!("{}/.beaver/shell_history", home_dir().unwrap().display())
+}
+
+fn read_history() -> Vec<String> {
+    fs::read_to_string(get_history_file()).map(|x| x.lines().map(|line| line.to_string()).collect()).unwrap_or_default()
+}
+
+fn write_history(history: &[String]) -> Result<(), Box<dyn Error>> {
+    let start = history.len().saturating_sub(HISTORY_SIZE);
+    let mut content = history[start..].join("\n");
+    content.push('\n');
+    fs::write(get_history_file(), content)?;
+    Ok(())
+}
+
+fn get_open_todo_ids() -> Vec<(u32, String)> {
+    let mut todos = read_all_todos().unwrap_or_default();
+    todos.retain(|x| !x.get_completed());
+    todos.sort_unstable_by_key(|x| x.get_id());
+    todos.iter().map(|x| (x.get_id(), x.get_title().to_string())).collect()
+}
+
+fn print_shell_help() {
+    println!("Type the commands like after beaver (ex: add Call mom -p=H, done 3, list --all)");
+    println!("    save    Write the changes to the todo list");
+    println!("    help    Print this help, beaver commands are listed by --help");
+    println!("    exit    Write the changes and quit (also quit or Ctrl-D)");
+    println!("Tab completes the commands, the options and the todo ids, up and down browse the history");
+}
+
+#[derive(Debug)]
+pub struct ShellCommand {
+}
+
+impl ShellCommand {
+    // Run a line, return false when the shell must stop
+    fn run_line(line: &str) -> bool {
+        let args = match split_line(line) {
+            Ok(args) => args,
+            Err(e) => {
+                crate::print_command_error(e);
+                return true;
+            }
+        };
+        match args.first().map(|x| x.to_lowercase()).as_deref() {
+            None => (),
+            Some("exit") | Some("quit") => return false,
+            Some("help") => print_shell_help(),
+            Some("save") => ShellCommand::save(),
+            Some("shell") => crate::print_command_error("The shell is already running".into()),
+            Some("-h") | Some("--help") => print!("{}", crate::cli_definition::get_usage()),
+            Some(_) => crate::run_args(args)
+        }
+        true
+    }
+
+    fn save() {
+        match save_session() {
+            Ok(true) => println!("{}The changes have been saved!{}", success_color(), reset_color()),
+            Ok(false) => println!("There is no change to save"),
+            Err(e) => crate::print_command_error(e)
+        }
+    }
+
+    // Read the lines of a file or a pipe (beaver shell < commands.txt)
+    fn run_script() -> Result<(), Box<dyn Error>> {
+        for line in io::stdin().lock().lines() {
+            if !ShellCommand::run_line(&line?) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_line(stdout: &mut impl Write, editor: &LineEditor) -> Result<(), Box<dyn Error>> {
+        let line = editor.get_line();
+        write!(stdout, "\r{}{}{}", clear::CurrentLine, PROMPT, line)?;
+        let characters_after_cursor = line.chars().count() - editor.cursor;
+        if characters_after_cursor > 0 {
+            write!(stdout, "{}", cursor::Left(characters_after_cursor as u16))?;
+        }
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn complete(stdout: &mut impl Write, editor: &mut LineEditor) -> Result<(), Box<dyn Error>> {
+        let text_before_cursor = editor.get_text_before_cursor();
+        let (word_start, completions) = get_completions(&text_before_cursor, &get_open_todo_ids());
+        let word_length = text_before_cursor.len() - word_start;
+        let start = text_before_cursor[..word_start].chars().count();
+        match completions.len() {
+            0 => (),
+            1 => {
+                let mut value = completions[0].value.clone();
+                if !value.ends_with('=') {
+                    value.push(' ');
+                }
+                editor.replace_before_cursor(start, &value);
+            },
+            _ => {
+                let prefix = get_common_prefix(&completions);
+                if prefix.len() > word_length {
+                    editor.replace_before_cursor(start, &prefix);
+                }
+                else {
+                    write!(stdout, "\r\n")?;
+                    for completion in &completions {
+                        match &completion.description {
+                            Some(description) => write!(stdout, "{:<20}{}\r\n", completion.value, description)?,
+                            None => write!(stdout, "{}\r\n", completion.value)?
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_interactive() -> Result<(), Box<dyn Error>> {
+        let mut editor = LineEditor::new(read_history());
+        let mut stdout = io::stdout().into_raw_mode()?;
+        ShellCommand::draw_line(&mut stdout, &editor)?;
+        for key in io::stdin().keys() {
+            match editor.handle_key(key?) {
+                EditorEvent::Submit(line) => {
+                    write!(stdout, "\r\n")?;
+                    // The commands print normally and can ask questions
+                    stdout.suspend_raw_mode()?;
+                    let keep_running = ShellCommand::run_line(&line);
+                    stdout.activate_raw_mode()?;
+                    if !keep_running {
+                        break;
+                    }
+                },
+                EditorEvent::Complete => ShellCommand::complete(&mut stdout, &mut editor)?,
+                EditorEvent::Cancel => write!(stdout, "^C\r\n")?,
+                EditorEvent::Exit => {
+                    write!(stdout, "\r\n")?;
+                    break;
+                },
+                EditorEvent::Continue => ()
+            }
+            ShellCommand::draw_line(&mut stdout, &editor)?;
+        }
+        stdout.suspend_raw_mode()?;
+        write_history(editor.get_history())
+    }
+}
+
+impl ExecutableCommand for ShellCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        if is_session_started() {
+            return Err("The shell is already running".into());
+        }
+        start_session()?;
+        let result = match termion::is_tty(&io::stdin()) {
+            true => {
+                println!("Beaver shell, type help for the list of commands and exit to quit");
+                ShellCommand::run_interactive()
+            },
+            false => ShellCommand::run_script()
+        };
+        // The changes are written even when the shell stops on an error
+        if has_unsaved_changes() {
+            ShellCommand::save();
+        }
+        end_session();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shell_command::{get_common_prefix, get_completions, split_line, Completion, EditorEvent, LineEditor};
+    use termion::event::Key;
+
+    fn type_text(editor: &mut LineEditor, text: &str) {
+        for c in text.chars() {
+            editor.handle_key(Key::Char(c));
+        }
+    }
+
+    fn get_values(completions: &[Completion]) -> Vec<&str> {
+        completions.iter().map(|x| x.value.as_str()).collect()
+    }
+
+    #[test]
+    fn split_line_with_quotes_and_escapes_return_args() {
+        assert_eq!(vec!["add", "Call mom", "-p=H"], split_line("  add \"Call mom\"  -p=H ").unwrap());
+        assert_eq!(vec!["edit", "2", "-t=It's done"], split_line("edit 2 -t='It'\\''s done'").unwrap());
+        assert_eq!(vec!["add", "a b", ""], split_line("add a\\ b ''").unwrap());
+        assert_eq!("Missing closing quote", split_line("add \"Call mom").err().unwrap().to_string());
+    }
+
+    #[test]
+    fn handle_key_with_editing_keys_change_line() {
+        let mut editor = LineEditor::new(vec![]);
+        type_text(&mut editor, "ad mom");
+        editor.handle_key(Key::Home);
+        editor.handle_key(Key::Right);
+        editor.handle_key(Key::Right);
+        type_text(&mut editor, "d Call");
+        editor.handle_key(Key::End);
+        editor.handle_key(Key::Backspace);
+        assert_eq!("add Call mo", editor.get_line());
+        assert_eq!(EditorEvent::Submit(String::from("add Call mo")), editor.handle_key(Key::Char('\n')));
+        assert_eq!("", editor.get_line());
+        assert_eq!(EditorEvent::Exit, editor.handle_key(Key::Ctrl('d')));
+    }
+
+    #[test]
+    fn handle_key_with_up_and_down_browse_history() {
+        let mut editor = LineEditor::new(vec![String::from("list"), String::from("done 3")]);
+        type_text(&mut editor, "ne");
+        editor.handle_key(Key::Up);
+        assert_eq!("done 3", editor.get_line());
+        editor.handle_key(Key::Up);
+        editor.handle_key(Key::Up);
+        assert_eq!("list", editor.get_line());
+        editor.handle_key(Key::Down);
+        editor.handle_key(Key::Down);
+        assert_eq!("ne", editor.get_line());
+        editor.handle_key(Key::Char('\n'));
+        editor.handle_key(Key::Up);
+        editor.handle_key(Key::Char('\n'));
+        assert_eq!(vec!["list", "done 3", "ne"], *editor.get_history());
+    }
+
+    #[test]
+    fn get_completions_return_commands_options_and_ids() {
+        let (start, completions) = get_completions("d", &[]);
+        assert_eq!(0, start);
+        assert_eq!(vec!["d", "done"], get_values(&completions));
+        let (start, completions) = get_completions("list --s", &[]);
+        assert_eq!(5, start);
+        assert_eq!(vec!["--sort="], get_values(&completions));
+        let (_, completions) = get_completions("ls --sort=creation", &[]);
+        assert_eq!(vec!["--sort=creationtimedesc", "--sort=creationtime"], get_values(&completions));
+        let ids = vec![(2, String::from("Call mom")), (12, String::from("Pay the bills"))];
+        let (start, completions) = get_completions("done 3 1", &ids);
+        assert_eq!(7, start);
+        assert_eq!(vec![Completion { value: String::from("12"), description: Some(String::from("Pay the bills")) }], completions);
+        assert!(get_completions("unknown ", &ids).1.is_empty());
+    }
+
+    #[test]
+    fn get_common_prefix_return_shared_start() {
+        let completions = vec![Completion::new("--sort=creationtimedesc"), Completion::new("--sort=creationtime")];
+        assert_eq!("--sort=creationtime", get_common_prefix(&completions));
+        assert_eq!("", get_common_prefix(&[Completion::new("add"), Completion::new("list")]));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::{read_all_todos, write_todos};
+use crate::duration_format::parse_date_time;
+use crate::output::{reset_color, success_color};
+use chrono::{DateTime, Local, Utc};
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct SnoozeCommand {
+    id: u32,
+    // None clears the snooze of the todo
+    wait_until: Option<DateTime<Utc>>
+}
+
+impl SnoozeCommand {
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<SnoozeCommand, Box<dyn Error>> {
+        let value = command_result.get_value().trim();
+        if value.is_empty() {
+            return Err("Value cannot be empty".into());
+        }
+        let (id, when) = match value.split_once(char::is_whitespace) {
+            Some((id, when)) => (id.parse::<u32>()?, when.trim()),
+            None => return Err("The date is required (ex: snooze 1 2026-11-05 or snooze 1 3d)".into())
+        };
+        let now = Local::now();
+        let wait_until = match when.to_lowercase().as_str() {
+            "none" => None,
+            _ => Some(parse_date_time(when, now)?)
+        };
+        if wait_until.map(|x| x <= now).unwrap_or(false) {
+            return Err(format!("The date {} has already passed", when).into());
+        }
+        Ok(SnoozeCommand { id, wait_until })
+    }
+}
+
+impl ExecutableCommand for SnoozeCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let mut todos = read_all_todos()?;
+        let mut iter = todos.iter_mut();
+        match iter.find(|x| x.get_id() == self.id && !x.get_completed()) {
+            Some(todo) => todo.set_wait_until(self.wait_until),
+            None => return Err(format!("Unable to find the todo with id {}", self.id).into())
+        }
+        write_todos(&todos)?;
+        match self.wait_until {
+            Some(wait_until) => println!("{}The todo {} has been snoozed until {}!{}", success_color(), self.id,
+                                         DateTime::<Local>::from(wait_until).format("%Y-%m-%d %H:%M"), reset_color()),
+            None => println!("{}The todo {} is no longer snoozed!{}", success_color(), self.id, reset_color())
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::snooze_command::SnoozeCommand;
+    use crate::common_structs::{Command, CommandResult};
+    use chrono::{Local, TimeZone};
+    use std::collections::HashMap;
+
+    fn get_command(value: &str) -> Result<SnoozeCommand, String> {
+        SnoozeCommand::new_from_command_result(&CommandResult::new(Command::Snooze, value, HashMap::new()))
+            .map_err(|x| x.to_string())
+    }
+
+    #[test]
+    fn snooze_command_new_from_command_result_with_id_only_return_error() {
+        assert_eq!("The date is required (ex: snooze 1 2026-11-05 or snooze 1 3d)", get_command("1").unwrap_err());
+    }
+
+    #[test]
+    fn snooze_command_new_from_command_result_with_date_and_time_return_success() {
+        let command = get_command("3 2099-11-05 14:00").unwrap();
+        assert_eq!(3, command.id);
+        assert_eq!(Local.with_ymd_and_hms(2099, 11, 5, 14, 0, 0).unwrap(), command.wait_until.unwrap());
+    }
+
+    #[test]
+    fn snooze_command_new_from_command_result_with_none_return_no_date() {
+        assert!(get_command("3 none").unwrap().wait_until.is_none());
+    }
+
+    #[test]
+    fn snooze_command_new_from_command_result_with_past_date_return_error() {
+        assert_eq!("The date 2020-01-01 has already passed", get_command("3 2020-01-01").unwrap_err());
+    }
+}
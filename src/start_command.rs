@@ -0,0 +1,80 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::{read_all_todos, write_todos};
+use crate::duration_format::format_duration;
+use crate::output::{reset_color, success_color};
+use chrono::Utc;
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct StartCommand {
+    id: u32
+}
+
+impl StartCommand {
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<StartCommand, Box<dyn Error>> {
+        let value = command_result.get_value().trim();
+        if value.is_empty() {
+            return Err("Value cannot be empty".into());
+        }
+        let id = value.parse::<u32>()?;
+        Ok(StartCommand { id })
+    }
+}
+
+impl ExecutableCommand for StartCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let mut todos = read_all_todos()?;
+        let now = Utc::now();
+        let todo_index = match todos.iter().position(|x| x.get_id() == self.id && !x.get_completed()) {
+            Some(index) => index,
+            None => return Err(format!("Unable to find the todo with id {}", self.id).into())
+        };
+        if todos[todo_index].is_tracking() {
+            return Err(format!("The todo {} is already being tracked", self.id).into());
+        }
+        //Only one todo can be tracked at a time
+        let mut stopped_todos = vec![];
+        for todo in todos.iter_mut().filter(|x| x.is_tracking()) {
+            let duration = todo.stop_tracking(now)?;
+            stopped_todos.push(format!("The tracking of the todo {} has been stopped after {}", todo.get_title(), format_duration(&duration)));
+        }
+        todos[todo_index].start_tracking(now)?;
+        write_todos(&todos)?;
+        for message in stopped_todos {
+            println!("{}", message);
+        }
+        println!("{}The tracking of the todo {} has started!{}", success_color(), todos[todo_index].get_title(), reset_color());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::start_command::StartCommand;
+    use crate::common_structs::{Command, CommandResult};
+    use std::collections::HashMap;
+
+    #[test]
+    fn start_command_new_from_command_result_with_empty_value_return_error() {
+        let command = StartCommand::new_from_command_result(&CommandResult::new(Command::Start, 
+            "", 
+            HashMap::new()));
+        assert_eq!("Value cannot be empty", command.unwrap_err().to_string())
+    }
+
+    #[test]
+    fn start_command_new_from_command_result_with_1_value_return_success() {
+        let command = StartCommand::new_from_command_result(&CommandResult::new(Command::Start, 
+            "1", 
+            HashMap::new()));
+        assert_eq!(1, command.unwrap().id);
+    }
+
+    #[test]
+    fn start_command_new_from_command_result_with_minus_abc_value_return_error() {
+        let command = StartCommand::new_from_command_result(&CommandResult::new(Command::Start, 
+            "abc", 
+            HashMap::new()));
+        assert_eq!("invalid digit found in string", command.unwrap_err().to_string());
+    }
+}
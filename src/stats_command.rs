@@ -0,0 +1,313 @@
+use crate::common_structs::{CommandResult, ExecutableCommand, Priority};
+use crate::data_service::read_todos_with_archive;
+use crate::duration_format::format_duration;
+use crate::output::{bold, reset_style, underline};
+use crate::todo::Todo;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use std::error::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatsPeriod {
+    Day,
+    Week,
+    Month
+}
+
+impl StatsPeriod {
+    fn get_count(self) -> usize {
+        match self {
+            StatsPeriod::Day => 14,
+            StatsPeriod::Week => 8,
+            StatsPeriod::Month => 12
+        }
+    }
+
+    // Return the first day of the period containing the date
+    fn get_start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            StatsPeriod::Day => date,
+            StatsPeriod::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            StatsPeriod::Month => date.with_day(1).unwrap()
+        }
+    }
+
+    fn get_previous_start(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            StatsPeriod::Day => start - Duration::days(1),
+            StatsPeriod::Week => start - Duration::days(7),
+            StatsPeriod::Month => start - Months::new(1)
+        }
+    }
+
+    fn format(self, start: NaiveDate) -> String {
+        match self {
+            StatsPeriod::Day => start.format("%Y-%m-%d").to_string(),
+            StatsPeriod::Week => format!("{}-W{:02}", start.iso_week().year(), start.iso_week().week()),
+            StatsPeriod::Month => start.format("%Y-%m").to_string()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PeriodCount {
+    start: NaiveDate,
+    created: usize,
+    completed: usize
+}
+
+#[derive(Debug)]
+pub struct StatsCommand {
+    period: StatsPeriod,
+    chart: bool
+}
+
+impl StatsCommand {
+    const PERIOD_WIDTH: usize = 12;
+    const COUNT_WIDTH: usize = 10;
+    const OLDEST_COUNT: usize = 5;
+    const SPARKLINE_LEVELS: [char; 8] = ['_', '.', ',', '-', '~', '=', '*', '#'];
+
+    pub fn new_from_command_result(command_result : &CommandResult) -> Result<StatsCommand, Box<dyn Error>> {
+        let period = match command_result.get_options().get("period") {
+            Some(p) => match p.to_lowercase().as_str() {
+                "day" => StatsPeriod::Day,
+                "week" => StatsPeriod::Week,
+                "month" => StatsPeriod::Month,
+                _ => return Err("Invalid period value. Must be day, week or month".into())
+            },
+            None => StatsPeriod::Week
+        };
+        let chart = command_result.get_options().contains_key("chart");
+        Ok(StatsCommand { period, chart })
+    }
+
+    fn print_period_counts(&self, counts: &[PeriodCount]) {
+        println!("{}{period:<widthp$}{created:>widthc$}{completed:>widthc$}{}",
+                 underline(), reset_style(),
+                 period="Period", widthp=StatsCommand::PERIOD_WIDTH,
+                 created="Created", completed="Completed", widthc=StatsCommand::COUNT_WIDTH);
+        for count in counts {
+            println!("{period:<widthp$}{created:>widthc$}{completed:>widthc$}",
+                     period=self.period.format(count.start), widthp=StatsCommand::PERIOD_WIDTH,
+                     created=count.created, completed=count.completed, widthc=StatsCommand::COUNT_WIDTH);
+        }
+        if self.chart {
+            let created: Vec<usize> = counts.iter().map(|x| x.created).collect();
+            let completed: Vec<usize> = counts.iter().map(|x| x.completed).collect();
+            println!();
+            println!("{:<widthp$}{}", "Created", build_sparkline(&created), widthp=StatsCommand::PERIOD_WIDTH);
+            println!("{:<widthp$}{}", "Completed", build_sparkline(&completed), widthp=StatsCommand::PERIOD_WIDTH);
+        }
+    }
+
+    fn print_completion_times(&self, todos: &[Todo]) {
+        println!("{}{priority:<widthp$}{average:>widthc$}{median:>widthc$}{}",
+                 underline(), reset_style(),
+                 priority="Priority", widthp=StatsCommand::PERIOD_WIDTH,
+                 average="Average", median="Median", widthc=StatsCommand::COUNT_WIDTH);
+        for priority in [Priority::High, Priority::Medium, Priority::Low] {
+            let mut durations = get_completion_durations(todos, priority);
+            let (average, median) = match (get_average(&durations), get_median(&mut durations)) {
+                (Some(average), Some(median)) => (format_duration(&average), format_duration(&median)),
+                _ => (String::from("-"), String::from("-"))
+            };
+            println!("{priority:<widthp$}{average:>widthc$}{median:>widthc$}",
+                     priority=priority.to_string(), widthp=StatsCommand::PERIOD_WIDTH,
+                     average=average, median=median, widthc=StatsCommand::COUNT_WIDTH);
+        }
+    }
+
+    fn print_oldest_open_todos(&self, todos: &[Todo]) {
+        let mut open_todos: Vec<&Todo> = todos.iter().filter(|x| !x.get_completed()).collect();
+        println!("{}Open backlog:{} {} todo(s)", bold(), reset_style(), open_todos.len());
+        if open_todos.is_empty() {
+            return;
+        }
+        open_todos.sort_by_key(|x| x.get_when_created_in_localtime());
+        println!("Oldest open todos:");
+        let today = Local::now().date_naive();
+        for todo in open_todos.iter().take(StatsCommand::OLDEST_COUNT) {
+            let age = (today - todo.get_when_created_in_localtime().date_naive()).num_days();
+            println!("    {:<3} {} ({} day(s) old)", todo.get_id(), todo.get_title(), age);
+        }
+    }
+}
+
+impl ExecutableCommand for StatsCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let todos = read_todos_with_archive()?;
+        let counts = count_per_period(&todos, self.period, Local::now().date_naive());
+        self.print_period_counts(&counts);
+        println!();
+        self.print_completion_times(&todos);
+        println!();
+        self.print_oldest_open_todos(&todos);
+        Ok(())
+    }
+}
+
+// Count the todos created and completed in each period, ending with the period of today.
+pub fn count_per_period(todos: &[Todo], period: StatsPeriod, today: NaiveDate) -> Vec<PeriodCount> {
+    let mut starts = vec![period.get_start(today)];
+    while starts.len() < period.get_count() {
+        starts.push(period.get_previous_start(*starts.last().unwrap()));
+    }
+    starts.reverse();
+    starts.iter().map(|start| {
+        let in_period = |date: NaiveDate| period.get_start(date) == *start;
+        PeriodCount {
+            start: *start,
+            created: todos.iter().filter(|x| in_period(x.get_when_created_in_localtime().date_naive())).count(),
+            completed: todos.iter().filter(|x| x.get_completed() && in_period(x.get_when_completed_in_localtime().date_naive())).count()
+        }
+    }).collect()
+}
+
+pub fn get_completion_durations(todos: &[Todo], priority: Priority) -> Vec<Duration> {
+    todos.iter()
+        .filter(|x| x.get_completed() && x.get_priority() == priority)
+        .map(|x| x.get_when_completed_in_localtime() - x.get_when_created_in_localtime())
+        .collect()
+}
+
+pub fn get_average(durations: &[Duration]) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    let total = durations.iter().fold(Duration::zero(), |total, x| total + *x);
+    Some(total / durations.len() as i32)
+}
+
+pub fn get_median(durations: &mut [Duration]) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    durations.sort();
+    let middle = durations.len() / 2;
+    if durations.len().is_multiple_of(2) {
+        Some((durations[middle - 1] + durations[middle]) / 2)
+    }
+    else {
+        Some(durations[middle])
+    }
+}
+
+pub fn build_sparkline(values: &[usize]) -> String {
+    let max = values.iter().max().copied().unwrap_or(0);
+    let levels = StatsCommand::SPARKLINE_LEVELS;
+    values.iter().map(|value| {
+        match (value * (levels.len() - 1)).checked_div(max) {
+            Some(level) => levels[level],
+            None => levels[0]
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use crate::stats_command::{build_sparkline, count_per_period, get_average, get_completion_durations, get_median, StatsCommand, StatsPeriod};
+    use crate::todo::Todo;
+    use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+    use std::collections::HashMap;
+
+    fn get_todo(created: NaiveDate, completed: Option<NaiveDate>) -> Todo {
+        let created = Local.from_local_datetime(&created.and_hms_opt(12, 0, 0).unwrap()).unwrap();
+        let mut todo = Todo::new(1, "Test", Priority::Low, created.with_timezone(&Utc)).unwrap();
+        if let Some(completed) = completed {
+            let completed = Local.from_local_datetime(&completed.and_hms_opt(12, 0, 0).unwrap()).unwrap();
+            todo.set_completed(true, Some(completed.with_timezone(&Utc)));
+        }
+        todo
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn stats_command_new_from_command_result_with_no_options_return_week() {
+        let command = StatsCommand::new_from_command_result(&CommandResult::new(Command::Stats, "", HashMap::new())).unwrap();
+        assert_eq!(StatsPeriod::Week, command.period);
+        assert!(!command.chart);
+    }
+
+    #[test]
+    fn stats_command_new_from_command_result_with_month_and_chart_return_success() {
+        let command = StatsCommand::new_from_command_result(&CommandResult::new(Command::Stats,
+            "",
+            HashMap::from([(String::from("period"), String::from("Month")),
+                           (String::from("chart"), String::from(""))]))).unwrap();
+        assert_eq!(StatsPeriod::Month, command.period);
+        assert!(command.chart);
+    }
+
+    #[test]
+    fn stats_command_new_from_command_result_with_invalid_period_return_error() {
+        let command = StatsCommand::new_from_command_result(&CommandResult::new(Command::Stats,
+            "",
+            HashMap::from([(String::from("period"), String::from("year"))])));
+        assert_eq!("Invalid period value. Must be day, week or month", command.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn count_per_period_with_week_return_counts_per_week() {
+        let todos = vec![get_todo(date(2026, 10, 5), Some(date(2026, 10, 13))),
+                         get_todo(date(2026, 10, 14), None),
+                         get_todo(date(2026, 10, 19), Some(date(2026, 10, 20)))];
+        let counts = count_per_period(&todos, StatsPeriod::Week, date(2026, 10, 21));
+        assert_eq!(8, counts.len());
+        assert_eq!(date(2026, 8, 31), counts[0].start);
+        assert_eq!(date(2026, 10, 19), counts[7].start);
+        assert_eq!((1, 0), (counts[5].created, counts[5].completed));
+        assert_eq!((1, 1), (counts[6].created, counts[6].completed));
+        assert_eq!((1, 1), (counts[7].created, counts[7].completed));
+    }
+
+    #[test]
+    fn count_per_period_with_month_return_counts_per_month() {
+        let todos = vec![get_todo(date(2026, 1, 31), Some(date(2026, 3, 1)))];
+        let counts = count_per_period(&todos, StatsPeriod::Month, date(2026, 3, 31));
+        assert_eq!(12, counts.len());
+        assert_eq!(date(2025, 4, 1), counts[0].start);
+        assert_eq!(1, counts[9].created);
+        assert_eq!(1, counts[11].completed);
+    }
+
+    #[test]
+    fn get_completion_durations_return_only_completed_of_priority() {
+        let todos = vec![get_todo(date(2026, 10, 5), Some(date(2026, 10, 7))),
+                         get_todo(date(2026, 10, 5), None)];
+        assert_eq!(vec![Duration::days(2)], get_completion_durations(&todos, Priority::Low));
+        assert!(get_completion_durations(&todos, Priority::High).is_empty());
+    }
+
+    #[test]
+    fn get_average_and_median_with_even_count_return_values() {
+        let mut durations = vec![Duration::hours(10), Duration::hours(1), Duration::hours(3), Duration::hours(2)];
+        assert_eq!(Duration::hours(4), get_average(&durations).unwrap());
+        assert_eq!(Duration::minutes(150), get_median(&mut durations).unwrap());
+    }
+
+    #[test]
+    fn get_median_with_odd_count_return_middle_value() {
+        let mut durations = vec![Duration::hours(10), Duration::hours(1), Duration::hours(3)];
+        assert_eq!(Duration::hours(3), get_median(&mut durations).unwrap());
+    }
+
+    #[test]
+    fn get_average_and_median_with_empty_return_none() {
+        assert!(get_average(&[]).is_none());
+        assert!(get_median(&mut []).is_none());
+    }
+
+    #[test]
+    fn build_sparkline_return_one_char_per_value() {
+        assert_eq!("_#=_", build_sparkline(&[0, 7, 5, 0]));
+    }
+
+    #[test]
+    fn build_sparkline_with_only_zeros_return_lowest_level() {
+        assert_eq!("___", build_sparkline(&[0, 0, 0]));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::common_structs::ExecutableCommand;
+use crate::data_service::{read_all_todos, write_todos};
+use crate::duration_format::format_duration;
+use crate::output::{reset_color, success_color};
+use chrono::Utc;
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct StopCommand {
+}
+
+impl ExecutableCommand for StopCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let mut todos = read_all_todos()?;
+        let now = Utc::now();
+        let mut messages = vec![];
+        for todo in todos.iter_mut().filter(|x| x.is_tracking()) {
+            let duration = todo.stop_tracking(now)?;
+            messages.push(format!("The tracking of the todo {} has been stopped after {} (total {})!",
+                                  todo.get_title(),
+                                  format_duration(&duration),
+                                  format_duration(&todo.get_time_spent(now))));
+        }
+        if messages.is_empty() {
+            return Err("No todo is currently being tracked".into());
+        }
+        write_todos(&todos)?;
+        for message in messages {
+            println!("{}{}{}", success_color(), message, reset_color());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+use crate::common_structs::Priority;
+use crate::todo::Todo;
+use crate::todo_format::{ImportResult, TodoFormat};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug)]
+pub struct TaskwarriorFormat {
+}
+
+// A task as written by task export
+#[derive(Debug, Deserialize, Serialize)]
+struct Task {
+    uuid: String,
+    description: String,
+    status: String,
+    entry: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>
+}
+
+impl TaskwarriorFormat {
+    fn format_date(date: DateTime<Utc>) -> String {
+        date.format(DATE_FORMAT).to_string()
+    }
+
+    fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+        NaiveDateTime::parse_from_str(value.trim(), DATE_FORMAT).ok().map(|x| x.and_utc())
+    }
+
+    fn to_task(todo: &Todo) -> Task {
+        Task {
+            uuid: todo.get_uuid().to_string(),
+            description: todo.get_title().to_string(),
+            status: String::from(if todo.get_completed() { "completed" } else { "pending" }),
+            entry: TaskwarriorFormat::format_date(todo.get_when_created_in_localtime().with_timezone(&Utc)),
+            end: match todo.get_completed() {
+                true => Some(TaskwarriorFormat::format_date(todo.get_when_completed_in_localtime().with_timezone(&Utc))),
+                false => None
+            },
+            due: todo.get_due_in_localtime().map(|x| TaskwarriorFormat::format_date(x.with_timezone(&Utc))),
+            wait: todo.get_wait_until_in_localtime().map(|x| TaskwarriorFormat::format_date(x.with_timezone(&Utc))),
+            priority: Some(todo.get_priority().to_letter().to_string()),
+            project: todo.get_project().map(|x| x.to_string()),
+            tags: todo.get_tags().clone()
+        }
+    }
+
+    // The line of the task in a JSON array, found by its uuid
+    fn find_line_number(content: &str, value: &serde_json::Value) -> usize {
+        let uuid = value.get("uuid").and_then(|x| x.as_str()).filter(|x| !x.is_empty());
+        uuid.and_then(|uuid| content.lines().position(|x| x.contains(uuid))).map(|x| x + 1).unwrap_or(1)
+    }
+
+    fn parse_task(task: Task, line_number: usize, line: &str, result: &mut ImportResult) {
+        let skip_reason = match task.status.as_str() {
+            "deleted" => Some("The task is deleted and has been skipped"),
+            "recurring" => Some("Recurring tasks aren't supported and the task has been skipped"),
+            _ => None
+        };
+        if let Some(reason) = skip_reason {
+            result.add_issue(line_number, reason, line);
+            return;
+        }
+        let priority = match task.priority.as_deref() {
+            None | Some("") => Priority::Low,
+            Some(value) => match Priority::from_string(value) {
+                Some(priority) => priority,
+                None => {
+                    result.add_issue(line_number, &format!("The priority {} has no equivalent and has been imported as Low", value), line);
+                    Priority::Low
+                }
+            }
+        };
+        let when_created = TaskwarriorFormat::parse_date(&task.entry).unwrap_or_else(Utc::now);
+        let mut todo = match Todo::new(0, task.description.trim(), priority, when_created) {
+            Ok(todo) => todo,
+            Err(_) => {
+                result.add_issue(line_number, "The task has no description and has been skipped", line);
+                return;
+            }
+        };
+        if todo.set_uuid(&task.uuid).is_err() {
+            result.add_issue(line_number, "The task has no valid uuid, importing it again will duplicate it", line);
+        }
+        if task.status == "completed" {
+            todo.set_completed(true, Some(task.end.and_then(|x| TaskwarriorFormat::parse_date(&x)).unwrap_or_else(Utc::now)));
+        }
+        todo.set_due(task.due.and_then(|x| TaskwarriorFormat::parse_date(&x)));
+        todo.set_wait_until(task.wait.and_then(|x| TaskwarriorFormat::parse_date(&x)));
+        todo.set_project(task.project.as_deref());
+        todo.set_tags(task.tags);
+        result.add_todo(todo);
+    }
+}
+
+impl TodoFormat for TaskwarriorFormat {
+    // Write one task per line like task export, so the file can be read line by line
+    fn export(&self, todos: &[Todo]) -> Result<String, Box<dyn Error>> {
+        let mut lines = vec![];
+        for todo in todos {
+            lines.push(serde_json::to_string(&TaskwarriorFormat::to_task(todo))?);
+        }
+        Ok(format!("[\n{}\n]\n", lines.join(",\n")))
+    }
+
+    // Read the output of task export, either a JSON array or the older format of one task per line
+    fn import(&self, content: &str) -> Result<ImportResult, Box<dyn Error>> {
+        let mut result = ImportResult::new();
+        // A JSON array can be on a single line or pretty printed, each task is parsed alone to report its issues
+        if let Ok(values) = serde_json::from_str::<Vec<serde_json::Value>>(content) {
+            for value in values {
+                let task_json = value.to_string();
+                let line_number = TaskwarriorFormat::find_line_number(content, &value);
+                match serde_json::from_value::<Task>(value) {
+                    Ok(task) => TaskwarriorFormat::parse_task(task, line_number, &task_json, &mut result),
+                    Err(e) => result.add_issue(line_number, &format!("The task is invalid ({}) and has been skipped", e), &task_json)
+                }
+            }
+            return Ok(result);
+        }
+        for (index, line) in content.lines().enumerate() {
+            let json = line.trim().trim_end_matches(',');
+            if json.is_empty() || json == "[" || json == "]" {
+                continue;
+            }
+            match serde_json::from_str::<Task>(json) {
+                Ok(task) => TaskwarriorFormat::parse_task(task, index + 1, line, &mut result),
+                Err(e) => result.add_issue(index + 1, &format!("The task is invalid ({}) and has been skipped", e), line)
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::taskwarrior_format::TaskwarriorFormat;
+    use crate::todo::Todo;
+    use crate::todo_format::TodoFormat;
+    use chrono::{TimeZone, Utc};
+
+    const UUID: &str = "5c2a7a4e-8d1c-4b8e-9f3e-2d6f1a0b9c11";
+
+    #[test]
+    fn import_with_task_export_return_todos() {
+        let content = format!("[\n\
+            {{\"id\":1,\"description\":\"Call mom\",\"entry\":\"20261001T093000Z\",\"modified\":\"20261001T093000Z\",\"priority\":\"H\",\"project\":\"family\",\"status\":\"pending\",\"tags\":[\"phone\",\"weekly\"],\"due\":\"20261020T220000Z\",\"uuid\":\"{}\",\"urgency\":8.2}},\n\
+            {{\"id\":0,\"description\":\"Pay the bills\",\"end\":\"20261003T180000Z\",\"entry\":\"20261001T093000Z\",\"status\":\"completed\",\"uuid\":\"1f0e5d7a-3b2c-4d1e-8f9a-0b1c2d3e4f50\"}}\n\
+            ]\n", UUID);
+        let result = TaskwarriorFormat {}.import(&content).unwrap();
+        assert_eq!(2, result.get_todos().len());
+        let todo = &result.get_todos()[0];
+        assert_eq!("Call mom", todo.get_title());
+        assert_eq!(UUID, todo.get_uuid());
+        assert_eq!(Priority::High, todo.get_priority());
+        assert_eq!(Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap(), todo.get_when_created_in_localtime());
+        assert_eq!(Some("family"), todo.get_project());
+        assert_eq!(vec!["phone", "weekly"], *todo.get_tags());
+        assert_eq!(Utc.with_ymd_and_hms(2026, 10, 20, 22, 0, 0).unwrap(), todo.get_due_in_localtime().unwrap());
+        assert!(!todo.get_completed());
+        let todo = &result.get_todos()[1];
+        assert_eq!(Priority::Low, todo.get_priority());
+        assert!(todo.get_completed());
+        assert_eq!(Utc.with_ymd_and_hms(2026, 10, 3, 18, 0, 0).unwrap(), todo.get_when_completed_in_localtime());
+        assert!(result.get_issues().is_empty());
+    }
+
+    #[test]
+    fn import_with_single_line_array_return_todos() {
+        let content = format!("[{{\"description\":\"Call mom\",\"entry\":\"20261001T093000Z\",\"status\":\"pending\",\"uuid\":\"{}\"}},\
+                               {{\"description\":\"Old\",\"entry\":\"20261001T093000Z\",\"status\":\"deleted\",\"uuid\":\"2\"}}]", UUID);
+        let result = TaskwarriorFormat {}.import(&content).unwrap();
+        assert_eq!(1, result.get_todos().len());
+        assert_eq!(UUID, result.get_todos()[0].get_uuid());
+        assert_eq!(1, result.get_issues().len());
+        assert!(result.get_issues()[0].starts_with("Line 1: The task is deleted and has been skipped"));
+    }
+
+    #[test]
+    fn import_with_pretty_printed_array_return_todos() {
+        let content = format!("[\n  {{\n    \"description\": \"Call mom\",\n    \"entry\": \"20261001T093000Z\",\n    \"status\": \"pending\",\n\
+                               \"tags\": [\n      \"phone\"\n    ],\n    \"uuid\": \"{}\"\n  }},\n  {{\n    \"description\": \"No status\",\n\
+                               \"uuid\": \"1f0e5d7a-3b2c-4d1e-8f9a-0b1c2d3e4f50\"\n  }}\n]\n", UUID);
+        let result = TaskwarriorFormat {}.import(&content).unwrap();
+        assert_eq!(1, result.get_todos().len());
+        assert_eq!("Call mom", result.get_todos()[0].get_title());
+        assert_eq!(vec!["phone"], *result.get_todos()[0].get_tags());
+        assert_eq!(1, result.get_issues().len());
+        assert!(result.get_issues()[0].starts_with("Line 13: The task is invalid (missing field `status`)"));
+    }
+
+    #[test]
+    fn import_with_unsupported_tasks_return_issues() {
+        let content = "{\"description\":\"Old\",\"entry\":\"20261001T093000Z\",\"status\":\"deleted\",\"uuid\":\"1\"}\n\
+                       {\"description\":\"Weekly\",\"entry\":\"20261001T093000Z\",\"status\":\"recurring\",\"uuid\":\"2\"}\n\
+                       {\"description\":\"Someday\",\"entry\":\"20261001T093000Z\",\"status\":\"waiting\",\"priority\":\"X\",\"uuid\":\"3\"}\n\
+                       not json\n";
+        let result = TaskwarriorFormat {}.import(content).unwrap();
+        assert_eq!(1, result.get_todos().len());
+        assert!(!result.get_todos()[0].get_completed());
+        let issues = result.get_issues();
+        assert_eq!(5, issues.len());
+        assert!(issues[0].starts_with("Line 1: The task is deleted and has been skipped"));
+        assert!(issues[1].starts_with("Line 2: Recurring tasks aren't supported"));
+        assert!(issues[2].starts_with("Line 3: The priority X has no equivalent and has been imported as Low"));
+        assert!(issues[3].starts_with("Line 3: The task has no valid uuid"));
+        assert!(issues[4].starts_with("Line 4: The task is invalid"));
+    }
+
+    #[test]
+    fn export_return_one_task_per_line() {
+        let created = Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap();
+        let mut todo = Todo::new(1, "Call mom", Priority::Medium, created).unwrap();
+        todo.set_uuid(UUID).unwrap();
+        todo.set_tags(vec![String::from("phone")]);
+        todo.set_completed(true, Some(Utc.with_ymd_and_hms(2026, 10, 3, 18, 0, 0).unwrap()));
+        assert_eq!(format!("[\n{{\"uuid\":\"{}\",\"description\":\"Call mom\",\"status\":\"completed\",\"entry\":\"20261001T093000Z\",\
+                            \"end\":\"20261003T180000Z\",\"priority\":\"M\",\"tags\":[\"phone\"]}}\n]\n", UUID),
+                   TaskwarriorFormat {}.export(&[todo]).unwrap());
+    }
+
+    #[test]
+    fn export_then_import_return_same_uuids() {
+        let todos = vec![Todo::new(1, "Call mom", Priority::High, Utc::now()).unwrap(),
+                         Todo::new(2, "Pay the bills", Priority::Low, Utc::now()).unwrap()];
+        let content = TaskwarriorFormat {}.export(&todos).unwrap();
+        let result = TaskwarriorFormat {}.import(&content).unwrap();
+        for (expected, actual) in todos.iter().zip(result.get_todos()) {
+            assert_eq!(expected.get_uuid(), actual.get_uuid());
+            assert_eq!(expected.get_title(), actual.get_title());
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::common_structs::Priority;
-use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use chrono::{DateTime, Duration, Local, Utc};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
+use uuid::Uuid;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Todo {
@@ -12,7 +13,119 @@ pub struct Todo {
     when_created_utc: DateTime<Utc>,
     completed: bool,
     #[serde(with = "utc_date_formatter")]
-    when_completed_utc: DateTime<Utc>
+    when_completed_utc: DateTime<Utc>,
+    #[serde(default)]
+    time_entries: Vec<TimeEntry>,
+    #[serde(default)]
+    estimate_minutes: Option<i64>,
+    #[serde(default = "generate_uuid")]
+    uuid: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default, with = "optional_utc_date_formatter")]
+    due_utc: Option<DateTime<Utc>>,
+    #[serde(default, with = "optional_utc_date_formatter")]
+    wait_until_utc: Option<DateTime<Utc>>,
+    #[serde(default)]
+    history: Vec<Change>
+}
+
+fn generate_uuid() -> String {
+    Uuid::new_v4().to_string()
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TimeEntry {
+    #[serde(with = "utc_date_formatter")]
+    start_utc: DateTime<Utc>,
+    #[serde(default, with = "optional_utc_date_formatter")]
+    end_utc: Option<DateTime<Utc>>
+}
+
+impl TimeEntry {
+    pub fn is_active(&self) -> bool {
+        self.end_utc.is_none()
+    }
+
+    pub fn get_duration(&self, now: DateTime<Utc>) -> Duration {
+        self.end_utc.unwrap_or(now) - self.start_utc
+    }
+}
+
+// A change in the history of a todo, the field is created, title, priority or status
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Change {
+    #[serde(with = "utc_date_formatter")]
+    when_utc: DateTime<Utc>,
+    user: String,
+    field: String,
+    #[serde(default)]
+    old_value: Option<String>,
+    #[serde(default)]
+    new_value: Option<String>
+}
+
+impl Change {
+    pub fn new(when_utc: DateTime<Utc>, user: &str, field: &str, old_value: Option<String>, new_value: Option<String>) -> Change {
+        Change { when_utc, user: user.to_string(), field: field.to_string(), old_value, new_value }
+    }
+
+    pub fn get_when_in_localtime(&self) -> DateTime<Local> {
+        DateTime::from(self.when_utc)
+    }
+
+    pub fn get_user(&self) -> &str {
+        self.user.as_str()
+    }
+
+    // Describe the change like: Priority changed from Low to High
+    pub fn get_description(&self) -> String {
+        let old_value = self.old_value.as_deref().unwrap_or("");
+        let new_value = self.new_value.as_deref().unwrap_or("");
+        match self.field.as_str() {
+            "created" => String::from("Created"),
+            "status" if new_value == "completed" => String::from("Completed"),
+            "status" => String::from("Reopened"),
+            "title" => format!("Title changed from \"{}\" to \"{}\"", old_value, new_value),
+            "due" if old_value.is_empty() => format!("Due on {}", new_value),
+            "due" if new_value.is_empty() => String::from("Due date removed"),
+            "wait" if new_value.is_empty() => String::from("Snooze cleared"),
+            "wait" => format!("Snoozed until {}", new_value),
+            field => format!("{}{} changed from {} to {}", field[..1].to_uppercase(), &field[1..], old_value, new_value)
+        }
+    }
+}
+
+// A removed todo kept in the trash until it's restored, emptied or expired
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TrashedTodo {
+    todo: Todo,
+    #[serde(with = "utc_date_formatter")]
+    when_deleted_utc: DateTime<Utc>
+}
+
+impl TrashedTodo {
+    pub fn new(todo: Todo, when_deleted_utc: DateTime<Utc>) -> TrashedTodo {
+        TrashedTodo { todo, when_deleted_utc }
+    }
+
+    pub fn get_todo(&self) -> &Todo {
+        &self.todo
+    }
+
+    pub fn into_todo(self) -> Todo {
+        self.todo
+    }
+
+    pub fn get_when_deleted(&self) -> DateTime<Utc> {
+        self.when_deleted_utc
+    }
+
+    pub fn get_when_deleted_in_localtime(&self) -> DateTime<Local> {
+        DateTime::from(self.when_deleted_utc)
+    }
 }
 
 impl Todo {
@@ -21,12 +134,20 @@ impl Todo {
             return Err("Title is required".into())
         }
 
-        Ok(Todo { id: id, 
+        Ok(Todo { id, 
                   title: title.to_string(), 
-                  priority: priority,
-                  when_created_utc: when_created_utc,
+                  priority,
+                  when_created_utc,
                   completed: false,
-                  when_completed_utc: Todo::get_default_completed_date()
+                  when_completed_utc: Todo::get_default_completed_date(),
+                  time_entries: vec![],
+                  estimate_minutes: None,
+                  uuid: generate_uuid(),
+                  tags: vec![],
+                  project: None,
+                  due_utc: None,
+                  wait_until_utc: None,
+                  history: vec![]
                 })
     }
 
@@ -66,6 +187,10 @@ impl Todo {
         Ok(())
     }
 
+    pub fn set_when_created(&mut self, when_created: DateTime<Utc>) {
+        self.when_created_utc = when_created;
+    }
+
     pub fn set_priority(&mut self, priority: Priority) {
         self.priority = priority
     }
@@ -78,16 +203,195 @@ impl Todo {
         }
     }
 
+    pub fn get_time_entries(&self) -> &Vec<TimeEntry> {
+        &self.time_entries
+    }
+
+    pub fn is_tracking(&self) -> bool {
+        self.time_entries.iter().any(|x| x.is_active())
+    }
+
+    pub fn start_tracking(&mut self, when: DateTime<Utc>) -> Result<(), Box<dyn Error>> {
+        if self.is_tracking() {
+            return Err(format!("The todo {} is already being tracked", self.id).into())
+        }
+        self.time_entries.push(TimeEntry { start_utc: when, end_utc: None });
+        Ok(())
+    }
+
+    pub fn stop_tracking(&mut self, when: DateTime<Utc>) -> Result<Duration, Box<dyn Error>> {
+        match self.time_entries.iter_mut().find(|x| x.is_active()) {
+            Some(entry) => {
+                entry.end_utc = Some(when.max(entry.start_utc));
+                Ok(entry.get_duration(when))
+            },
+            None => Err(format!("The todo {} is not being tracked", self.id).into())
+        }
+    }
+
+    pub fn log_time(&mut self, duration: Duration, when_logged: DateTime<Utc>) -> Result<(), Box<dyn Error>> {
+        if duration <= Duration::zero() {
+            return Err("The time logged must be greater than zero".into())
+        }
+        let start_utc = match when_logged.checked_sub_signed(duration) {
+            Some(start_utc) => start_utc,
+            None => return Err("The time logged is too long".into())
+        };
+        self.time_entries.push(TimeEntry { start_utc, end_utc: Some(when_logged) });
+        Ok(())
+    }
+
+    pub fn get_time_spent(&self, now: DateTime<Utc>) -> Duration {
+        self.time_entries.iter()
+            .fold(Duration::zero(), |total, x| total + x.get_duration(now))
+    }
+
+    pub fn get_estimate(&self) -> Option<Duration> {
+        self.estimate_minutes.map(Duration::minutes)
+    }
+
+    pub fn set_estimate(&mut self, estimate: Option<Duration>) -> Result<(), Box<dyn Error>> {
+        if let Some(duration) = estimate {
+            if duration <= Duration::zero() {
+                return Err("The estimate must be greater than zero".into())
+            }
+        }
+        self.estimate_minutes = estimate.map(|x| x.num_minutes());
+        Ok(())
+    }
+
+    // Return the time tracked on a completed todo, or the time between its creation
+    // and its completion when no time has been tracked.
+    pub fn get_actual_duration(&self) -> Option<Duration> {
+        if !self.completed {
+            return None
+        }
+        if self.time_entries.is_empty() {
+            Some(self.when_completed_utc - self.when_created_utc)
+        }
+        else {
+            Some(self.get_time_spent(self.when_completed_utc))
+        }
+    }
+
+    pub fn get_uuid(&self) -> &str {
+        self.uuid.as_str()
+    }
+
+    pub fn set_uuid(&mut self, uuid: &str) -> Result<(), Box<dyn Error>> {
+        match Uuid::parse_str(uuid.trim()) {
+            Ok(uuid) => {
+                self.uuid = uuid.to_string();
+                Ok(())
+            },
+            Err(_) => Err(format!("Invalid uuid {}", uuid).into())
+        }
+    }
+
+    pub fn get_tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
+    pub fn get_project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
+
+    pub fn set_project(&mut self, project: Option<&str>) {
+        self.project = project.map(|x| x.trim().to_string()).filter(|x| !x.is_empty());
+    }
+
+    pub fn get_due_in_localtime(&self) -> Option<DateTime<Local>> {
+        self.due_utc.map(DateTime::from)
+    }
+
+    pub fn set_due(&mut self, due: Option<DateTime<Utc>>) {
+        self.due_utc = due;
+    }
+
+    pub fn get_wait_until_in_localtime(&self) -> Option<DateTime<Local>> {
+        self.wait_until_utc.map(DateTime::from)
+    }
+
+    pub fn set_wait_until(&mut self, wait_until: Option<DateTime<Utc>>) {
+        self.wait_until_utc = wait_until;
+    }
+
+    // A snoozed todo is hidden from list and next until its wait date
+    pub fn is_waiting(&self, now: DateTime<Utc>) -> bool {
+        !self.completed && self.wait_until_utc.map(|x| x > now).unwrap_or(false)
+    }
+
+    // The wait date has passed, the todo is marked until it's snoozed again or cleared
+    pub fn has_reappeared(&self, now: DateTime<Utc>) -> bool {
+        !self.completed && self.wait_until_utc.map(|x| x <= now).unwrap_or(false)
+    }
+
+    // Replace the content of the todo by the content of the same todo imported again,
+    // keeping the id and the time tracked.
+    pub fn update_from(&mut self, imported: &Todo) {
+        self.title = imported.title.clone();
+        self.priority = imported.priority;
+        self.when_created_utc = imported.when_created_utc;
+        self.completed = imported.completed;
+        self.when_completed_utc = imported.when_completed_utc;
+        if imported.estimate_minutes.is_some() {
+            self.estimate_minutes = imported.estimate_minutes;
+        }
+        self.tags = imported.tags.clone();
+        self.project = imported.project.clone();
+        self.due_utc = imported.due_utc;
+        self.wait_until_utc = imported.wait_until_utc;
+    }
+
+    pub fn get_history(&self) -> &Vec<Change> {
+        &self.history
+    }
+
+    // Append the changes made since the previous version of the todo to its history.
+    // A todo without a previous version and without history is recorded as created.
+    pub fn record_changes(&mut self, previous: Option<&Todo>, when: DateTime<Utc>, user: &str) {
+        let previous = match previous {
+            Some(previous) => previous,
+            None => {
+                if self.history.is_empty() {
+                    self.history.push(Change::new(self.when_created_utc, user, "created", None, None));
+                }
+                return;
+            }
+        };
+        if previous.title != self.title {
+            self.history.push(Change::new(when, user, "title", Some(previous.title.clone()), Some(self.title.clone())));
+        }
+        if previous.priority != self.priority {
+            self.history.push(Change::new(when, user, "priority", Some(previous.priority.to_string()), Some(self.priority.to_string())));
+        }
+        if previous.completed != self.completed {
+            let status = |completed: bool| String::from(if completed { "completed" } else { "open" });
+            self.history.push(Change::new(when, user, "status", Some(status(previous.completed)), Some(status(self.completed))));
+        }
+        let format = |date: Option<DateTime<Local>>| date.map(|x| x.format("%Y-%m-%d %H:%M").to_string());
+        if previous.due_utc != self.due_utc {
+            self.history.push(Change::new(when, user, "due", format(previous.get_due_in_localtime()), format(self.get_due_in_localtime())));
+        }
+        if previous.wait_until_utc != self.wait_until_utc {
+            self.history.push(Change::new(when, user, "wait", format(previous.get_wait_until_in_localtime()), format(self.get_wait_until_in_localtime())));
+        }
+    }
+
     pub fn get_default_completed_date() -> DateTime<Utc> {
-        DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(0, 0), Utc)
+        DateTime::<Utc>::UNIX_EPOCH
     }
 }
 
 mod utc_date_formatter {
-    use chrono::{DateTime, Utc, TimeZone};
+    use chrono::{DateTime, NaiveDateTime, Utc};
     use serde::{self, Deserialize, Serializer, Deserializer};
 
-    const FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+    const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
     pub fn serialize<S>(
         date: &DateTime<Utc>,
@@ -107,7 +411,43 @@ mod utc_date_formatter {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        Utc.datetime_from_str(&s, FORMAT).map_err(serde::de::Error::custom)
+        NaiveDateTime::parse_from_str(&s, FORMAT)
+            .map(|date| date.and_utc())
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+mod optional_utc_date_formatter {
+    use chrono::{DateTime, NaiveDateTime, Utc};
+    use serde::{self, Deserialize, Serializer, Deserializer};
+
+    const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+    pub fn serialize<S>(
+        date: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match date {
+            Some(date) => serializer.serialize_str(&format!("{}", date.format(FORMAT))),
+            None => serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) => NaiveDateTime::parse_from_str(&s, FORMAT)
+                .map(|date| Some(date.and_utc()))
+                .map_err(serde::de::Error::custom),
+            None => Ok(None)
+        }
     }
 }
 
@@ -115,7 +455,7 @@ mod utc_date_formatter {
 mod tests {
     use crate::common_structs::Priority;
     use crate::todo::Todo;
-    use chrono::{DateTime, Local, Utc, TimeZone};
+    use chrono::{DateTime, Duration, Local, Utc, TimeZone};
 
     fn get_sample_todo() -> Todo {
         Todo::new(1, "Test", Priority::Low, Utc::now()).unwrap()
@@ -161,7 +501,7 @@ mod tests {
     #[test]
     fn todo_get_completed_return_false() {
         let actual = get_sample_todo();
-        assert_eq!(false, actual.get_completed());
+        assert!(!actual.get_completed());
     }
 
     #[test]
@@ -212,13 +552,156 @@ mod tests {
 
     #[test]
     fn todo_set_completed_with_true_return_success() {
-        let completed_date = Utc.ymd(1970, 2, 2).and_hms(1, 1, 1);
+        let completed_date = Utc.with_ymd_and_hms(1970, 2, 2, 1, 1, 1).unwrap();
         let mut actual = get_sample_todo();
 
-        assert_eq!(false, actual.get_completed());
+        assert!(!actual.get_completed());
         assert_eq!(Todo::get_default_completed_date(), actual.get_when_completed_in_localtime());
         actual.set_completed(true, Some(completed_date));
-        assert_eq!(true, actual.get_completed());
+        assert!(actual.get_completed());
         assert_eq!(completed_date, actual.get_when_completed_in_localtime());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn todo_start_tracking_twice_return_error() {
+        let mut actual = get_sample_todo();
+        actual.start_tracking(Utc::now()).unwrap();
+        assert!(actual.is_tracking());
+        assert_eq!("The todo 1 is already being tracked", actual.start_tracking(Utc::now()).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn todo_stop_tracking_return_duration() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
+        let mut actual = get_sample_todo();
+        actual.start_tracking(start).unwrap();
+        assert_eq!(Duration::minutes(30), actual.stop_tracking(start + Duration::minutes(30)).unwrap());
+        assert!(!actual.is_tracking());
+    }
+
+    #[test]
+    fn todo_stop_tracking_without_active_entry_return_error() {
+        let mut actual = get_sample_todo();
+        assert_eq!("The todo 1 is not being tracked", actual.stop_tracking(Utc::now()).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn todo_get_time_spent_with_logged_and_active_entries_return_total() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
+        let mut actual = get_sample_todo();
+        actual.log_time(Duration::minutes(45), now - Duration::hours(2)).unwrap();
+        actual.start_tracking(now - Duration::minutes(15)).unwrap();
+        assert_eq!(Duration::minutes(60), actual.get_time_spent(now));
+    }
+
+    #[test]
+    fn todo_log_time_with_zero_return_error() {
+        let mut actual = get_sample_todo();
+        assert_eq!("The time logged must be greater than zero", actual.log_time(Duration::zero(), Utc::now()).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn todo_deserialize_without_time_entries_return_empty_entries() {
+        let json = r#"{"id":1,"title":"Test","priority":"Low","when_created_utc":"2026-01-01 10:00:00","completed":false,"when_completed_utc":"1970-01-01 00:00:00"}"#;
+        let actual: Todo = serde_json::from_str(json).unwrap();
+        assert!(actual.get_time_entries().is_empty());
+    }
+
+    #[test]
+    fn todo_deserialize_without_uuid_return_generated_uuid() {
+        let json = r#"{"id":1,"title":"Test","priority":"Low","when_created_utc":"2026-01-01 10:00:00","completed":false,"when_completed_utc":"1970-01-01 00:00:00"}"#;
+        let actual: Todo = serde_json::from_str(json).unwrap();
+        assert_eq!(36, actual.get_uuid().len());
+        assert!(actual.get_tags().is_empty());
+        assert!(actual.get_project().is_none());
+        assert!(actual.get_due_in_localtime().is_none());
+    }
+
+    #[test]
+    fn todo_set_uuid_with_invalid_uuid_return_error() {
+        let mut actual = get_sample_todo();
+        assert_eq!("Invalid uuid 1234", actual.set_uuid("1234").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn todo_update_from_keep_id_and_time_entries() {
+        let created = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
+        let mut actual = Todo::new(3, "Test", Priority::Low, created).unwrap();
+        actual.log_time(Duration::minutes(40), created + Duration::hours(1)).unwrap();
+        let mut imported = Todo::new(0, "Test updated", Priority::High, created).unwrap();
+        imported.set_tags(vec![String::from("work")]);
+        imported.set_project(Some("beaver"));
+        actual.update_from(&imported);
+        assert_eq!(3, actual.get_id());
+        assert_eq!("Test updated", actual.get_title());
+        assert_eq!(Priority::High, actual.get_priority());
+        assert_eq!(vec!["work"], *actual.get_tags());
+        assert_eq!(Some("beaver"), actual.get_project());
+        assert_eq!(1, actual.get_time_entries().len());
+    }
+
+    #[test]
+    fn todo_serialize_with_active_entry_return_null_end() {
+        let mut actual = get_sample_todo();
+        actual.start_tracking(Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap()).unwrap();
+        let json = serde_json::to_string(&actual).unwrap();
+        assert!(json.contains(r#""time_entries":[{"start_utc":"2026-01-01 10:00:00","end_utc":null}]"#));
+        let deserialized: Todo = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.is_tracking());
+    }
+
+    #[test]
+    fn todo_set_estimate_with_zero_return_error() {
+        let mut actual = get_sample_todo();
+        assert_eq!("The estimate must be greater than zero", actual.set_estimate(Some(Duration::zero())).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn todo_set_estimate_with_2h_return_success() {
+        let mut actual = get_sample_todo();
+        assert!(actual.get_estimate().is_none());
+        actual.set_estimate(Some(Duration::hours(2))).unwrap();
+        assert_eq!(Duration::hours(2), actual.get_estimate().unwrap());
+    }
+
+    #[test]
+    fn todo_get_actual_duration_with_open_todo_return_none() {
+        assert!(get_sample_todo().get_actual_duration().is_none());
+    }
+
+    #[test]
+    fn todo_get_actual_duration_without_time_entries_return_completion_duration() {
+        let created = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
+        let mut actual = Todo::new(1, "Test", Priority::Low, created).unwrap();
+        actual.set_completed(true, Some(created + Duration::hours(5)));
+        assert_eq!(Duration::hours(5), actual.get_actual_duration().unwrap());
+    }
+
+    #[test]
+    fn todo_get_actual_duration_with_time_entries_return_tracked_time() {
+        let created = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
+        let mut actual = Todo::new(1, "Test", Priority::Low, created).unwrap();
+        actual.log_time(Duration::minutes(40), created + Duration::hours(1)).unwrap();
+        actual.set_completed(true, Some(created + Duration::hours(5)));
+        assert_eq!(Duration::minutes(40), actual.get_actual_duration().unwrap());
+    }
+
+    #[test]
+    fn todo_record_changes_with_completion_and_reopen_return_status_changes() {
+        let created = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
+        let mut actual = Todo::new(1, "Test", Priority::Low, created).unwrap();
+        actual.record_changes(None, created, "alice");
+        let open = actual.clone();
+        actual.set_completed(true, Some(created + Duration::hours(1)));
+        actual.record_changes(Some(&open), created + Duration::hours(1), "alice");
+        let completed = actual.clone();
+        actual.set_completed(false, None);
+        actual.record_changes(Some(&completed), created + Duration::hours(2), "bob");
+        actual.record_changes(Some(&actual.clone()), created + Duration::hours(3), "bob");
+        actual.record_changes(None, created + Duration::hours(3), "bob");
+        let descriptions: Vec<String> = actual.get_history().iter().map(|x| x.get_description()).collect();
+        assert_eq!(vec!["Created", "Completed", "Reopened"], descriptions);
+        assert_eq!(created, actual.get_history()[0].get_when_in_localtime());
+        assert_eq!("bob", actual.get_history()[2].get_user());
+    }
+}
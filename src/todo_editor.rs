@@ -0,0 +1,438 @@
+use crate::common_structs::Priority;
+use crate::duration_format::{format_duration, parse_date_time, parse_duration};
+use crate::todo::Todo;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::process;
+use uuid::Uuid;
+
+const ERROR_PREFIX: &str = "# ERROR:";
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const DUE_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug, PartialEq)]
+pub struct FieldError {
+    line: Option<usize>,
+    message: String
+}
+
+impl FieldError {
+    fn new(line: Option<usize>, message: &str) -> FieldError {
+        FieldError { line, message: message.to_string() }
+    }
+}
+
+// Serialize a todo into an editable document of `key = value` lines.
+pub fn to_document(todo: &Todo) -> String {
+    let mut document = String::new();
+    document.push_str(&format!("# Editing the todo with id {}\n", todo.get_id()));
+    document.push_str("# Lines starting with '#' are ignored. Save an empty file to cancel.\n");
+    document.push_str("# The id and created fields are read-only.\n");
+    document.push_str(&format!("id = {}\n", todo.get_id()));
+    document.push_str(&format!("title = {}\n", quote(todo.get_title())));
+    document.push_str("# Possible values are H, M and L for High, Medium and Low\n");
+    document.push_str(&format!("priority = {}\n", quote(todo.get_priority().to_letter())));
+    document.push_str("# Estimated duration like 45m, 2h or 1h30m. Leave empty for no estimate\n");
+    document.push_str(&format!("estimate = {}\n", quote(&todo.get_estimate().map(|x| format_duration(&x).replace(' ', "")).unwrap_or_default())));
+    document.push_str("# Date like 2026-11-05, 2026-11-05 14:00, tomorrow or 3d. Leave empty for no due date\n");
+    document.push_str(&format!("due = {}\n", quote(&format_date(todo.get_due_in_localtime()))));
+    document.push_str("# The date a snoozed todo comes back, like the due date. Leave empty to not snooze it\n");
+    document.push_str(&format!("wait = {}\n", quote(&format_date(todo.get_wait_until_in_localtime()))));
+    document.push_str("# Tags separated by commas like home, phone\n");
+    document.push_str(&format!("tags = {}\n", quote(&todo.get_tags().join(", "))));
+    document.push_str(&format!("project = {}\n", quote(todo.get_project().unwrap_or_default())));
+    document.push_str(&format!("completed = {}\n", todo.get_completed()));
+    document.push_str(&format!("created = {}\n", quote(&todo.get_when_created_in_localtime().format(DATE_FORMAT).to_string())));
+    document
+}
+
+// Validate the document and apply every changed field to the todo.
+// The todo is left untouched if any field is invalid.
+// Returns the name of the fields that have been changed.
+pub fn apply_document(todo: &mut Todo, document: &str) -> Result<Vec<String>, Vec<FieldError>> {
+    let (fields, mut errors) = parse_document(document);
+    let mut updated = todo.clone();
+    let mut changes: Vec<String> = vec![];
+    for (key, (line, value)) in &fields {
+        let line = Some(*line);
+        match key.as_str() {
+            "id" => {
+                if value != &todo.get_id().to_string() {
+                    errors.push(FieldError::new(line, "The id field is read-only"));
+                }
+            },
+            "created" => {
+                if value != &todo.get_when_created_in_localtime().format(DATE_FORMAT).to_string() {
+                    errors.push(FieldError::new(line, "The created field is read-only"));
+                }
+            },
+            "title" => {
+                if value != todo.get_title() {
+                    match updated.set_title(value) {
+                        Ok(_) => changes.push(key.to_string()),
+                        Err(e) => errors.push(FieldError::new(line, &e.to_string()))
+                    }
+                }
+            },
+            "priority" => match Priority::from_string(value) {
+                Some(priority) => {
+                    if priority != todo.get_priority() {
+                        updated.set_priority(priority);
+                        changes.push(key.to_string());
+                    }
+                },
+                None => errors.push(FieldError::new(line, "Invalid priority value. Must be H, M or L"))
+            },
+            "estimate" => {
+                let estimate = match value.is_empty() {
+                    true => Ok(None),
+                    false => parse_duration(value).map(Some)
+                };
+                match estimate {
+                    Ok(estimate) => {
+                        if estimate != todo.get_estimate() {
+                            match updated.set_estimate(estimate) {
+                                Ok(_) => changes.push(key.to_string()),
+                                Err(e) => errors.push(FieldError::new(line, &e.to_string()))
+                            }
+                        }
+                    },
+                    Err(e) => errors.push(FieldError::new(line, &e.to_string()))
+                }
+            },
+            "due" | "wait" => {
+                let current = match key.as_str() {
+                    "due" => todo.get_due_in_localtime(),
+                    _ => todo.get_wait_until_in_localtime()
+                };
+                // The dates are compared as displayed, an unchanged date keeps its seconds
+                if value != &format_date(current) {
+                    let date = match value.is_empty() {
+                        true => Ok(None),
+                        false => parse_date_time(value, Local::now()).map(Some)
+                    };
+                    match date {
+                        Ok(date) => {
+                            match key.as_str() {
+                                "due" => updated.set_due(date),
+                                _ => updated.set_wait_until(date)
+                            }
+                            changes.push(key.to_string());
+                        },
+                        Err(e) => errors.push(FieldError::new(line, &e.to_string()))
+                    }
+                }
+            },
+            "tags" => {
+                let tags: Vec<String> = value.split(',').map(|x| x.trim().to_string()).filter(|x| !x.is_empty()).collect();
+                if &tags != todo.get_tags() {
+                    updated.set_tags(tags);
+                    changes.push(key.to_string());
+                }
+            },
+            "project" => {
+                if value.trim() != todo.get_project().unwrap_or_default() {
+                    updated.set_project(Some(value));
+                    changes.push(key.to_string());
+                }
+            },
+            "completed" => match value.as_str() {
+                "true" | "false" => {
+                    let completed = value == "true";
+                    if completed != todo.get_completed() {
+                        updated.set_completed(completed, None);
+                        changes.push(key.to_string());
+                    }
+                },
+                _ => errors.push(FieldError::new(line, "Invalid completed value. Must be true or false"))
+            },
+            _ => errors.push(FieldError::new(line, &format!("Unknown field {}", key)))
+        }
+    }
+    if !errors.is_empty() {
+        errors.sort_by_key(|e| e.line);
+        return Err(errors);
+    }
+    changes.sort();
+    *todo = updated;
+    Ok(changes)
+}
+
+fn format_date(date: Option<DateTime<Local>>) -> String {
+    date.map(|x| x.format(DUE_DATE_FORMAT).to_string()).unwrap_or_default()
+}
+
+// Rebuild the document with an error comment inserted above each invalid line.
+// Error comments from a previous attempt are removed first.
+pub fn annotate_errors(document: &str, errors: &[FieldError]) -> String {
+    let mut annotated = String::new();
+    for error in errors.iter().filter(|e| e.line.is_none()) {
+        annotated.push_str(&format!("{} {}\n", ERROR_PREFIX, error.message));
+    }
+    for (index, line) in document.lines().enumerate() {
+        if line.trim_start().starts_with(ERROR_PREFIX) {
+            continue;
+        }
+        for error in errors.iter().filter(|e| e.line == Some(index)) {
+            annotated.push_str(&format!("{} {}\n", ERROR_PREFIX, error.message));
+        }
+        annotated.push_str(line);
+        annotated.push('\n');
+    }
+    annotated
+}
+
+// Open the content in the user editor ($VISUAL, $EDITOR or vi) and return the saved content.
+pub fn edit_in_editor(content: &str, file_name: &str) -> Result<String, Box<dyn Error>> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| String::from("vi"));
+    let mut editor_parts = editor.split_whitespace();
+    let program = match editor_parts.next() {
+        Some(program) => program,
+        None => return Err("The EDITOR environment variable is empty".into())
+    };
+    // A new file with a random name, only readable by the user, so another user can't replace it by a link
+    let file_path = env::temp_dir().join(format!("{}-{}.toml", file_name, Uuid::new_v4()));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&file_path)?
+        .write_all(content.as_bytes())?;
+    let status = process::Command::new(program)
+        .args(editor_parts)
+        .arg(&file_path)
+        .status();
+    let result = match status {
+        Ok(status) if status.success() => fs::read_to_string(&file_path).map_err(|e| e.into()),
+        Ok(status) => Err(format!("The editor {} exited with {}", program, status).into()),
+        Err(e) => Err(format!("Unable to launch the editor {}: {}", program, e).into())
+    };
+    let _ = fs::remove_file(&file_path);
+    result
+}
+
+// Return every `key = value` of the document indexed by key with its line number.
+fn parse_document(document: &str) -> (HashMap<String, (usize, String)>, Vec<FieldError>) {
+    let mut fields: HashMap<String, (usize, String)> = HashMap::new();
+    let mut errors: Vec<FieldError> = vec![];
+    for (index, line) in document.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let (key, raw_value) = match trimmed.split_once('=') {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+            None => {
+                errors.push(FieldError::new(Some(index), "Expected a line in the format key = value"));
+                continue;
+            }
+        };
+        let value = match unquote(raw_value) {
+            Ok(value) => value,
+            Err(message) => {
+                errors.push(FieldError::new(Some(index), &message));
+                continue;
+            }
+        };
+        if fields.contains_key(&key) {
+            errors.push(FieldError::new(Some(index), &format!("The field {} is defined more than once", key)));
+            continue;
+        }
+        fields.insert(key, (index, value));
+    }
+    (fields, errors)
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn unquote(value: &str) -> Result<String, String> {
+    if !value.starts_with('"') {
+        return Ok(value.to_string());
+    }
+    if value.len() < 2 || !value.ends_with('"') {
+        return Err(String::from("Missing closing quote"));
+    }
+    let mut retval = String::new();
+    let mut chars = value[1..value.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(escaped) if escaped == '"' || escaped == '\\' => retval.push(escaped),
+                _ => return Err(String::from("Invalid escape sequence, only \\\" and \\\\ are supported"))
+            }
+        }
+        else if c == '"' {
+            return Err(String::from("Unescaped quote inside the value"));
+        }
+        else {
+            retval.push(c);
+        }
+    }
+    Ok(retval)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::todo::Todo;
+    use crate::todo_editor::{annotate_errors, apply_document, to_document, unquote};
+    use chrono::Utc;
+
+    fn get_sample_todo() -> Todo {
+        Todo::new(3, "Write the \"report\"", Priority::Low, Utc::now()).unwrap()
+    }
+
+    #[test]
+    fn apply_document_with_unchanged_document_return_no_changes() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo);
+        assert!(apply_document(&mut todo, &document).unwrap().is_empty());
+    }
+
+    #[test]
+    fn apply_document_with_title_and_priority_changed_return_changes() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo)
+            .replace("title = \"Write the \\\"report\\\"\"", "title = \"Another title\"")
+            .replace("priority = \"L\"", "priority = \"H\"");
+        assert_eq!(vec!["priority", "title"], apply_document(&mut todo, &document).unwrap());
+        assert_eq!("Another title", todo.get_title());
+        assert_eq!(Priority::High, todo.get_priority());
+    }
+
+    #[test]
+    fn apply_document_with_estimate_changed_return_changes() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo).replace("estimate = \"\"", "estimate = \"1h30m\"");
+        assert_eq!(vec!["estimate"], apply_document(&mut todo, &document).unwrap());
+        assert_eq!(90, todo.get_estimate().unwrap().num_minutes());
+        let document = to_document(&todo);
+        assert!(document.contains("estimate = \"1h30m\""));
+        assert!(apply_document(&mut todo, &document).unwrap().is_empty());
+    }
+
+    #[test]
+    fn apply_document_with_due_and_wait_changed_return_changes() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo)
+            .replace("due = \"\"", "due = \"2026-11-05 14:00\"")
+            .replace("wait = \"\"", "wait = \"2026-11-02\"");
+        assert_eq!(vec!["due", "wait"], apply_document(&mut todo, &document).unwrap());
+        assert_eq!("2026-11-05 14:00", todo.get_due_in_localtime().unwrap().format("%Y-%m-%d %H:%M").to_string());
+        assert_eq!("2026-11-02 00:00", todo.get_wait_until_in_localtime().unwrap().format("%Y-%m-%d %H:%M").to_string());
+        let document = to_document(&todo);
+        assert!(apply_document(&mut todo, &document).unwrap().is_empty());
+        let document = document.replace("due = \"2026-11-05 14:00\"", "due = \"\"");
+        assert_eq!(vec!["due"], apply_document(&mut todo, &document).unwrap());
+        assert_eq!(None, todo.get_due_in_localtime());
+    }
+
+    #[test]
+    fn apply_document_with_tags_and_project_changed_return_changes() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo)
+            .replace("tags = \"\"", "tags = \"home, phone,\"")
+            .replace("project = \"\"", "project = \" Family \"");
+        assert_eq!(vec!["project", "tags"], apply_document(&mut todo, &document).unwrap());
+        assert_eq!(&vec![String::from("home"), String::from("phone")], todo.get_tags());
+        assert_eq!(Some("Family"), todo.get_project());
+        let document = to_document(&todo);
+        assert!(document.contains("tags = \"home, phone\""));
+        assert!(apply_document(&mut todo, &document).unwrap().is_empty());
+    }
+
+    #[test]
+    fn apply_document_with_invalid_due_return_error() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo).replace("due = \"\"", "due = \"someday\"");
+        let errors = apply_document(&mut todo, &document).unwrap_err();
+        assert_eq!("Invalid date someday. Must be like 2026-11-05, 2026-11-05 14:00, tomorrow, 3d or 4h", errors[0].message);
+    }
+
+    #[test]
+    fn apply_document_with_completed_true_return_completed_todo() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo).replace("completed = false", "completed = true");
+        assert_eq!(vec!["completed"], apply_document(&mut todo, &document).unwrap());
+        assert!(todo.get_completed());
+    }
+
+    #[test]
+    fn apply_document_with_invalid_fields_return_errors_and_keep_todo() {
+        let mut todo = get_sample_todo();
+        let document = to_document(&todo)
+            .replace("title = \"Write the \\\"report\\\"\"", "title = \"  \"")
+            .replace("priority = \"L\"", "priority = \"Z\"")
+            .replace("id = 3", "id = 4");
+        let errors = apply_document(&mut todo, &document).unwrap_err();
+        assert_eq!(3, errors.len());
+        assert_eq!("The id field is read-only", errors[0].message);
+        assert_eq!("Title cannot be empty", errors[1].message);
+        assert_eq!("Invalid priority value. Must be H, M or L", errors[2].message);
+        assert_eq!("Write the \"report\"", todo.get_title());
+        assert_eq!(Priority::Low, todo.get_priority());
+    }
+
+    #[test]
+    fn apply_document_with_unknown_field_return_error() {
+        let mut todo = get_sample_todo();
+        let document = format!("{}color = blue\n", to_document(&todo));
+        let errors = apply_document(&mut todo, &document).unwrap_err();
+        assert_eq!("Unknown field color", errors[0].message);
+    }
+
+    #[test]
+    fn apply_document_with_malformed_line_return_error() {
+        let mut todo = get_sample_todo();
+        let document = format!("{}blabla\n", to_document(&todo));
+        let errors = apply_document(&mut todo, &document).unwrap_err();
+        assert_eq!("Expected a line in the format key = value", errors[0].message);
+    }
+
+    #[test]
+    fn apply_document_with_duplicate_field_return_error() {
+        let mut todo = get_sample_todo();
+        let document = format!("{}title = \"Again\"\n", to_document(&todo));
+        let errors = apply_document(&mut todo, &document).unwrap_err();
+        assert_eq!("The field title is defined more than once", errors[0].message);
+    }
+
+    #[test]
+    fn annotate_errors_insert_comment_above_invalid_line() {
+        let mut todo = get_sample_todo();
+        let document = "id = 3\npriority = Z\n";
+        let errors = apply_document(&mut todo, document).unwrap_err();
+        assert_eq!("id = 3\n# ERROR: Invalid priority value. Must be H, M or L\npriority = Z\n",
+                   annotate_errors(document, &errors));
+    }
+
+    #[test]
+    fn annotate_errors_remove_previous_error_comments() {
+        let document = "# ERROR: Old error\nid = 3\n";
+        assert_eq!("id = 3\n", annotate_errors(document, &[]));
+    }
+
+    #[test]
+    fn unquote_with_escaped_quote_return_value() {
+        assert_eq!("a \"b\" \\c", unquote("\"a \\\"b\\\" \\\\c\"").unwrap());
+    }
+
+    #[test]
+    fn unquote_with_missing_closing_quote_return_error() {
+        assert_eq!("Missing closing quote", unquote("\"abc").unwrap_err());
+    }
+
+    #[test]
+    fn unquote_with_bare_value_return_value() {
+        assert_eq!("H", unquote("H").unwrap());
+    }
+}
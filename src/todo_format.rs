@@ -0,0 +1,68 @@
+use crate::csv_format::CsvFormat;
+use crate::ics_format::IcsFormat;
+use crate::markdown_format::MarkdownFormat;
+use crate::org_format::OrgFormat;
+use crate::taskwarrior_format::TaskwarriorFormat;
+use crate::todo::Todo;
+use crate::todotxt_format::TodoTxtFormat;
+use std::collections::HashMap;
+use std::error::Error;
+
+pub struct ImportResult {
+    todos: Vec<Todo>,
+    issues: Vec<String>
+}
+
+impl ImportResult {
+    pub fn new() -> ImportResult {
+        ImportResult { todos: vec![], issues: vec![] }
+    }
+
+    pub fn get_todos(&self) -> &Vec<Todo> {
+        &self.todos
+    }
+
+    pub fn get_issues(&self) -> &Vec<String> {
+        &self.issues
+    }
+
+    pub fn add_todo(&mut self, todo: Todo) {
+        self.todos.push(todo);
+    }
+
+    // Record a line that couldn't be mapped, or only partially
+    pub fn add_issue(&mut self, line_number: usize, message: &str, line: &str) {
+        self.issues.push(format!("Line {}: {} ({})", line_number, message, line.trim()));
+    }
+}
+
+pub trait TodoFormat {
+    fn export(&self, todos: &[Todo]) -> Result<String, Box<dyn Error>>;
+    fn import(&self, content: &str) -> Result<ImportResult, Box<dyn Error>>;
+}
+
+// Return the format name and the file path. The file can either be the value of the
+// command or follow the format option (import --from=todotxt todo.txt).
+pub fn split_format_and_file(format_option: &str, value: &str) -> (String, String) {
+    let (format_name, file_path) = match format_option.trim().split_once(char::is_whitespace) {
+        Some((format_name, file_path)) => (format_name, file_path.trim()),
+        None => (format_option.trim(), "")
+    };
+    match file_path.is_empty() {
+        true => (format_name.to_string(), value.trim().to_string()),
+        false => (format_name.to_string(), file_path.to_string())
+    }
+}
+
+pub fn new_format(name: &str, options: &HashMap<String, String>) -> Result<Box<dyn TodoFormat>, Box<dyn Error>> {
+    match name.trim().to_lowercase().as_str() {
+        "csv" => Ok(Box::new(CsvFormat::new_from_options(options)?)),
+        "ics" => Ok(Box::new(IcsFormat {})),
+        "markdown" => Ok(Box::new(MarkdownFormat {})),
+        "org" => Ok(Box::new(OrgFormat {})),
+        "taskwarrior" => Ok(Box::new(TaskwarriorFormat {})),
+        "todotxt" => Ok(Box::new(TodoTxtFormat {})),
+        "" => Err("The format is required".into()),
+        _ => Err(format!("Unknown format {}. Must be csv, ics, markdown, org, taskwarrior or todotxt", name).into())
+    }
+}
@@ -0,0 +1,353 @@
+use crate::common_structs::{CommandResult, Priority};
+use crate::todo::Todo;
+use chrono::NaiveDate;
+use question::{Question, Answer};
+use std::collections::HashSet;
+use std::error::Error;
+use std::io;
+
+// The largest range of ids accepted (ex: 1-1000)
+const MAX_RANGE_SIZE: u32 = 1000;
+
+#[derive(Debug, PartialEq)]
+pub enum Criterion {
+    Priority(Priority),
+    TitleContains(String),
+    Completed(bool),
+    CreatedBefore(NaiveDate),
+    CreatedAfter(NaiveDate)
+}
+
+impl Criterion {
+    pub fn matches(&self, todo: &Todo) -> bool {
+        match self {
+            Criterion::Priority(priority) => todo.get_priority() == *priority,
+            Criterion::TitleContains(text) => todo.get_title().to_lowercase().contains(&text.to_lowercase()),
+            Criterion::Completed(completed) => todo.get_completed() == *completed,
+            Criterion::CreatedBefore(date) => todo.get_when_created_in_localtime().date_naive() < *date,
+            Criterion::CreatedAfter(date) => todo.get_when_created_in_localtime().date_naive() > *date
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TodoSelection {
+    Ids(Vec<u32>),
+    Criteria(Vec<Criterion>)
+}
+
+impl TodoSelection {
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<TodoSelection, Box<dyn Error>> {
+        let value = command_result.get_value().trim();
+        match command_result.get_options().get("where") {
+            Some(criteria) => {
+                if !value.is_empty() {
+                    return Err("The where option cannot be combined with a list of ids".into());
+                }
+                Ok(TodoSelection::Criteria(parse_criteria(criteria)?))
+            },
+            None => {
+                if value.is_empty() {
+                    return Err("Value cannot be empty".into());
+                }
+                Ok(TodoSelection::Ids(parse_ids(value)?))
+            }
+        }
+    }
+
+    // Return the indexes of the open todos selected, in the order of the selection.
+    // The criteria select the completed todos too when they have a status criterion.
+    pub fn find_indexes(&self, todos: &[Todo]) -> Result<Vec<usize>, Box<dyn Error>> {
+        match self {
+            TodoSelection::Ids(ids) => {
+                let mut indexes = vec![];
+                let mut missing_ids = vec![];
+                for id in ids {
+                    match todos.iter().position(|x| x.get_id() == *id && !x.get_completed()) {
+                        Some(index) => indexes.push(index),
+                        None => missing_ids.push(id.to_string())
+                    }
+                }
+                if !missing_ids.is_empty() {
+                    return Err(format!("Unable to find the todo with id {}", missing_ids.join(", ")).into());
+                }
+                Ok(indexes)
+            },
+            TodoSelection::Criteria(criteria) => {
+                let has_status = criteria.iter().any(|c| matches!(c, Criterion::Completed(_)));
+                let indexes: Vec<usize> = todos.iter()
+                    .enumerate()
+                    .filter(|(_, x)| (has_status || !x.get_completed()) && criteria.iter().all(|c| c.matches(x)))
+                    .map(|(i, _)| i)
+                    .collect();
+                if indexes.is_empty() {
+                    return Err("No todo matches the criteria".into());
+                }
+                Ok(indexes)
+            }
+        }
+    }
+}
+
+// Parse a list of ids separated by spaces or commas, where a-b is an inclusive range.
+pub fn parse_ids(value: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+    let mut ids: Vec<u32> = vec![];
+    let mut found_ids: HashSet<u32> = HashSet::new();
+    for token in value.split(|c: char| c == ',' || c.is_whitespace()).filter(|x| !x.is_empty()) {
+        let range: Vec<u32> = match token.find('-') {
+            Some(position) if position > 0 => {
+                let start = token[..position].parse::<u32>()?;
+                let end = token[position + 1..].parse::<u32>()?;
+                if start > end {
+                    return Err(format!("Invalid range {}. The start must be lower than the end", token).into());
+                }
+                if end - start >= MAX_RANGE_SIZE {
+                    return Err(format!("Invalid range {}. A range cannot contain more than {} ids", token, MAX_RANGE_SIZE).into());
+                }
+                (start..=end).collect()
+            },
+            _ => vec![token.parse::<u32>()?]
+        };
+        for id in range {
+            if found_ids.insert(id) {
+                ids.push(id);
+            }
+        }
+    }
+    if ids.is_empty() {
+        return Err("Value cannot be empty".into());
+    }
+    Ok(ids)
+}
+
+// Parse criteria separated by commas. Supported criteria are priority=x, title~text,
+// status=open|completed, created<yyyy-mm-dd and created>yyyy-mm-dd.
+pub fn parse_criteria(value: &str) -> Result<Vec<Criterion>, Box<dyn Error>> {
+    let mut criteria = vec![];
+    for clause in value.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+        let position = match clause.find(['=', '~', '<', '>']) {
+            Some(position) => position,
+            None => return Err(format!("Invalid criterion {}", clause).into())
+        };
+        let field = clause[..position].trim().to_lowercase();
+        let operator = &clause[position..position + 1];
+        let operand = clause[position + 1..].trim();
+        let criterion = match (field.as_str(), operator) {
+            ("priority", "=") => match Priority::from_string(operand) {
+                Some(priority) => Criterion::Priority(priority),
+                None => return Err("Invalid priority value. Must be H, M or L".into())
+            },
+            ("title", "~") if !operand.is_empty() => Criterion::TitleContains(operand.to_string()),
+            ("status", "=") => match operand.to_lowercase().as_str() {
+                "open" => Criterion::Completed(false),
+                "completed" => Criterion::Completed(true),
+                _ => return Err("Invalid status value. Must be open or completed".into())
+            },
+            ("created", "<") => Criterion::CreatedBefore(parse_date(operand)?),
+            ("created", ">") => Criterion::CreatedAfter(parse_date(operand)?),
+            _ => return Err(format!("Invalid criterion {}. Must be priority=x, title~text, status=x, created<date or created>date", clause).into())
+        };
+        criteria.push(criterion);
+    }
+    if criteria.is_empty() {
+        return Err("The where option cannot be empty".into());
+    }
+    Ok(criteria)
+}
+
+// How a deletion is confirmed, --yes skips the question and --dry-run only prints the todos
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConfirmMode {
+    Ask,
+    Yes,
+    DryRun
+}
+
+impl ConfirmMode {
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<ConfirmMode, Box<dyn Error>> {
+        let options = command_result.get_options();
+        match (options.contains_key("yes"), options.contains_key("dry-run")) {
+            (true, true) => Err("The options --yes and --dry-run cannot be used together".into()),
+            (true, false) => Ok(ConfirmMode::Yes),
+            (false, true) => Ok(ConfirmMode::DryRun),
+            (false, false) => Ok(ConfirmMode::Ask)
+        }
+    }
+}
+
+fn print_affected_todos(todos: &[Todo], indexes: &[usize]) {
+    for index in indexes {
+        let todo = &todos[*index];
+        println!("    {:<3} {} ({})", todo.get_id(), todo.get_title(), todo.get_priority());
+    }
+}
+
+// Print the todos affected by an action and ask for a single confirmation.
+// The question fails instead of waiting when the input isn't a terminal (scripts, cron).
+pub fn confirm_action(action: &str, todos: &[Todo], indexes: &[usize]) -> Result<bool, Box<dyn Error>> {
+    if !termion::is_tty(&io::stdin()) {
+        return Err("Unable to ask for a confirmation, the input isn't a terminal. Use --yes to confirm".into());
+    }
+    println!("The following {} todo(s) will be {}:", indexes.len(), action);
+    print_affected_todos(todos, indexes);
+    let answer = Question::new("Are you sure? (y/n)")
+                             .yes_no()
+                             .until_acceptable()
+                             .ask();
+    Ok(answer.unwrap_or(Answer::NO) == Answer::YES)
+}
+
+// Confirm the deletion of todos, --dry-run is suggested as well when the input isn't a terminal
+pub fn confirm_deletion(todos: &[Todo], indexes: &[usize], mode: ConfirmMode) -> Result<bool, Box<dyn Error>> {
+    match mode {
+        ConfirmMode::Yes => Ok(true),
+        ConfirmMode::DryRun => {
+            println!("The following {} todo(s) would be deleted:", indexes.len());
+            print_affected_todos(todos, indexes);
+            Ok(false)
+        },
+        ConfirmMode::Ask if !termion::is_tty(&io::stdin()) =>
+            Err("Unable to ask for a confirmation, the input isn't a terminal. Use --yes to delete or --dry-run to preview".into()),
+        ConfirmMode::Ask => confirm_action("deleted", todos, indexes)
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, Box<dyn Error>> {
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Ok(date),
+        Err(_) => Err(format!("Invalid date {}. Must be in the format yyyy-mm-dd", value).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::{Command, CommandResult, Priority};
+    use crate::todo::Todo;
+    use crate::todo_selection::{parse_criteria, parse_ids, Criterion, TodoSelection};
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::collections::HashMap;
+
+    fn get_sample_todos() -> Vec<Todo> {
+        let mut todos = vec![
+            Todo::new(1, "Write the report", Priority::High, Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap()).unwrap(),
+            Todo::new(2, "Review the report", Priority::Low, Utc.with_ymd_and_hms(2026, 2, 10, 12, 0, 0).unwrap()).unwrap(),
+            Todo::new(3, "Buy milk", Priority::Low, Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap()).unwrap(),
+            Todo::new(1, "Old todo", Priority::Low, Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap()).unwrap()
+        ];
+        todos[3].set_completed(true, None);
+        todos
+    }
+
+    #[test]
+    fn parse_ids_with_single_id_return_id() {
+        assert_eq!(vec![1], parse_ids("1").unwrap());
+    }
+
+    #[test]
+    fn parse_ids_with_spaces_commas_and_range_return_ids() {
+        assert_eq!(vec![1, 3, 5, 6, 7, 8, 2, 4], parse_ids("1 3 5-8 2,4").unwrap());
+    }
+
+    #[test]
+    fn parse_ids_with_duplicates_return_unique_ids() {
+        assert_eq!(vec![1, 2, 3], parse_ids("1,2 1-3").unwrap());
+    }
+
+    #[test]
+    fn parse_ids_with_minus_1_return_error() {
+        assert_eq!("invalid digit found in string", parse_ids("-1").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_ids_with_reversed_range_return_error() {
+        assert_eq!("Invalid range 8-5. The start must be lower than the end", parse_ids("8-5").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_ids_with_huge_range_return_error() {
+        assert_eq!("Invalid range 1-4294967295. A range cannot contain more than 1000 ids",
+                   parse_ids("1-4294967295").unwrap_err().to_string());
+        assert_eq!(1000, parse_ids("1-1000").unwrap().len());
+    }
+
+    #[test]
+    fn parse_ids_with_only_commas_return_error() {
+        assert_eq!("Value cannot be empty", parse_ids(",,").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_criteria_with_all_criteria_return_criteria() {
+        let actual = parse_criteria("priority=h, title~report,created<2026-02-01,created>2026-01-01").unwrap();
+        assert_eq!(vec![Criterion::Priority(Priority::High),
+                        Criterion::TitleContains(String::from("report")),
+                        Criterion::CreatedBefore(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()),
+                        Criterion::CreatedAfter(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap())], actual);
+    }
+
+    #[test]
+    fn parse_criteria_with_unknown_field_return_error() {
+        assert_eq!("Invalid criterion color=blue. Must be priority=x, title~text, status=x, created<date or created>date",
+                   parse_criteria("color=blue").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_criteria_with_status_return_completed_criterion() {
+        assert_eq!(vec![Criterion::Completed(true), Criterion::Completed(false)],
+                   parse_criteria("status=Completed,status=open").unwrap());
+    }
+
+    #[test]
+    fn parse_criteria_with_invalid_status_return_error() {
+        assert_eq!("Invalid status value. Must be open or completed", parse_criteria("status=late").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_criteria_with_invalid_date_return_error() {
+        assert_eq!("Invalid date 2026-13-01. Must be in the format yyyy-mm-dd",
+                   parse_criteria("created<2026-13-01").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_criteria_with_empty_return_error() {
+        assert_eq!("The where option cannot be empty", parse_criteria(" ").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn new_from_command_result_with_ids_and_where_return_error() {
+        let command_result = CommandResult::new(Command::Remove, "1", HashMap::from([(String::from("where"), String::from("priority=H"))]));
+        assert_eq!("The where option cannot be combined with a list of ids",
+                   TodoSelection::new_from_command_result(&command_result).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn find_indexes_with_ids_return_open_todos_indexes() {
+        let selection = TodoSelection::Ids(vec![3, 1]);
+        assert_eq!(vec![2, 0], selection.find_indexes(&get_sample_todos()).unwrap());
+    }
+
+    #[test]
+    fn find_indexes_with_missing_ids_return_error() {
+        let selection = TodoSelection::Ids(vec![1, 8, 9]);
+        assert_eq!("Unable to find the todo with id 8, 9", selection.find_indexes(&get_sample_todos()).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn find_indexes_with_criteria_return_matching_open_todos_indexes() {
+        let selection = TodoSelection::Criteria(vec![Criterion::TitleContains(String::from("REPORT")), Criterion::Priority(Priority::Low)]);
+        assert_eq!(vec![1], selection.find_indexes(&get_sample_todos()).unwrap());
+    }
+
+    #[test]
+    fn find_indexes_with_completed_status_return_completed_todos_indexes() {
+        let selection = TodoSelection::Criteria(vec![Criterion::Completed(true)]);
+        assert_eq!(vec![3], selection.find_indexes(&get_sample_todos()).unwrap());
+        let selection = TodoSelection::Criteria(vec![Criterion::Completed(false), Criterion::Priority(Priority::Low)]);
+        assert_eq!(vec![1, 2], selection.find_indexes(&get_sample_todos()).unwrap());
+    }
+
+    #[test]
+    fn find_indexes_with_criteria_ignore_completed_todos() {
+        let selection = TodoSelection::Criteria(vec![Criterion::CreatedBefore(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap())]);
+        assert_eq!("No todo matches the criteria", selection.find_indexes(&get_sample_todos()).unwrap_err().to_string());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::common_structs::Priority;
+use crate::todo::Todo;
+use crate::todo_format::{ImportResult, TodoFormat};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use std::error::Error;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug)]
+pub struct TodoTxtFormat {
+}
+
+impl TodoTxtFormat {
+    fn priority_to_letter(priority: Priority) -> char {
+        match priority {
+            Priority::High => 'A',
+            Priority::Medium => 'B',
+            Priority::Low => 'C'
+        }
+    }
+
+    // Return the priority mapped from a todo.txt letter, and if the letter has an equivalent
+    fn priority_from_letter(letter: char) -> (Priority, bool) {
+        match letter {
+            'A' => (Priority::High, true),
+            'B' => (Priority::Medium, true),
+            'C' => (Priority::Low, true),
+            _ => (Priority::Low, false)
+        }
+    }
+
+    fn format_date(date: DateTime<Local>) -> String {
+        date.format(DATE_FORMAT).to_string()
+    }
+
+    fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+        let date = NaiveDate::parse_from_str(value, DATE_FORMAT).ok()?;
+        Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .earliest()
+            .map(|x| x.with_timezone(&Utc))
+    }
+
+    fn is_date(value: &str) -> bool {
+        value.len() == 10 && NaiveDate::parse_from_str(value, DATE_FORMAT).is_ok()
+    }
+
+    fn parse_priority(value: &str) -> Option<char> {
+        let chars: Vec<char> = value.chars().collect();
+        if chars.len() == 3 && chars[0] == '(' && chars[2] == ')' && chars[1].is_ascii_uppercase() {
+            Some(chars[1])
+        }
+        else {
+            None
+        }
+    }
+
+    fn parse_line(line: &str, line_number: usize, result: &mut ImportResult) {
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+        let completed = words.first() == Some(&"x");
+        if completed {
+            words.remove(0);
+        }
+        let mut priority_letter = match words.first().and_then(|x| TodoTxtFormat::parse_priority(x)) {
+            Some(letter) => {
+                words.remove(0);
+                Some(letter)
+            },
+            None => None
+        };
+        let mut when_completed = None;
+        let mut when_created = None;
+        if completed && words.len() > 1 && TodoTxtFormat::is_date(words[0]) && TodoTxtFormat::is_date(words[1]) {
+            when_completed = TodoTxtFormat::parse_date(words[0]);
+            when_created = TodoTxtFormat::parse_date(words[1]);
+            words.drain(..2);
+        }
+        else if !words.is_empty() && TodoTxtFormat::is_date(words[0]) {
+            let date = TodoTxtFormat::parse_date(words[0]);
+            if completed {
+                when_completed = date;
+            }
+            else {
+                when_created = date;
+            }
+            words.remove(0);
+        }
+        //Completed tasks usually keep their priority as a pri:x tag
+        if let Some(position) = words.iter().position(|x| x.starts_with("pri:") && x.len() == 5) {
+            if priority_letter.is_none() {
+                priority_letter = words[position].chars().nth(4).map(|x| x.to_ascii_uppercase());
+            }
+            words.remove(position);
+        }
+        let (priority, priority_mapped) = match priority_letter {
+            Some(letter) => TodoTxtFormat::priority_from_letter(letter),
+            None => (Priority::Low, true)
+        };
+        let title = words.join(" ");
+        let mut todo = match Todo::new(0, &title, priority, when_created.unwrap_or_else(Utc::now)) {
+            Ok(todo) => todo,
+            Err(_) => {
+                result.add_issue(line_number, "The line has no description and has been skipped", line);
+                return;
+            }
+        };
+        if !priority_mapped {
+            result.add_issue(line_number, &format!("The priority ({}) has no equivalent and has been imported as Low", priority_letter.unwrap()), line);
+        }
+        if completed {
+            todo.set_completed(true, Some(when_completed.unwrap_or_else(Utc::now)));
+        }
+        result.add_todo(todo);
+    }
+}
+
+impl TodoFormat for TodoTxtFormat {
+    fn export(&self, todos: &[Todo]) -> Result<String, Box<dyn Error>> {
+        let mut content = String::new();
+        for todo in todos {
+            let priority = TodoTxtFormat::priority_to_letter(todo.get_priority());
+            let created = TodoTxtFormat::format_date(todo.get_when_created_in_localtime());
+            if todo.get_completed() {
+                let completed = TodoTxtFormat::format_date(todo.get_when_completed_in_localtime());
+                content.push_str(&format!("x {} {} {} pri:{}\n", completed, created, todo.get_title(), priority));
+            }
+            else {
+                content.push_str(&format!("({}) {} {}\n", priority, created, todo.get_title()));
+            }
+        }
+        Ok(content)
+    }
+
+    fn import(&self, content: &str) -> Result<ImportResult, Box<dyn Error>> {
+        let mut result = ImportResult::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            TodoTxtFormat::parse_line(line, index + 1, &mut result);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::todo::Todo;
+    use crate::todo_format::TodoFormat;
+    use crate::todotxt_format::TodoTxtFormat;
+    use chrono::{Local, TimeZone, Utc};
+
+    fn local_date(year: i32, month: u32, day: u32) -> chrono::DateTime<Utc> {
+        Local.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn import_with_open_todo_with_priority_and_date_return_todo() {
+        let result = TodoTxtFormat {}.import("(A) 2026-10-01 Call mom +family @phone\n").unwrap();
+        assert_eq!(1, result.get_todos().len());
+        let todo = &result.get_todos()[0];
+        assert_eq!("Call mom +family @phone", todo.get_title());
+        assert_eq!(Priority::High, todo.get_priority());
+        assert_eq!(local_date(2026, 10, 1), todo.get_when_created_in_localtime());
+        assert!(!todo.get_completed());
+        assert!(result.get_issues().is_empty());
+    }
+
+    #[test]
+    fn import_with_completed_todo_with_dates_return_completed_todo() {
+        let result = TodoTxtFormat {}.import("x 2026-10-03 2026-10-01 Pay the bills pri:B").unwrap();
+        let todo = &result.get_todos()[0];
+        assert_eq!("Pay the bills", todo.get_title());
+        assert_eq!(Priority::Medium, todo.get_priority());
+        assert!(todo.get_completed());
+        assert_eq!(local_date(2026, 10, 3), todo.get_when_completed_in_localtime());
+        assert_eq!(local_date(2026, 10, 1), todo.get_when_created_in_localtime());
+    }
+
+    #[test]
+    fn import_with_completed_todo_with_completion_date_only_return_completed_todo() {
+        let result = TodoTxtFormat {}.import("x 2026-10-03 Pay the bills").unwrap();
+        let todo = &result.get_todos()[0];
+        assert_eq!("Pay the bills", todo.get_title());
+        assert_eq!(Priority::Low, todo.get_priority());
+        assert_eq!(local_date(2026, 10, 3), todo.get_when_completed_in_localtime());
+    }
+
+    #[test]
+    fn import_with_unmapped_priority_return_low_and_issue() {
+        let result = TodoTxtFormat {}.import("(D) Someday task").unwrap();
+        assert_eq!(Priority::Low, result.get_todos()[0].get_priority());
+        assert_eq!(vec!["Line 1: The priority (D) has no equivalent and has been imported as Low ((D) Someday task)"], *result.get_issues());
+    }
+
+    #[test]
+    fn import_with_empty_description_skip_line_and_report_issue() {
+        let result = TodoTxtFormat {}.import("Buy milk\n\n(A) 2026-10-01\n").unwrap();
+        assert_eq!(1, result.get_todos().len());
+        assert_eq!(vec!["Line 3: The line has no description and has been skipped ((A) 2026-10-01)"], *result.get_issues());
+    }
+
+    #[test]
+    fn export_return_todotxt_lines() {
+        let mut completed = Todo::new(2, "Pay the bills", Priority::Medium, local_date(2026, 10, 1)).unwrap();
+        completed.set_completed(true, Some(local_date(2026, 10, 3)));
+        let todos = vec![Todo::new(1, "Call mom +family", Priority::High, local_date(2026, 10, 1)).unwrap(), completed];
+        assert_eq!("(A) 2026-10-01 Call mom +family\nx 2026-10-03 2026-10-01 Pay the bills pri:B\n",
+                   TodoTxtFormat {}.export(&todos).unwrap());
+    }
+
+    #[test]
+    fn export_then_import_return_same_todos() {
+        let todos = vec![Todo::new(1, "Write the report", Priority::Low, local_date(2026, 10, 1)).unwrap()];
+        let content = TodoTxtFormat {}.export(&todos).unwrap();
+        let result = TodoTxtFormat {}.import(&content).unwrap();
+        assert_eq!(todos[0].get_title(), result.get_todos()[0].get_title());
+        assert_eq!(todos[0].get_priority(), result.get_todos()[0].get_priority());
+        assert_eq!(todos[0].get_when_created_in_localtime(), result.get_todos()[0].get_when_created_in_localtime());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::config::get_config;
+use crate::data_service::{empty_trash, find_todos_to_empty, read_trash, restore_from_trash};
+use crate::duration_format::parse_age;
+use crate::output::{reset_color, reset_style, success_color, underline};
+use crate::todo::Todo;
+use crate::todo_selection::{confirm_deletion, ConfirmMode};
+use chrono::{Duration, Utc};
+use std::error::Error;
+
+#[derive(Debug, PartialEq)]
+pub enum TrashAction {
+    List,
+    Restore(u32),
+    Empty(Option<Duration>, ConfirmMode)
+}
+
+#[derive(Debug)]
+pub struct TrashCommand {
+    action: TrashAction
+}
+
+impl TrashCommand {
+    const ID_WIDTH: usize = 3;
+    const TITLE_WIDTH: usize = 50;
+    const PRIORITY_WIDTH: usize = 10;
+
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<TrashCommand, Box<dyn Error>> {
+        let value = command_result.get_value().trim();
+        let (action_name, rest) = value.split_once(char::is_whitespace).unwrap_or((value, ""));
+        let options = command_result.get_options();
+        let older_than = options.get("older-than");
+        let action = match (action_name.to_lowercase().as_str(), rest.trim()) {
+            (_, _) if !action_name.eq_ignore_ascii_case("empty") && older_than.is_some() =>
+                return Err("The option --older-than is only used by trash empty".into()),
+            (_, _) if !action_name.eq_ignore_ascii_case("empty") && (options.contains_key("yes") || options.contains_key("dry-run")) =>
+                return Err("The options --yes and --dry-run are only used by trash empty".into()),
+            ("" | "list", "") => TrashAction::List,
+            ("restore", id) if !id.is_empty() => TrashAction::Restore(id.parse::<u32>()?),
+            ("empty", "") => TrashAction::Empty(older_than.map(|x| parse_age(x)).transpose()?,
+                                                ConfirmMode::new_from_command_result(command_result)?),
+            ("list" | "restore" | "empty", _) =>
+                return Err("Invalid arguments. Usage: trash list, trash restore id or trash empty [--older-than=x]".into()),
+            _ => return Err("Invalid action. Must be list, restore or empty".into())
+        };
+        Ok(TrashCommand { action })
+    }
+}
+
+impl ExecutableCommand for TrashCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        match self.action {
+            TrashAction::List => {
+                let mut trash = read_trash()?;
+                if trash.is_empty() {
+                    println!("The trash is empty");
+                    return Ok(());
+                }
+                trash.sort_by_key(|x| std::cmp::Reverse(x.get_when_deleted()));
+                println!("{}{id:<widthi$} {title:<widtht$} {priority:<widthp$}Removed on{}", underline(), reset_style(),
+                         id="ID", widthi=TrashCommand::ID_WIDTH, title="Title", widtht=TrashCommand::TITLE_WIDTH,
+                         priority="Priority", widthp=TrashCommand::PRIORITY_WIDTH);
+                for trashed_todo in &trash {
+                    let todo = trashed_todo.get_todo();
+                    let title: String = todo.get_title().chars().take(TrashCommand::TITLE_WIDTH).collect();
+                    println!("{id:<widthi$} {title:<widtht$} {priority:<widthp$}{deleted}",
+                             id=todo.get_id(), widthi=TrashCommand::ID_WIDTH, title=title, widtht=TrashCommand::TITLE_WIDTH,
+                             priority=todo.get_priority().to_string(), widthp=TrashCommand::PRIORITY_WIDTH,
+                             deleted=trashed_todo.get_when_deleted_in_localtime().format("%Y-%m-%d %H:%M"));
+                }
+                if let Some(expiry) = get_config().get_trash_expiry() {
+                    println!("The todos are deleted from the trash after {} day(s)", expiry.num_days());
+                }
+            },
+            TrashAction::Restore(id) => {
+                let todo = restore_from_trash(id)?;
+                println!("{}The todo {} has been restored with id {}!{}", success_color(), todo.get_title(), todo.get_id(), reset_color());
+            },
+            TrashAction::Empty(older_than, confirm_mode) => {
+                let trash = read_trash()?;
+                let indexes = find_todos_to_empty(&trash, Utc::now(), older_than);
+                if indexes.is_empty() {
+                    println!("There is no todo to delete from the trash");
+                    return Ok(());
+                }
+                let todos: Vec<Todo> = trash.iter().map(|x| x.get_todo().clone()).collect();
+                if confirm_deletion(&todos, &indexes, confirm_mode)? {
+                    empty_trash(trash, &indexes)?;
+                    println!("{}{} todo(s) deleted from the trash!{}", success_color(), indexes.len(), reset_color());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::{Command, CommandResult};
+    use crate::todo_selection::ConfirmMode;
+    use crate::trash_command::{TrashAction, TrashCommand};
+    use chrono::Duration;
+
+    fn new_trash_command(value: &str, options: &[(&str, &str)]) -> Result<TrashCommand, Box<dyn std::error::Error>> {
+        let options = options.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        TrashCommand::new_from_command_result(&CommandResult::new(Command::Trash, value, options))
+    }
+
+    #[test]
+    fn new_from_command_result_with_actions_return_action() {
+        assert_eq!(TrashAction::List, new_trash_command("", &[]).unwrap().action);
+        assert_eq!(TrashAction::List, new_trash_command("List", &[]).unwrap().action);
+        assert_eq!(TrashAction::Restore(3), new_trash_command("restore 3", &[]).unwrap().action);
+        assert_eq!(TrashAction::Empty(None, ConfirmMode::Ask), new_trash_command("empty", &[]).unwrap().action);
+        assert_eq!(TrashAction::Empty(Some(Duration::days(14)), ConfirmMode::Yes),
+                   new_trash_command("empty", &[("older-than", "2w"), ("yes", "")]).unwrap().action);
+        assert_eq!(TrashAction::Empty(None, ConfirmMode::DryRun), new_trash_command("empty", &[("dry-run", "")]).unwrap().action);
+    }
+
+    #[test]
+    fn new_from_command_result_with_invalid_arguments_return_error() {
+        assert_eq!("Invalid arguments. Usage: trash list, trash restore id or trash empty [--older-than=x]",
+                   new_trash_command("restore", &[]).err().unwrap().to_string());
+        assert_eq!("Invalid action. Must be list, restore or empty", new_trash_command("purge", &[]).err().unwrap().to_string());
+        assert_eq!("The option --older-than is only used by trash empty",
+                   new_trash_command("list", &[("older-than", "30d")]).err().unwrap().to_string());
+        assert_eq!("The options --yes and --dry-run are only used by trash empty",
+                   new_trash_command("restore 3", &[("yes", "")]).err().unwrap().to_string());
+        assert_eq!("invalid digit found in string", new_trash_command("restore abc", &[]).err().unwrap().to_string());
+    }
+}
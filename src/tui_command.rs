@@ -0,0 +1,493 @@
+use crate::common_structs::{ExecutableCommand, Priority};
+use crate::config::get_config;
+use crate::data_service::{find_next_available_todo_id, move_to_trash, read_all_todos, write_todos};
+use crate::fetch_command::get_todo_details;
+use crate::todo::Todo;
+use chrono::Utc;
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::screen::AlternateScreen;
+use termion::{clear, cursor, style};
+use std::error::Error;
+use std::io::{self, Write};
+
+const ID_WIDTH: usize = 4;
+const PRIORITY_WIDTH: usize = 10;
+const DETAILS_HEIGHT: usize = 8;
+const HELP: &str = "j/k move  a add  e edit  d done  x remove  p priority  / filter  q quit";
+
+#[derive(Debug, PartialEq)]
+pub enum Mode {
+    Normal,
+    Filter,
+    Add,
+    Edit,
+    ConfirmRemove
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TuiEvent {
+    Nothing,
+    TodosChanged,
+    Quit
+}
+
+// The todos displayed by the tui with the selection, the filter and the text being typed
+#[derive(Debug)]
+pub struct TuiState {
+    todos: Vec<Todo>,
+    // The indexes in todos of the open todos matching the filter, in display order
+    visible: Vec<usize>,
+    selected: usize,
+    scroll: usize,
+    list_height: usize,
+    filter: String,
+    mode: Mode,
+    input: String,
+    message: String,
+    // The todos removed since the last write, moved to the trash
+    removed: Vec<Todo>
+}
+
+impl TuiState {
+    pub fn new(todos: Vec<Todo>) -> TuiState {
+        let mut state = TuiState { todos, visible: vec![], selected: 0, scroll: 0, list_height: 10,
+                                   filter: String::new(), mode: Mode::Normal, input: String::new(), message: String::new(),
+                                   removed: vec![] };
+        state.refresh_visible();
+        state
+    }
+
+    pub fn get_todos(&self) -> &Vec<Todo> {
+        &self.todos
+    }
+
+    pub fn take_removed_todos(&mut self) -> Vec<Todo> {
+        std::mem::take(&mut self.removed)
+    }
+
+    fn get_selected_todo(&self) -> Option<&Todo> {
+        self.visible.get(self.selected).map(|x| &self.todos[*x])
+    }
+
+    // Sort the open todos like the list command and keep the selection on the same todo when possible
+    fn refresh_visible(&mut self) {
+        let selected_uuid = self.get_selected_todo().map(|x| x.get_uuid().to_string());
+        let filter = self.filter.to_lowercase();
+        let mut visible: Vec<usize> = (0..self.todos.len())
+            .filter(|x| !self.todos[*x].get_completed() && self.todos[*x].get_title().to_lowercase().contains(&filter))
+            .collect();
+        visible.sort_by_key(|x| (self.todos[*x].get_priority(), self.todos[*x].get_when_created_in_localtime()));
+        self.visible = visible;
+        if let Some(uuid) = selected_uuid {
+            if let Some(position) = self.visible.iter().position(|x| self.todos[*x].get_uuid() == uuid) {
+                self.selected = position;
+            }
+        }
+        self.selected = self.selected.min(self.visible.len().saturating_sub(1));
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let last = self.visible.len().saturating_sub(1) as isize;
+        self.selected = (self.selected as isize + offset).clamp(0, last) as usize;
+    }
+
+    pub fn handle_key(&mut self, key: Key) -> TuiEvent {
+        self.message.clear();
+        match self.mode {
+            Mode::Normal => self.handle_normal_key(key),
+            Mode::Filter => {
+                match key {
+                    Key::Char('\n') => self.mode = Mode::Normal,
+                    Key::Esc => {
+                        self.filter.clear();
+                        self.mode = Mode::Normal;
+                    },
+                    Key::Backspace => {
+                        self.filter.pop();
+                    },
+                    Key::Char(c) if !c.is_control() => self.filter.push(c),
+                    _ => ()
+                }
+                self.refresh_visible();
+                TuiEvent::Nothing
+            },
+            Mode::Add | Mode::Edit => match key {
+                Key::Char('\n') => self.apply_input(),
+                Key::Esc => {
+                    self.mode = Mode::Normal;
+                    TuiEvent::Nothing
+                },
+                Key::Backspace => {
+                    self.input.pop();
+                    TuiEvent::Nothing
+                },
+                Key::Char(c) if !c.is_control() => {
+                    self.input.push(c);
+                    TuiEvent::Nothing
+                },
+                _ => TuiEvent::Nothing
+            },
+            Mode::ConfirmRemove => {
+                self.mode = Mode::Normal;
+                match key {
+                    Key::Char('y') | Key::Char('Y') => self.remove_selected(),
+                    _ => TuiEvent::Nothing
+                }
+            }
+        }
+    }
+
+    fn handle_normal_key(&mut self, key: Key) -> TuiEvent {
+        match key {
+            Key::Char('q') => return TuiEvent::Quit,
+            Key::Esc if self.filter.is_empty() => return TuiEvent::Quit,
+            Key::Esc => {
+                self.filter.clear();
+                self.refresh_visible();
+            },
+            Key::Up | Key::Char('k') => self.move_selection(-1),
+            Key::Down | Key::Char('j') => self.move_selection(1),
+            Key::PageUp => self.move_selection(-(self.list_height as isize)),
+            Key::PageDown => self.move_selection(self.list_height as isize),
+            Key::Home => self.selected = 0,
+            Key::End => self.selected = self.visible.len().saturating_sub(1),
+            Key::Char('/') => self.mode = Mode::Filter,
+            Key::Char('a') => {
+                self.input.clear();
+                self.mode = Mode::Add;
+            },
+            Key::Char('e') => if let Some(todo) = self.get_selected_todo() {
+                self.input = todo.get_title().to_string();
+                self.mode = Mode::Edit;
+            },
+            Key::Char('x') | Key::Delete if self.get_selected_todo().is_some() => self.mode = Mode::ConfirmRemove,
+            Key::Char('d') => return self.complete_selected(),
+            Key::Char('p') => return self.cycle_selected_priority(),
+            _ => ()
+        }
+        TuiEvent::Nothing
+    }
+
+    // Add a todo or rename the selected one with the text typed
+    fn apply_input(&mut self) -> TuiEvent {
+        let title = self.input.trim().to_string();
+        if title.is_empty() {
+            self.message = String::from("The title cannot be empty");
+            return TuiEvent::Nothing;
+        }
+        let event = match self.mode {
+            Mode::Add => {
+                // The add.priority setting of the config file is used as for the add command
+                let priority = get_config().get_default_option("add", "priority").and_then(Priority::from_string).unwrap_or(Priority::Low);
+                let id = find_next_available_todo_id(&self.todos);
+                match Todo::new(id, &title, priority, Utc::now()) {
+                    Ok(todo) => {
+                        self.todos.push(todo);
+                        self.refresh_visible();
+                        if let Some(position) = self.visible.iter().position(|x| *x == self.todos.len() - 1) {
+                            self.selected = position;
+                        }
+                        self.message = format!("The todo {} has been added with id {}!", title, id);
+                        TuiEvent::TodosChanged
+                    },
+                    Err(e) => {
+                        self.message = e.to_string();
+                        TuiEvent::Nothing
+                    }
+                }
+            },
+            _ => match self.visible.get(self.selected) {
+                Some(index) => match self.todos[*index].set_title(&title) {
+                    Ok(_) => {
+                        self.message = format!("The todo {} has been updated!", self.todos[*index].get_id());
+                        self.refresh_visible();
+                        TuiEvent::TodosChanged
+                    },
+                    Err(e) => {
+                        self.message = e.to_string();
+                        TuiEvent::Nothing
+                    }
+                },
+                None => TuiEvent::Nothing
+            }
+        };
+        self.mode = Mode::Normal;
+        event
+    }
+
+    fn complete_selected(&mut self) -> TuiEvent {
+        let index = match self.visible.get(self.selected) {
+            Some(index) => *index,
+            None => return TuiEvent::Nothing
+        };
+        let todo = &mut self.todos[index];
+        if todo.is_tracking() && todo.stop_tracking(Utc::now()).is_err() {
+            return TuiEvent::Nothing;
+        }
+        todo.set_completed(true, None);
+        self.message = format!("The todo {} has been completed!", todo.get_title());
+        self.refresh_visible();
+        TuiEvent::TodosChanged
+    }
+
+    fn remove_selected(&mut self) -> TuiEvent {
+        let index = match self.visible.get(self.selected) {
+            Some(index) => *index,
+            None => return TuiEvent::Nothing
+        };
+        let todo = self.todos.remove(index);
+        self.message = format!("The todo with id {} has been moved to the trash!", todo.get_id());
+        self.removed.push(todo);
+        // The indexes of the visible todos are outdated, the selection stays on the same row
+        self.visible.clear();
+        self.refresh_visible();
+        TuiEvent::TodosChanged
+    }
+
+    // High -> Medium -> Low -> High
+    fn cycle_selected_priority(&mut self) -> TuiEvent {
+        let index = match self.visible.get(self.selected) {
+            Some(index) => *index,
+            None => return TuiEvent::Nothing
+        };
+        let priority = match self.todos[index].get_priority() {
+            Priority::High => Priority::Medium,
+            Priority::Medium => Priority::Low,
+            Priority::Low => Priority::High
+        };
+        self.todos[index].set_priority(priority);
+        self.refresh_visible();
+        TuiEvent::TodosChanged
+    }
+
+    // Return the lines of the screen: the header, the list, the details of the selected todo,
+    // the prompt or the last message and the keys
+    pub fn render(&mut self, width: usize, height: usize) -> Vec<String> {
+        self.list_height = height.saturating_sub(DETAILS_HEIGHT + 5).max(1);
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        }
+        if self.selected >= self.scroll + self.list_height {
+            self.scroll = self.selected + 1 - self.list_height;
+        }
+        let mut header = format!("Beaver todos - {} todo(s)", self.visible.len());
+        if !self.filter.is_empty() {
+            header.push_str(&format!(" matching \"{}\"", self.filter));
+        }
+        let mut lines = vec![format!("{}{}{}", style::Bold, fit(&header, width), style::Reset),
+                             format!("{}{}{}", style::Underline, fit(&format!("{:<id$}{:<priority$}{}", "ID", "Priority", "Title",
+                                                                                  id=ID_WIDTH, priority=PRIORITY_WIDTH), width), style::Reset)];
+        for position in self.scroll..(self.scroll + self.list_height) {
+            let line = match self.visible.get(position) {
+                Some(index) => {
+                    let todo = &self.todos[*index];
+                    let text = fit(&format!("{:<id$}{:<priority$}{}", todo.get_id(), todo.get_priority().to_string(), todo.get_title(),
+                                            id=ID_WIDTH, priority=PRIORITY_WIDTH), width);
+                    match position == self.selected {
+                        true => format!("{}{:<width$}{}", style::Invert, text, style::Reset, width=width),
+                        false => text
+                    }
+                },
+                None => String::new()
+            };
+            lines.push(line);
+        }
+        lines.push(fit(&"\u{2500}".repeat(width), width));
+        let mut details: Vec<String> = match self.get_selected_todo() {
+            Some(todo) => get_todo_details(todo, Utc::now()).iter().map(|(label, value)| fit(&format!("{}: {}", label, value), width)).collect(),
+            None => vec![String::from("Your todo list is empty! :)")]
+        };
+        details.resize(DETAILS_HEIGHT, String::new());
+        lines.extend(details);
+        let status = match self.mode {
+            Mode::Filter => format!("Filter: {}", self.filter),
+            Mode::Add => format!("Add: {}", self.input),
+            Mode::Edit => format!("Edit: {}", self.input),
+            Mode::ConfirmRemove => format!("Remove the todo {}? (y/n)", self.get_selected_todo().map(|x| x.get_title()).unwrap_or("")),
+            Mode::Normal => self.message.clone()
+        };
+        lines.push(fit(&status, width));
+        lines.push(fit(HELP, width));
+        lines.truncate(height);
+        lines
+    }
+}
+
+// Cut the text to the width of the screen
+fn fit(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+#[derive(Debug)]
+pub struct TuiCommand {
+}
+
+impl TuiCommand {
+    fn draw(screen: &mut impl Write, state: &mut TuiState) -> Result<(), Box<dyn Error>> {
+        // Some terminals report a size of 0
+        let (width, height) = termion::terminal_size().ok().filter(|(width, height)| *width > 0 && *height > 0).unwrap_or((80, 24));
+        for (row, line) in state.render(usize::from(width), usize::from(height)).iter().enumerate() {
+            write!(screen, "{}{}{}", cursor::Goto(1, row as u16 + 1), clear::CurrentLine, line)?;
+        }
+        screen.flush()?;
+        Ok(())
+    }
+
+    fn run(screen: &mut impl Write, state: &mut TuiState) -> Result<(), Box<dyn Error>> {
+        TuiCommand::draw(screen, state)?;
+        for key in io::stdin().keys() {
+            match state.handle_key(key?) {
+                TuiEvent::Quit => break,
+                TuiEvent::TodosChanged => {
+                    let removed_todos = state.take_removed_todos();
+                    if !removed_todos.is_empty() {
+                        move_to_trash(&removed_todos)?;
+                    }
+                    write_todos(state.get_todos())?
+                },
+                TuiEvent::Nothing => ()
+            }
+            TuiCommand::draw(screen, state)?;
+        }
+        Ok(())
+    }
+}
+
+impl ExecutableCommand for TuiCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        if !termion::is_tty(&io::stdout()) {
+            return Err("The tui command must be run in a terminal".into());
+        }
+        let mut state = TuiState::new(read_all_todos()?);
+        let mut screen = AlternateScreen::from(io::stdout().into_raw_mode()?);
+        write!(screen, "{}{}", cursor::Hide, clear::All)?;
+        let result = TuiCommand::run(&mut screen, &mut state);
+        write!(screen, "{}", cursor::Show)?;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_structs::Priority;
+    use crate::todo::Todo;
+    use crate::tui_command::{Mode, TuiEvent, TuiState};
+    use chrono::{Duration, Utc};
+    use termion::event::Key;
+
+    fn new_state() -> TuiState {
+        let now = Utc::now();
+        let mut completed = Todo::new(4, "Pay the bills", Priority::High, now).unwrap();
+        completed.set_completed(true, None);
+        TuiState::new(vec![Todo::new(1, "Write the report", Priority::Low, now).unwrap(),
+                           Todo::new(2, "Call mom", Priority::High, now).unwrap(),
+                           Todo::new(3, "Review the report", Priority::Low, now + Duration::minutes(1)).unwrap(),
+                           completed])
+    }
+
+    fn type_text(state: &mut TuiState, text: &str) {
+        for c in text.chars() {
+            state.handle_key(Key::Char(c));
+        }
+    }
+
+    fn get_selected_id(state: &TuiState) -> u32 {
+        state.get_selected_todo().unwrap().get_id()
+    }
+
+    #[test]
+    fn new_show_open_todos_sorted_by_priority() {
+        let state = new_state();
+        let ids: Vec<u32> = state.visible.iter().map(|x| state.todos[*x].get_id()).collect();
+        assert_eq!(vec![2, 1, 3], ids);
+    }
+
+    #[test]
+    fn handle_key_with_navigation_keys_stay_in_list() {
+        let mut state = new_state();
+        state.handle_key(Key::Up);
+        assert_eq!(2, get_selected_id(&state));
+        state.handle_key(Key::Char('j'));
+        state.handle_key(Key::Down);
+        state.handle_key(Key::Down);
+        assert_eq!(3, get_selected_id(&state));
+        state.handle_key(Key::Home);
+        assert_eq!(2, get_selected_id(&state));
+    }
+
+    #[test]
+    fn handle_key_with_filter_show_matching_todos_while_typing() {
+        let mut state = new_state();
+        state.handle_key(Key::Char('/'));
+        type_text(&mut state, "REPORT");
+        assert_eq!(2, state.visible.len());
+        state.handle_key(Key::Char('\n'));
+        assert_eq!(Mode::Normal, state.mode);
+        assert_eq!(2, state.visible.len());
+        state.handle_key(Key::Esc);
+        assert_eq!(3, state.visible.len());
+        assert_eq!(TuiEvent::Quit, state.handle_key(Key::Esc));
+    }
+
+    #[test]
+    fn handle_key_with_add_and_edit_change_todos() {
+        let mut state = new_state();
+        state.handle_key(Key::Char('a'));
+        type_text(&mut state, "Buy milk");
+        assert_eq!(TuiEvent::TodosChanged, state.handle_key(Key::Char('\n')));
+        assert_eq!("Buy milk", state.get_selected_todo().unwrap().get_title());
+        assert_eq!(4, get_selected_id(&state));
+        state.handle_key(Key::Char('e'));
+        state.handle_key(Key::Backspace);
+        type_text(&mut state, "k and bread");
+        assert_eq!(TuiEvent::TodosChanged, state.handle_key(Key::Char('\n')));
+        assert_eq!("Buy milk and bread", state.get_selected_todo().unwrap().get_title());
+        state.handle_key(Key::Char('a'));
+        assert_eq!(TuiEvent::Nothing, state.handle_key(Key::Char('\n')));
+        assert_eq!("The title cannot be empty", state.message);
+    }
+
+    #[test]
+    fn handle_key_with_done_priority_and_remove_change_selected_todo() {
+        let mut state = new_state();
+        assert_eq!(TuiEvent::TodosChanged, state.handle_key(Key::Char('d')));
+        assert!(state.todos[1].get_completed());
+        assert_eq!(2, state.visible.len());
+        assert_eq!(TuiEvent::TodosChanged, state.handle_key(Key::Char('p')));
+        assert_eq!(Priority::High, state.todos[0].get_priority());
+        assert_eq!(1, get_selected_id(&state));
+        state.handle_key(Key::Char('x'));
+        assert_eq!(TuiEvent::Nothing, state.handle_key(Key::Char('n')));
+        assert_eq!(4, state.todos.len());
+        state.handle_key(Key::Char('x'));
+        assert_eq!(TuiEvent::TodosChanged, state.handle_key(Key::Char('y')));
+        assert_eq!(3, state.todos.len());
+        assert_eq!(1, state.take_removed_todos()[0].get_id());
+        assert_eq!(3, get_selected_id(&state));
+    }
+
+    #[test]
+    fn render_show_list_details_and_keys() {
+        let mut state = new_state();
+        state.handle_key(Key::Down);
+        let lines = state.render(40, 20);
+        assert_eq!(20, lines.len());
+        assert!(lines[0].contains("Beaver todos - 3 todo(s)"));
+        assert_eq!("2   High      Call mom", lines[2]);
+        assert!(lines[3].contains("1   Low       Write the report"));
+        assert_eq!("Title: Write the report", lines[10]);
+        assert!(lines[19].starts_with("j/k move"));
+    }
+
+    #[test]
+    fn render_scroll_to_selected_todo() {
+        let mut state = new_state();
+        state.render(40, 14);
+        state.handle_key(Key::End);
+        let lines = state.render(40, 14);
+        assert_eq!(2, state.scroll);
+        assert!(lines[2].contains("Review the report"));
+    }
+}
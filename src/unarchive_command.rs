@@ -0,0 +1,27 @@
+use crate::common_structs::{CommandResult, ExecutableCommand};
+use crate::data_service::unarchive_todo;
+use crate::output::{reset_color, success_color};
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct UnarchiveCommand {
+    uuid: String
+}
+
+impl UnarchiveCommand {
+    pub fn new_from_command_result(command_result: &CommandResult) -> Result<UnarchiveCommand, Box<dyn Error>> {
+        let uuid = command_result.get_value().trim();
+        if uuid.is_empty() {
+            return Err("Value cannot be empty".into());
+        }
+        Ok(UnarchiveCommand { uuid: uuid.to_string() })
+    }
+}
+
+impl ExecutableCommand for UnarchiveCommand {
+    fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let todo = unarchive_todo(&self.uuid)?;
+        println!("{}The todo {} has been moved back to the todo list!{}", success_color(), todo.get_title(), reset_color());
+        Ok(())
+    }
+}